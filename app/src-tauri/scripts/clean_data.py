@@ -17,6 +17,9 @@ import time
 
 from i18n import t, init_i18n, add_lang_arg
 
+# 2 added --chunk-size and --strategy.
+PROTOCOL_VERSION = "2"
+
 
 def emit(event_type, **kwargs):
     """Emit a JSON event line to stdout for Rust to parse."""
@@ -213,10 +216,22 @@ def segment_fixed_length(text, max_tokens=1024, overlap_ratio=0.12):
     return segments
 
 
-def segment_with_strategy(text, ext, max_tokens=1024):
+CLEAN_STRATEGIES = ("auto", "paragraph_balanced", "heading_split", "fixed")
+
+
+def segment_with_strategy(text, ext, max_tokens=1024, forced="auto"):
     ext = ext.lower()
 
-    if ext in MARKDOWN_EXTS:
+    if forced == "paragraph_balanced":
+        strategy = "paragraph_balanced"
+        segments = smart_segment(text, max_tokens=max_tokens)
+    elif forced == "heading_split":
+        strategy = "markdown_recursive"
+        segments = segment_markdown_structured(text, max_tokens=max_tokens)
+    elif forced == "fixed":
+        strategy = "fixed_length"
+        segments = segment_fixed_length(text, max_tokens=max_tokens)
+    elif ext in MARKDOWN_EXTS:
         strategy = "markdown_recursive"
         segments = segment_markdown_structured(text, max_tokens=max_tokens)
     elif ext in CODE_EXTS:
@@ -270,7 +285,8 @@ def read_pdf(path):
         return None
 
 
-def clean_file(input_path, privacy_filter=False, fuzzy_dedup=False, fuzzy_threshold=0.85):
+def clean_file(input_path, privacy_filter=False, fuzzy_dedup=False, fuzzy_threshold=0.85,
+               chunk_size=1024, strategy="auto"):
     """Clean a single file and return cleaned segments."""
     ext = os.path.splitext(input_path)[1].lower()
 
@@ -314,7 +330,7 @@ def clean_file(input_path, privacy_filter=False, fuzzy_dedup=False, fuzzy_thresh
 
     # Rejoin and segment with strategy auto-match
     cleaned_text = "\n\n".join(paragraphs)
-    strategy, segments = segment_with_strategy(cleaned_text, ext, max_tokens=1024)
+    strategy, segments = segment_with_strategy(cleaned_text, ext, max_tokens=chunk_size, forced=strategy)
 
     out = []
     for seg in segments:
@@ -333,10 +349,14 @@ def clean_file(input_path, privacy_filter=False, fuzzy_dedup=False, fuzzy_thresh
 
 def main():
     parser = argparse.ArgumentParser(description="Courtyard data cleaning")
+    parser.add_argument("--version", action="version", version=PROTOCOL_VERSION)
     parser.add_argument("--project-dir", required=True, help="Project directory path")
     parser.add_argument("--privacy-filter", action="store_true", help="Enable PII masking")
     parser.add_argument("--fuzzy-dedup", action="store_true", help="Enable fuzzy near-duplicate removal")
     parser.add_argument("--fuzzy-threshold", type=float, default=0.85, help="Fuzzy dedup threshold (0.5-1.0)")
+    parser.add_argument("--chunk-size", type=int, default=1024, help="Target segment size in tokens")
+    parser.add_argument("--strategy", choices=CLEAN_STRATEGIES, default="auto",
+                        help="Segmentation strategy (auto picks by file type)")
     add_lang_arg(parser)
     args = parser.parse_args()
 
@@ -405,6 +425,8 @@ def main():
                 privacy_filter=args.privacy_filter,
                 fuzzy_dedup=args.fuzzy_dedup,
                 fuzzy_threshold=max(0.5, min(1.0, args.fuzzy_threshold)),
+                chunk_size=max(64, args.chunk_size),
+                strategy=args.strategy,
             )
 
             cleaned_chars = sum(len(s["text"]) for s in segments)
@@ -451,6 +473,8 @@ def main():
                     "generated_at": int(time.time()),
                     "raw_files": raw_manifest,
                     "total_segments": total_segments,
+                    "chunk_size": max(64, args.chunk_size),
+                    "strategy": args.strategy,
                 },
                 ensure_ascii=False,
                 indent=2,
@@ -20,6 +20,8 @@ import argparse
 
 from i18n import t, init_i18n, add_lang_arg
 
+PROTOCOL_VERSION = "1"
+
 
 def emit(event_data: dict):
     """Print a JSON event line for Rust to parse."""
@@ -28,6 +30,7 @@ def emit(event_data: dict):
 
 def main():
     parser = argparse.ArgumentParser()
+    parser.add_argument("--version", action="version", version=PROTOCOL_VERSION)
     parser.add_argument("repo_id", help="HuggingFace repo ID, e.g. mlx-community/Qwen2.5-3B-Instruct-4bit")
     parser.add_argument("--cache-dir", default=None, help="Custom cache directory")
     add_lang_arg(parser)
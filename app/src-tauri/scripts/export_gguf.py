@@ -10,10 +10,16 @@ import argparse
 import glob
 import json
 import os
+import shutil
 import subprocess
 import sys
 
 from i18n import t, init_i18n, add_lang_arg
+from fused_cache import read_marker, write_marker
+
+# 2 added the fused-model cache (--fused-dir, --cache-key), --output-name,
+# --quantize-bin and the imatrix options.
+PROTOCOL_VERSION = "2"
 
 
 def emit(event_type, **kwargs):
@@ -26,7 +32,7 @@ def run_cli(cmd, timeout=900):
         result = subprocess.run(cmd, capture_output=True, text=True, timeout=timeout)
         return result.returncode == 0, result.stdout.strip(), result.stderr.strip()
     except subprocess.TimeoutExpired:
-        return False, "", "Command timed out after 15 minutes"
+        return False, "", f"Command timed out after {timeout // 60} minutes"
     except FileNotFoundError as e:
         return False, "", str(e)
 
@@ -48,21 +54,111 @@ def resolve_model_path(model_id):
 
 
 def find_gguf(directory):
+    # Newest first: output_dir also holds earlier exports.
     for pat in [
         os.path.join(directory, "*.gguf"),
         os.path.join(directory, "**", "*.gguf"),
     ]:
         files = glob.glob(pat, recursive=True)
         if files:
-            return files[0]
+            return max(files, key=os.path.getmtime)
     return None
 
 
+def output_filename(output_dir, stem, produced):
+    """Name for the exported file: `<stem>-<quant>.gguf`, never an existing path.
+
+    The quantization tag comes from the mlx_lm output (ggml-model-f16.gguf → f16).
+    Without a stem the produced basename is kept, still de-duplicated.
+    """
+    base = os.path.splitext(os.path.basename(produced))[0]
+    if stem:
+        quant = base.rsplit("-", 1)[-1] if "-" in base else ""
+        base = f"{stem}-{quant}" if quant else stem
+    path = os.path.join(output_dir, base + ".gguf")
+    n = 2
+    while os.path.exists(path):
+        path = os.path.join(output_dir, f"{base}-{n}.gguf")
+        n += 1
+    return path
+
+
+def corpus_as_text(path, work_dir):
+    """llama-imatrix reads plain text; flatten a .jsonl corpus to its text fields."""
+    if not path.endswith(".jsonl"):
+        return path
+    out = os.path.join(work_dir, "imatrix_corpus.txt")
+    with open(path, encoding="utf-8") as src, open(out, "w", encoding="utf-8") as dst:
+        for line in src:
+            try:
+                row = json.loads(line)
+            except ValueError:
+                continue
+            text = row.get("text") or row.get("completion") or ""
+            if text:
+                dst.write(text.strip() + "\n\n")
+    return out
+
+
+def quantize(args, f16_path):
+    """Quantize the f16 export with llama-quantize, guided by an importance
+    matrix when --imatrix-bin is available. Returns the final .gguf path;
+    the f16 file is kept if quantization fails."""
+    imatrix_path = None
+    if args.imatrix_dataset and args.imatrix_bin:
+        emit("stage", stage="imatrix", desc=t("gguf.imatrix"))
+        corpus = corpus_as_text(args.imatrix_dataset, args.output_dir)
+        imatrix_path = os.path.join(args.output_dir, "imatrix.dat")
+        ok, _stdout, stderr = run_cli(
+            [args.imatrix_bin, "-m", f16_path, "-f", corpus, "-o", imatrix_path],
+            timeout=3600,
+        )
+        if corpus != args.imatrix_dataset:
+            os.remove(corpus)
+        if not ok:
+            emit("progress", step="imatrix",
+                 desc=t("gguf.imatrix_fail", error=(stderr or "Unknown error")[-300:]))
+            imatrix_path = None
+
+    emit("stage", stage="quantize", desc=t("gguf.quantizing", quant=args.quant_type))
+    base = os.path.splitext(os.path.basename(f16_path))[0]
+    if base.lower().endswith("-f16"):
+        base = base[:-4]
+    out_path = output_filename(args.output_dir, "", f"{base}-{args.quant_type.lower()}.gguf")
+    cmd = [args.quantize_bin]
+    if imatrix_path:
+        cmd += ["--imatrix", imatrix_path]
+    cmd += [f16_path, out_path, args.quant_type]
+    ok, _stdout, stderr = run_cli(cmd)
+    if imatrix_path and os.path.exists(imatrix_path):
+        os.remove(imatrix_path)
+    if not ok:
+        emit("progress", step="quantize",
+             desc=t("gguf.quantize_fail", error=(stderr or "Unknown error")[-300:]))
+        return f16_path
+    os.remove(f16_path)
+    return out_path
+
+
 def main():
     parser = argparse.ArgumentParser(description="Courtyard GGUF export")
+    parser.add_argument("--version", action="version", version=PROTOCOL_VERSION)
     parser.add_argument("--model", required=True)
     parser.add_argument("--adapter-path", required=True)
     parser.add_argument("--output-dir", required=True)
+    parser.add_argument("--fused-dir", default="",
+                        help="Fused model cache directory; the .gguf is copied to --output-dir")
+    parser.add_argument("--cache-key", default="",
+                        help="Adapter + base model key; reuse --fused-dir when its marker matches")
+    parser.add_argument("--output-name", default="",
+                        help="Filename stem, e.g. model-adapter-iter800; quantization is appended")
+    parser.add_argument("--quantize-bin", default="",
+                        help="llama-quantize path; when set the f16 export is quantized")
+    parser.add_argument("--quant-type", default="Q4_K_M")
+    parser.add_argument("--imatrix-bin", default="",
+                        help="llama-imatrix path for importance-matrix quantization")
+    parser.add_argument("--imatrix-dataset", default="",
+                        help="Text or .jsonl corpus used to compute the importance matrix")
     add_lang_arg(parser)
     args = parser.parse_args()
 
@@ -102,32 +198,60 @@ def _run(args):
 
     os.makedirs(args.output_dir, exist_ok=True)
 
-    # Run mlx_lm.fuse --export-gguf --dequantize
-    emit("progress", step="fuse", desc=t("gguf.fusing"))
-    cmd = [
-        sys.executable, "-m", "mlx_lm.fuse",
-        "--model", resolved,
-        "--adapter-path", args.adapter_path,
-        "--save-path", args.output_dir,
-        "--export-gguf",
-        "--dequantize",
-    ]
-    ok, _stdout, stderr = run_cli(cmd, timeout=900)
+    cached = read_marker(args.fused_dir, args.cache_key) if args.fused_dir else None
+    if cached and cached[1] == "gguf":
+        emit("progress", step="fuse",
+             desc=t("gguf.cache_hit", filename=os.path.basename(cached[0])))
+        gguf_path = output_filename(args.output_dir, args.output_name, cached[0])
+        shutil.copy2(cached[0], gguf_path)
+    else:
+        # Fuse into the cache dir when one is given so other exports can reuse it.
+        save_path = args.fused_dir or args.output_dir
+        if args.fused_dir:
+            shutil.rmtree(save_path, ignore_errors=True)
+            os.makedirs(save_path, exist_ok=True)
 
-    if not ok:
-        # Detect upstream architecture limitation and emit a friendly message
-        import re as _re
-        _arch_match = _re.search(r'Model type (\S+) not supported for GGUF conversion', stderr or '')
-        if _arch_match:
-            emit("error", message=t("gguf.arch_not_supported", arch=_arch_match.group(1)))
+        # Run mlx_lm.fuse --export-gguf --dequantize
+        emit("progress", step="fuse", desc=t("gguf.fusing"))
+        cmd = [
+            sys.executable, "-m", "mlx_lm.fuse",
+            "--model", resolved,
+            "--adapter-path", args.adapter_path,
+            "--save-path", save_path,
+            "--export-gguf",
+            "--dequantize",
+        ]
+        ok, _stdout, stderr = run_cli(cmd, timeout=900)
+
+        if not ok:
+            # Detect upstream architecture limitation and emit a friendly message
+            import re as _re
+            _arch_match = _re.search(r'Model type (\S+) not supported for GGUF conversion', stderr or '')
+            if _arch_match:
+                emit("error", message=t("gguf.arch_not_supported", arch=_arch_match.group(1)))
+            else:
+                emit("error", message=t("gguf.fuse_fail", error=(stderr or "Unknown error")[-600:]))
+            sys.exit(1)
+
+        gguf_path = find_gguf(save_path)
+        if not gguf_path:
+            emit("error", message=t("gguf.no_output"))
+            sys.exit(1)
+
+        if args.fused_dir:
+            write_marker(save_path, args.cache_key, args.model, args.adapter_path, gguf_path, "gguf")
+            cached_gguf = gguf_path
+            gguf_path = output_filename(args.output_dir, args.output_name, cached_gguf)
+            shutil.copy2(cached_gguf, gguf_path)
         else:
-            emit("error", message=t("gguf.fuse_fail", error=(stderr or "Unknown error")[-600:]))
-        sys.exit(1)
+            # Fused straight into output_dir; mlx_lm's fixed name would be
+            # overwritten by the next export, so move it aside.
+            produced = gguf_path
+            gguf_path = output_filename(args.output_dir, args.output_name, produced)
+            os.replace(produced, gguf_path)
 
-    gguf_path = find_gguf(args.output_dir)
-    if not gguf_path:
-        emit("error", message=t("gguf.no_output"))
-        sys.exit(1)
+    if args.quantize_bin:
+        gguf_path = quantize(args, gguf_path)
 
     size_mb = round(os.path.getsize(gguf_path) / 1024 / 1024, 1)
     emit("progress", step="fuse", desc=t("gguf.done", filename=os.path.basename(gguf_path), size_mb=size_mb))
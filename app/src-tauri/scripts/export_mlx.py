@@ -15,6 +15,8 @@ import sys
 
 from i18n import t, init_i18n, add_lang_arg
 
+PROTOCOL_VERSION = "1"
+
 
 def emit(event_type, **kwargs):
     payload = {"type": event_type, **kwargs}
@@ -51,6 +53,7 @@ def _dir_size_mb(path):
 
 def main():
     parser = argparse.ArgumentParser(description="Courtyard MLX model export")
+    parser.add_argument("--version", action="version", version=PROTOCOL_VERSION)
     parser.add_argument("--model", required=True, help="Base model path or HuggingFace ID")
     parser.add_argument("--adapter-path", required=True, help="Path to LoRA adapter directory")
     parser.add_argument("--output-dir", required=True, help="Output directory for fused model")
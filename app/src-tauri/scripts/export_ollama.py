@@ -13,6 +13,7 @@ Strategy:
   3) ollama create from GGUF or cleaned safetensors
 
 Input:  --model <id> --adapter-path <path> --model-name <name> --quantization <q4|q8|f16>
+        [--fused-dir <dir> --cache-key <key>]  (reuse a cached fuse when the key matches)
 Output: JSON lines to stdout (progress + completion)
 """
 import argparse
@@ -27,6 +28,11 @@ import sys
 import tempfile
 
 from i18n import t, init_i18n, add_lang_arg
+from fused_cache import read_marker, write_marker
+
+# 2 added the fused-model cache (--fused-dir, --cache-key) and the Modelfile
+# chat template (--template, --template-stop).
+PROTOCOL_VERSION = "2"
 
 # Ollama-compatible safetensors dtypes (from reader_safetensors.go)
 OLLAMA_OK_DTYPES = {"F32", "F16", "BF16", "U8"}
@@ -360,7 +366,8 @@ def try_gguf_export(model_path, adapter_path, output_path):
 # ---------------------------------------------------------------------------
 # Step B: Create Ollama model
 # ---------------------------------------------------------------------------
-def create_ollama_model(model_name, model_path, model_format, quantization="q4"):
+def create_ollama_model(model_name, model_path, model_format, quantization="q4",
+                        template="", template_stops=None):
     """Create an Ollama model from a GGUF file or safetensors directory."""
     quant_map = {"q4": "q4_0", "q8": "q8_0", "f16": "f16"}
     ollama_quant = quant_map.get(quantization, "q4_0")
@@ -373,6 +380,10 @@ def create_ollama_model(model_name, model_path, model_format, quantization="q4")
     run_cli([_OLLAMA_BIN, "rm", model_name], timeout=30)
 
     modelfile_content = f"FROM {model_path}\n"
+    if template:
+        modelfile_content += f'TEMPLATE """{template}"""\n'
+        for stop in template_stops or []:
+            modelfile_content += f"PARAMETER stop {json.dumps(stop)}\n"
     with tempfile.NamedTemporaryFile(mode="w", suffix=".Modelfile", delete=False) as f:
         f.write(modelfile_content)
         modelfile_path = f.name
@@ -433,8 +444,57 @@ def verify_ollama_model_runtime(model_name):
 # ---------------------------------------------------------------------------
 # Main pipeline
 # ---------------------------------------------------------------------------
+def _fuse_fresh(args, resolved, fused_dir):
+    """Fuse into a clean fused_dir and return (model_output, model_format)."""
+    if os.path.isdir(fused_dir):
+        # The export target directory is reused per project. Clean it first so
+        # stale files from previous runs cannot pollute the current export.
+        shutil.rmtree(fused_dir, ignore_errors=True)
+    os.makedirs(fused_dir, exist_ok=True)
+
+    # Step 3: Try GGUF export first (fast path for Llama/Mistral/Mixtral)
+    emit("progress", step="fuse",
+         desc=t("export.fuse_start", model=resolved, adapter=args.adapter_path))
+
+    model_output, model_format = try_gguf_export(resolved, args.adapter_path, fused_dir)
+
+    # Step 3b: If GGUF failed, use direct MLX API (works for ALL architectures)
+    if model_output is None:
+        emit("progress", step="fuse",
+             desc=t("export.gguf_fallback"))
+        try:
+            model_output, model_format = fuse_and_dequantize_direct(
+                resolved, args.adapter_path, fused_dir
+            )
+        except Exception as e:
+            emit("error", message=t("export.fuse_fail", error=str(e)[-600:]))
+            sys.exit(1)
+
+    # Step 3.5: Binary safety net — remove any non-float tensors from safetensors
+    # Even after proper dequantization, some edge cases may leave U32/I32 artifacts.
+    if model_format == "safetensors":
+        emit("progress", step="convert",
+             desc=t("export.verify_start"))
+        try:
+            kept, removed = clean_safetensors_for_ollama(model_output)
+            config_cleaned = clean_config_for_ollama(model_output)
+            parts = []
+            if removed:
+                parts.append(t("export.removed_tensors", count=removed))
+            parts.append(t("export.tensors_ready", count=kept))
+            if config_cleaned:
+                parts.append(t("export.config_cleaned"))
+            emit("progress", step="convert", desc=t("export.verify_done", details='; '.join(parts)))
+        except Exception as e:
+            emit("progress", step="convert",
+                 desc=t("export.verify_warn", error=str(e)))
+
+    return model_output, model_format
+
+
 def main():
     parser = argparse.ArgumentParser(description="Courtyard Ollama export")
+    parser.add_argument("--version", action="version", version=PROTOCOL_VERSION)
     parser.add_argument("--model", required=True)
     parser.add_argument("--adapter-path", required=True)
     parser.add_argument("--model-name", required=True)
@@ -444,6 +504,13 @@ def main():
     parser.add_argument("--ollama-bin", default="", help="Full path to ollama binary")
     parser.add_argument("--keep-fused", action="store_true", default=False,
                         help="Keep the intermediate fused model directory for LM Studio / mlx-lm.server use")
+    parser.add_argument("--fused-dir", default="",
+                        help="Fused model cache directory (defaults to <output-dir>/fused)")
+    parser.add_argument("--cache-key", default="",
+                        help="Adapter + base model key; reuse --fused-dir when its marker matches")
+    parser.add_argument("--template", default="", help="Modelfile TEMPLATE (Go template syntax)")
+    parser.add_argument("--template-stop", action="append", default=[],
+                        help="PARAMETER stop entry for the template (repeatable)")
     add_lang_arg(parser)
     args = parser.parse_args()
 
@@ -488,56 +555,23 @@ def _run(args):
     emit("progress", step="resolve",
          desc=f"Adapter: {args.adapter_path} ({len(adapter_files)} weight files)")
 
-    fused_dir = os.path.join(args.output_dir, "fused")
-    if os.path.isdir(fused_dir):
-        # The export target directory is reused per project. Clean it first so
-        # stale files from previous runs cannot pollute the current export.
-        shutil.rmtree(fused_dir, ignore_errors=True)
-    os.makedirs(fused_dir, exist_ok=True)
-
-    # Step 3: Try GGUF export first (fast path for Llama/Mistral/Mixtral)
-    emit("progress", step="fuse",
-         desc=t("export.fuse_start", model=resolved, adapter=args.adapter_path))
-
-    model_output, model_format = try_gguf_export(resolved, args.adapter_path, fused_dir)
-
-    # Step 3b: If GGUF failed, use direct MLX API (works for ALL architectures)
-    if model_output is None:
-        emit("progress", step="fuse",
-             desc=t("export.gguf_fallback"))
-        try:
-            model_output, model_format = fuse_and_dequantize_direct(
-                resolved, args.adapter_path, fused_dir
-            )
-        except Exception as e:
-            emit("error", message=t("export.fuse_fail", error=str(e)[-600:]))
-            sys.exit(1)
-
-    # Step 3.5: Binary safety net — remove any non-float tensors from safetensors
-    # Even after proper dequantization, some edge cases may leave U32/I32 artifacts.
-    if model_format == "safetensors":
-        emit("progress", step="convert",
-             desc=t("export.verify_start"))
-        try:
-            kept, removed = clean_safetensors_for_ollama(model_output)
-            config_cleaned = clean_config_for_ollama(model_output)
-            parts = []
-            if removed:
-                parts.append(t("export.removed_tensors", count=removed))
-            parts.append(t("export.tensors_ready", count=kept))
-            if config_cleaned:
-                parts.append(t("export.config_cleaned"))
-            emit("progress", step="convert", desc=t("export.verify_done", details='; '.join(parts)))
-        except Exception as e:
-            emit("progress", step="convert",
-                 desc=t("export.verify_warn", error=str(e)))
+    fused_dir = args.fused_dir or os.path.join(args.output_dir, "fused")
+    cached = read_marker(fused_dir, args.cache_key)
+    if cached:
+        model_output, model_format = cached
+        emit("progress", step="fuse", desc=t("export.fused_cache_hit", format=model_format))
+    else:
+        model_output, model_format = _fuse_fresh(args, resolved, fused_dir)
+        write_marker(fused_dir, args.cache_key, args.model, args.adapter_path,
+                     model_output, model_format)
 
     emit("progress", step="fuse_done",
          desc=t("export.model_ready", format=model_format, filename=os.path.basename(model_output)))
 
     # Step 4: Create Ollama model
     result = create_ollama_model(
-        args.model_name, model_output, model_format, args.quantization
+        args.model_name, model_output, model_format, args.quantization,
+        args.template, args.template_stop,
     )
 
     if result is True:
@@ -553,14 +587,20 @@ def _run(args):
             os.path.expanduser("~/.ollama/models")
         )
         ollama_models = os.path.abspath(os.path.expanduser(ollama_models))
+        # Manifests live at library/<name>/<tag>.
+        manifest_name = args.model_name.rsplit(":", 1)[0]
         manifest_dir = os.path.join(
-            ollama_models, "manifests", "registry.ollama.ai", "library", args.model_name
+            ollama_models, "manifests", "registry.ollama.ai", "library", manifest_name
         )
 
         # Auto-cleanup intermediate fused files after successful export,
         # unless the user chose to keep them for LM Studio / mlx-lm.server.
         fused_kept = False
-        if args.keep_fused and os.path.isdir(fused_dir):
+        if args.cache_key and os.path.isdir(fused_dir):
+            fused_kept = True
+            emit("progress", step="cleanup",
+                 desc=t("export.fused_cached"))
+        elif args.keep_fused and os.path.isdir(fused_dir):
             fused_kept = True
             emit("progress", step="cleanup",
                  desc=t("export.fused_kept"))
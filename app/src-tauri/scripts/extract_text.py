@@ -9,6 +9,8 @@ import sys
 import os
 import argparse
 
+PROTOCOL_VERSION = "1"
+
 
 def extract_pdf(path):
     """Extract text from a PDF file using PyPDF2."""
@@ -44,6 +46,7 @@ def extract_docx(path):
 
 def main():
     parser = argparse.ArgumentParser(description="Extract text from PDF/DOCX")
+    parser.add_argument("--version", action="version", version=PROTOCOL_VERSION)
     parser.add_argument("file_path", help="Path to the file")
     parser.add_argument("--max-chars", type=int, default=0, help="Max characters to output (0 = unlimited)")
     args = parser.parse_args()
@@ -0,0 +1,44 @@
+"""Shared fused-model cache for Courtyard export scripts.
+
+Rust keys each cache directory (export/fused/<key>) on the adapter weights and
+base model id. A marker file records what was produced so a later export can
+reuse it instead of fusing again.
+"""
+
+import json
+import os
+
+MARKER_NAME = ".courtyard_fused.json"
+
+
+def read_marker(cache_dir, key):
+    """Return (artifact_path, format) if cache_dir holds a valid fuse for key."""
+    marker_path = os.path.join(cache_dir, MARKER_NAME)
+    if not key or not os.path.isfile(marker_path):
+        return None
+    try:
+        with open(marker_path, "r", encoding="utf-8") as f:
+            marker = json.load(f)
+    except (OSError, ValueError):
+        return None
+    if marker.get("key") != key:
+        return None
+    artifact = os.path.normpath(os.path.join(cache_dir, marker.get("artifact", "")))
+    if not os.path.exists(artifact):
+        return None
+    return artifact, marker.get("format", "")
+
+
+def write_marker(cache_dir, key, model, adapter_path, artifact, model_format):
+    """Record a completed fuse so later exports can reuse it."""
+    if not key:
+        return
+    marker = {
+        "key": key,
+        "model": model,
+        "adapter_path": adapter_path,
+        "artifact": os.path.relpath(artifact, cache_dir),
+        "format": model_format,
+    }
+    with open(os.path.join(cache_dir, MARKER_NAME), "w", encoding="utf-8") as f:
+        json.dump(marker, f, ensure_ascii=False, indent=2)
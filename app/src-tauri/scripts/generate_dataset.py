@@ -14,6 +14,8 @@ import sys
 
 from i18n import init_i18n, add_lang_arg
 
+PROTOCOL_VERSION = "1"
+
 
 def emit(event_type, **kwargs):
     payload = {"type": event_type, **kwargs}
@@ -161,6 +163,7 @@ def compute_quality_score(total, success, avg_output_len):
 
 def main():
     parser = argparse.ArgumentParser(description="Courtyard dataset generation")
+    parser.add_argument("--version", action="version", version=PROTOCOL_VERSION)
     parser.add_argument("--project-dir", required=True)
     parser.add_argument("--output-dir", default=None, help="Output directory for dataset files")
     parser.add_argument("--model", required=True, help="Model path or HuggingFace ID")
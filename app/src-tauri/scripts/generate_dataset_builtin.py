@@ -16,6 +16,8 @@ import random
 
 from i18n import t, pt, init_i18n, init_prompt_i18n, detect_content_language, add_lang_arg
 
+PROTOCOL_VERSION = "1"
+
 
 def emit(event_type, **kwargs):
     payload = {"type": event_type, **kwargs}
@@ -243,6 +245,7 @@ def generate_builtin(segments: list[str], mode: str) -> list[dict]:
 
 def main():
     parser = argparse.ArgumentParser()
+    parser.add_argument("--version", action="version", version=PROTOCOL_VERSION)
     parser.add_argument("--project-dir", required=True)
     parser.add_argument("--output-dir", default=None, help="Output directory for dataset files")
     parser.add_argument("--mode", default="qa", choices=["qa", "style", "chat", "instruct"])
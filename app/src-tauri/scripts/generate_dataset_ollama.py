@@ -20,6 +20,8 @@ import urllib.error
 
 from i18n import t, pt, init_i18n, init_prompt_i18n, detect_content_language, add_lang_arg
 
+PROTOCOL_VERSION = "1"
+
 
 def emit(event_type, **kwargs):
     payload = {"type": event_type, **kwargs}
@@ -501,6 +503,7 @@ def compute_quality_score(total: int, success: int, avg_output_len: float) -> tu
 
 def main():
     parser = argparse.ArgumentParser()
+    parser.add_argument("--version", action="version", version=PROTOCOL_VERSION)
     parser.add_argument("--project-dir", required=True)
     parser.add_argument("--output-dir", default=None, help="Output directory for dataset files")
     parser.add_argument("--model", required=True)
@@ -3,7 +3,9 @@
 Courtyard - Model inference script.
 Streams tokens to stdout for the Rust backend to relay to the frontend.
 Input:  --model <path> --adapter-path <path> --prompt <text> --max-tokens <n> --temp <f>
-Output: JSON lines to stdout (token events + completion)
+        [--stop <text> ...] [--prompt-template <text with {prompt}>]
+        or --batch-file <JSON list of prompts> instead of --prompt
+Output: JSON lines to stdout (token events + completion; batch_item per prompt in batch mode)
 """
 import argparse
 import json
@@ -11,25 +13,107 @@ import sys
 
 from i18n import t, init_i18n, add_lang_arg
 
+# 2 added stop sequences (--stop), --prompt-template and batch mode (--batch-file).
+PROTOCOL_VERSION = "2"
+
 
 def emit(event_type, **kwargs):
     payload = {"type": event_type, **kwargs}
     print(json.dumps(payload, ensure_ascii=False), flush=True)
 
 
+def _cut_at_stop(text, stops):
+    """Truncate text at the earliest stop sequence. Returns (text, matched_stop)."""
+    hits = [(text.find(s), s) for s in stops if s and s in text]
+    if not hits:
+        return text, None
+    idx, stop = min(hits)
+    return text[:idx], stop
+
+
+def _generate_until_stop(model, tokenizer, stops, gen_kwargs):
+    """Stream tokens and stop as soon as a stop sequence appears."""
+    from mlx_lm import stream_generate
+
+    text = ""
+    for chunk in stream_generate(model, tokenizer, **gen_kwargs):
+        text += chunk if isinstance(chunk, str) else getattr(chunk, "text", "")
+        cut, stop = _cut_at_stop(text, stops)
+        if stop is not None:
+            return cut, stop
+    return text, None
+
+
+def _sampling_kwargs(args):
+    """Sampler kwargs: new API (mlx-lm >= 0.19) first, legacy temp param otherwise."""
+    try:
+        from mlx_lm.sample_utils import make_sampler
+        return {"sampler": make_sampler(temp=args.temp, top_p=args.top_p)}
+    except (ImportError, TypeError):
+        return {"temp": args.temp, "top_p": args.top_p}
+
+
+def _format_single_prompt(tokenizer, prompt, template):
+    if template:
+        return template.replace("{prompt}", prompt)
+    if hasattr(tokenizer, "apply_chat_template"):
+        return tokenizer.apply_chat_template(
+            [{"role": "user", "content": prompt}], tokenize=False, add_generation_prompt=True
+        )
+    return f"User: {prompt}\nAssistant:"
+
+
+def run_batch(model, tokenizer, args):
+    """Answer every prompt in --batch-file with the already loaded model."""
+    from mlx_lm import generate
+
+    with open(args.batch_file, "r", encoding="utf-8") as f:
+        prompts = json.load(f)
+    if not isinstance(prompts, list):
+        raise ValueError("--batch-file must contain a JSON list of prompts")
+
+    done = 0
+    for index, prompt in enumerate(prompts):
+        prompt = str(prompt)
+        try:
+            gen_kwargs = dict(
+                prompt=_format_single_prompt(tokenizer, prompt, args.prompt_template),
+                max_tokens=args.max_tokens,
+                verbose=False,
+                **_sampling_kwargs(args),
+            )
+            response = generate(model, tokenizer, **gen_kwargs)
+            if args.stop:
+                response, _ = _cut_at_stop(response, args.stop)
+            emit("batch_item", index=index, total=len(prompts), prompt=prompt, response=response)
+            done += 1
+        except Exception as e:
+            emit("batch_item", index=index, total=len(prompts), prompt=prompt, error=str(e))
+    emit("complete", prompts=len(prompts), succeeded=done)
+
+
 def main():
     parser = argparse.ArgumentParser(description="Courtyard model inference")
+    parser.add_argument("--version", action="version", version=PROTOCOL_VERSION)
     parser.add_argument("--model", required=True, help="Base model path or HF ID")
     parser.add_argument("--adapter-path", default="", help="LoRA adapter path")
-    parser.add_argument("--prompt", required=True, help="User prompt text")
+    parser.add_argument("--prompt", default="", help="User prompt text")
+    parser.add_argument("--batch-file", default="",
+                        help="JSON list of prompts to answer one by one (replaces --prompt)")
     parser.add_argument("--messages-json", default="", help="Conversation messages JSON")
     parser.add_argument("--max-tokens", type=int, default=1024)
     parser.add_argument("--temp", type=float, default=0.7)
     parser.add_argument("--top-p", type=float, default=0.9)
+    parser.add_argument("--stop", action="append", default=[],
+                        help="Stop sequence (repeatable); generation ends when one appears")
+    parser.add_argument("--prompt-template", default="",
+                        help="Format with a {prompt} placeholder; replaces the chat template")
     add_lang_arg(parser)
     args = parser.parse_args()
 
     init_i18n(args.lang)
+    if not args.prompt and not args.batch_file:
+        parser.error("one of --prompt or --batch-file is required")
 
     emit("status", message=t("inference.loading"))
 
@@ -70,6 +154,10 @@ def main():
 
         emit("status", message=t("inference.generating"))
 
+        if args.batch_file:
+            run_batch(model, tokenizer, args)
+            return
+
         messages = [{"role": "user", "content": args.prompt}]
         if args.messages_json and args.messages_json.strip():
             try:
@@ -89,8 +177,10 @@ def main():
             except Exception:
                 pass
 
-        # Build chat prompt using tokenizer's chat template if available
-        if hasattr(tokenizer, "apply_chat_template"):
+        # An explicit template wraps the prompt verbatim, bypassing the chat template
+        if args.prompt_template:
+            prompt_text = args.prompt_template.replace("{prompt}", args.prompt)
+        elif hasattr(tokenizer, "apply_chat_template"):
             prompt_text = tokenizer.apply_chat_template(
                 messages, tokenize=False, add_generation_prompt=True
             )
@@ -103,24 +193,27 @@ def main():
                 transcript.append("Assistant:")
             prompt_text = "\n".join(transcript).strip()
 
-        # Try new API with sampler first (mlx-lm >= 0.19), fallback to legacy temp param
         gen_kwargs = dict(
             prompt=prompt_text,
             max_tokens=args.max_tokens,
             verbose=False,
+            **_sampling_kwargs(args),
         )
-        try:
-            from mlx_lm.sample_utils import make_sampler
-            sampler = make_sampler(temp=args.temp, top_p=args.top_p)
-            gen_kwargs["sampler"] = sampler
-        except (ImportError, TypeError):
-            gen_kwargs["temp"] = args.temp
-            gen_kwargs["top_p"] = args.top_p
 
-        response = generate(model, tokenizer, **gen_kwargs)
+        stop = None
+        if args.stop:
+            gen_kwargs.pop("verbose", None)
+            try:
+                response, stop = _generate_until_stop(model, tokenizer, args.stop, gen_kwargs)
+            except ImportError:
+                response, stop = _cut_at_stop(
+                    generate(model, tokenizer, verbose=False, **gen_kwargs), args.stop
+                )
+        else:
+            response = generate(model, tokenizer, **gen_kwargs)
 
         emit("response", text=response)
-        emit("complete", tokens=len(response.split()))
+        emit("complete", tokens=len(response.split()), stop=stop)
 
     except Exception as e:
         emit("error", message=str(e))
@@ -0,0 +1,117 @@
+/// Default Ollama Modelfile chat template for one model family.
+pub struct ChatTemplate {
+    pub family: &'static str,
+    pub template: &'static str,
+    pub stop: &'static [&'static str],
+}
+
+const LLAMA: ChatTemplate = ChatTemplate {
+    family: "llama",
+    template: "{{ if .System }}<|start_header_id|>system<|end_header_id|>\n\n{{ .System }}<|eot_id|>{{ end }}{{ if .Prompt }}<|start_header_id|>user<|end_header_id|>\n\n{{ .Prompt }}<|eot_id|>{{ end }}<|start_header_id|>assistant<|end_header_id|>\n\n{{ .Response }}<|eot_id|>",
+    stop: &["<|eot_id|>", "<|start_header_id|>"],
+};
+
+const QWEN: ChatTemplate = ChatTemplate {
+    family: "qwen",
+    template: "{{ if .System }}<|im_start|>system\n{{ .System }}<|im_end|>\n{{ end }}{{ if .Prompt }}<|im_start|>user\n{{ .Prompt }}<|im_end|>\n{{ end }}<|im_start|>assistant\n{{ .Response }}<|im_end|>",
+    stop: &["<|im_end|>", "<|im_start|>"],
+};
+
+const MISTRAL: ChatTemplate = ChatTemplate {
+    family: "mistral",
+    template: "[INST] {{ if .System }}{{ .System }}\n\n{{ end }}{{ .Prompt }} [/INST]{{ .Response }}",
+    stop: &["[INST]", "[/INST]"],
+};
+
+const GEMMA: ChatTemplate = ChatTemplate {
+    family: "gemma",
+    template: "<start_of_turn>user\n{{ if .System }}{{ .System }}\n\n{{ end }}{{ .Prompt }}<end_of_turn>\n<start_of_turn>model\n{{ .Response }}<end_of_turn>",
+    stop: &["<end_of_turn>", "<start_of_turn>"],
+};
+
+const PHI: ChatTemplate = ChatTemplate {
+    family: "phi",
+    template: "{{ if .System }}<|system|>\n{{ .System }}<|end|>\n{{ end }}{{ if .Prompt }}<|user|>\n{{ .Prompt }}<|end|>\n{{ end }}<|assistant|>\n{{ .Response }}<|end|>",
+    stop: &["<|end|>", "<|user|>"],
+};
+
+const GENERIC: ChatTemplate = ChatTemplate {
+    family: "generic",
+    template: "{{ if .System }}{{ .System }}\n\n{{ end }}User: {{ .Prompt }}\nAssistant: {{ .Response }}",
+    stop: &["User:"],
+};
+
+/// Versions that follow `family` in the id tokens: "llama3" and "llama-3"
+/// both give "3", a bare "phi" gives "". Words that merely contain the family
+/// name (e.g. "dolphin", "codellama") do not match.
+fn family_versions<'a>(tokens: &'a [&'a str], family: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+    tokens.iter().enumerate().filter_map(move |(i, tok)| {
+        let rest = tok.strip_prefix(family)?;
+        if rest.is_empty() {
+            Some(tokens.get(i + 1).copied().unwrap_or(""))
+        } else if rest.starts_with(|c: char| c.is_ascii_digit()) {
+            Some(rest)
+        } else {
+            None
+        }
+    })
+}
+
+/// Infer the model family from a model id or path and return its template.
+/// Unknown families (including Llama 2 / CodeLlama) get a plain User/Assistant template.
+pub fn template_for_model(model: &str) -> &'static ChatTemplate {
+    let id = model.to_lowercase();
+    let tokens: Vec<&str> = id
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .collect();
+    let is = |family: &'static str| family_versions(&tokens, family).next().is_some();
+    if is("qwen") {
+        &QWEN
+    } else if is("mistral") || is("mixtral") {
+        &MISTRAL
+    } else if is("gemma") {
+        &GEMMA
+    } else if is("phi") {
+        &PHI
+    } else if family_versions(&tokens, "llama").any(|v| v.starts_with('3')) {
+        &LLAMA
+    } else {
+        &GENERIC
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn family(model: &str) -> &'static str {
+        template_for_model(model).family
+    }
+
+    #[test]
+    fn maps_known_families() {
+        assert_eq!(family("Qwen/Qwen2.5-7B-Instruct"), "qwen");
+        assert_eq!(family("mistralai/Mistral-7B-Instruct-v0.3"), "mistral");
+        assert_eq!(family("mistralai/Mixtral-8x7B-Instruct-v0.1"), "mistral");
+        assert_eq!(family("google/gemma-2-9b-it"), "gemma");
+        assert_eq!(family("microsoft/Phi-3-mini-4k-instruct"), "phi");
+        assert_eq!(family("mlx-community/phi3-mini-4bit"), "phi");
+        assert_eq!(family("meta-llama/Meta-Llama-3-8B-Instruct"), "llama");
+        assert_eq!(family("mlx-community/Llama-3.2-3B-Instruct-4bit"), "llama");
+        assert_eq!(family("/Users/me/models/llama3.1-8b"), "llama");
+    }
+
+    #[test]
+    fn substrings_do_not_pick_a_family() {
+        assert_eq!(family("cognitivecomputations/dolphin-2.9-llama3-8b"), "llama");
+        assert_eq!(family("some-org/dolphin-7b"), "generic");
+    }
+
+    #[test]
+    fn older_llamas_fall_back_to_generic() {
+        assert_eq!(family("meta-llama/Llama-2-7b-chat-hf"), "generic");
+        assert_eq!(family("codellama/CodeLlama-7b-Instruct-hf"), "generic");
+        assert_eq!(family("unknown-model"), "generic");
+    }
+}
@@ -8,9 +8,11 @@ pub struct AppConfig {
     #[serde(default)]
     pub model_paths: ModelPaths,
     pub export_path: Option<String>,
-    /// Model download source: "huggingface" (default), "hf-mirror", "modelscope"
+    /// Model download source: "huggingface" (default), "hf-mirror", "modelscope", "custom"
     #[serde(default = "default_hf_source")]
     pub hf_source: String,
+    /// HF_ENDPOINT used when hf_source is "custom" (private HF proxy / artifactory)
+    pub hf_custom_endpoint: Option<String>,
     /// Custom path to the ollama binary (overrides auto-detection)
     pub ollama_bin: Option<String>,
     /// LM Studio local API base URL (default: http://localhost:1234)
@@ -18,6 +20,33 @@ pub struct AppConfig {
     /// Enterprise network compatibility settings
     #[serde(default)]
     pub network: NetworkConfig,
+    /// Keep the machine awake (caffeinate -i) during long jobs; unset means on
+    pub prevent_sleep: Option<bool>,
+    /// Send an OS notification when training, generation or an export finishes
+    #[serde(default)]
+    pub notify_on_complete: bool,
+    /// Extra directories scanned for standalone .gguf files
+    #[serde(default)]
+    pub gguf_dirs: Vec<String>,
+    /// Remembered settings for one-click export
+    #[serde(default)]
+    pub default_export: Option<DefaultExport>,
+    /// Friendly display names keyed by model id
+    #[serde(default)]
+    pub aliases: std::collections::HashMap<String, String>,
+    /// Delete a run's intermediate fused model once its export succeeds
+    #[serde(default)]
+    pub autoclean_after_export: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DefaultExport {
+    /// "ollama", "gguf" or "mlx"
+    pub format: String,
+    /// Ollama quantization (e.g. "q4"); ignored by other formats
+    pub quantization: Option<String>,
+    /// Ollama model tag; ignored by other formats
+    pub tag: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
@@ -30,6 +59,9 @@ pub struct NetworkConfig {
     pub ssl_cert_file: Option<String>,
     /// Directory containing CA certificates
     pub ssl_cert_dir: Option<String>,
+    /// Set HF_HUB_ENABLE_HF_TRANSFER for HF downloads (needs the hf_transfer package)
+    #[serde(default)]
+    pub hf_transfer: bool,
 }
 
 fn default_hf_source() -> String {
@@ -61,13 +93,35 @@ pub fn load_config() -> AppConfig {
     }
 }
 
+/// Serializes load-modify-save so concurrent setters can't drop each other's updates.
+static CONFIG_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Apply `f` to the current config and save it, holding CONFIG_LOCK throughout.
+/// Nothing is written if `f` returns an error.
+pub fn update_config<F>(f: F) -> Result<(), String>
+where
+    F: FnOnce(&mut AppConfig) -> Result<(), String>,
+{
+    let _guard = CONFIG_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut config = load_config();
+    f(&mut config)?;
+    save_config(&config)
+}
+
+/// Write config.json via a temp file + rename so a crash mid-write can't
+/// leave a truncated file behind.
 fn save_config(config: &AppConfig) -> Result<(), String> {
     let path = config_path();
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
     }
     let json = serde_json::to_string_pretty(config).map_err(|e| e.to_string())?;
-    std::fs::write(&path, json).map_err(|e| e.to_string())
+    let tmp_path = path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, json).map_err(|e| e.to_string())?;
+    std::fs::rename(&tmp_path, &path).map_err(|e| {
+        let _ = std::fs::remove_file(&tmp_path);
+        e.to_string()
+    })
 }
 
 /// Resolve actual paths (custom or default)
@@ -115,8 +169,21 @@ pub struct AppConfigResponse {
     pub lmstudio_installed: bool,
     pub lmstudio_api_url: String,
     pub hf_source: String,
+    pub hf_custom_endpoint: Option<String>,
+    /// Endpoint downloads actually use; None for ModelScope (not HF_ENDPOINT based).
+    pub hf_endpoint: Option<String>,
     pub ollama_bin_path: String,
     pub ollama_bin_custom: bool,
+    pub prevent_sleep: bool,
+    /// Courtyard base directory (projects, tmp, model index).
+    pub base_dir: String,
+    /// Whether the Python venv has been set up.
+    pub venv_ready: bool,
+    pub notify_on_complete: bool,
+    pub gguf_dirs: Vec<String>,
+    pub default_export: Option<DefaultExport>,
+    pub aliases: std::collections::HashMap<String, String>,
+    pub autoclean_after_export: bool,
 }
 
 #[tauri::command]
@@ -130,6 +197,9 @@ pub fn get_app_config() -> Result<AppConfigResponse, String> {
         .map(|p| p.to_string_lossy().to_string())
         .unwrap_or_else(|| "./projects".to_string());
     let ollama_bin_custom = config.ollama_bin.is_some();
+    let prevent_sleep = config.prevent_sleep.unwrap_or(true);
+    let hf_endpoint = hf_endpoint_for_source(&config.hf_source, config.hf_custom_endpoint.as_deref())
+        .or_else(|| (config.hf_source == "huggingface").then(|| "https://huggingface.co".to_string()));
 
     let lmstudio_installed = resolved.lmstudio.exists();
     let lmstudio_api_url = config.lmstudio_api_url.clone()
@@ -150,40 +220,248 @@ pub fn get_app_config() -> Result<AppConfigResponse, String> {
         lmstudio_installed,
         lmstudio_api_url,
         hf_source: config.hf_source,
+        hf_custom_endpoint: config.hf_custom_endpoint,
+        hf_endpoint,
         ollama_bin_path,
         ollama_bin_custom,
+        prevent_sleep,
+        base_dir: ProjectDirManager::new().base_dir().to_string_lossy().to_string(),
+        venv_ready: PythonExecutor::default().is_ready(),
+        notify_on_complete: config.notify_on_complete,
+        gguf_dirs: config.gguf_dirs,
+        default_export: config.default_export,
+        aliases: config.aliases,
+        autoclean_after_export: config.autoclean_after_export,
     })
 }
 
+/// With `migrate`, previously downloaded HuggingFace models (`models--*`) are
+/// moved from the old cache to the new one before the config is saved, emitting
+/// `model-migrate:progress` per model and `model-migrate:complete` at the end.
 #[tauri::command]
-pub fn set_model_source_path(source: String, path: Option<String>) -> Result<(), String> {
-    let mut config = load_config();
-    match source.as_str() {
-        "huggingface" => config.model_paths.huggingface = path,
-        "modelscope" => config.model_paths.modelscope = path,
-        "ollama" => config.model_paths.ollama = path,
-        "lmstudio" => config.model_paths.lmstudio = path,
-        _ => return Err(format!("Unknown source: {}", source)),
+pub async fn set_model_source_path(
+    app: tauri::AppHandle,
+    source: String,
+    path: Option<String>,
+    migrate: Option<bool>,
+) -> Result<(), String> {
+    let mut failures: Vec<String> = Vec::new();
+    if source == "huggingface" && migrate.unwrap_or(false) {
+        let old_dir = resolve_model_paths().huggingface;
+        let new_dir = path.as_ref().map(PathBuf::from).unwrap_or_else(|| {
+            dirs::home_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join(".cache")
+                .join("huggingface")
+                .join("hub")
+        });
+        if old_dir != new_dir && old_dir.is_dir() {
+            failures = tokio::task::spawn_blocking(move || migrate_hf_cache(&app, &old_dir, &new_dir))
+                .await
+                .map_err(|e| e.to_string())?;
+        }
     }
-    save_config(&config)
+
+    update_config(|config| {
+        match source.as_str() {
+            "huggingface" => config.model_paths.huggingface = path,
+            "modelscope" => config.model_paths.modelscope = path,
+            "ollama" => config.model_paths.ollama = path,
+            "lmstudio" => config.model_paths.lmstudio = path,
+            _ => return Err(format!("Unknown source: {}", source)),
+        }
+        Ok(())
+    })?;
+    if source == "ollama" {
+        crate::commands::environment::invalidate_ollama_models_dir_cache();
+    }
+    if !failures.is_empty() {
+        return Err(format!(
+            "Cache path saved, but some models could not be moved: {}",
+            failures.join("; ")
+        ));
+    }
+    Ok(())
+}
+
+/// Move every `models--*` dir from `old_dir` to `new_dir`, skipping ones that
+/// already exist at the destination. Returns per-model failure messages.
+fn migrate_hf_cache(app: &tauri::AppHandle, old_dir: &std::path::Path, new_dir: &std::path::Path) -> Vec<String> {
+    use tauri::Emitter;
+
+    let mut models: Vec<PathBuf> = std::fs::read_dir(old_dir)
+        .map(|rd| {
+            rd.flatten()
+                .map(|e| e.path())
+                .filter(|p| {
+                    p.is_dir()
+                        && p.file_name()
+                            .map(|n| n.to_string_lossy().starts_with("models--"))
+                            .unwrap_or(false)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    models.sort();
+
+    let total = models.len();
+    let mut moved = 0usize;
+    let mut skipped = 0usize;
+    let mut failures = Vec::new();
+    for (index, src) in models.iter().enumerate() {
+        let name = src.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let _ = app.emit("model-migrate:progress", serde_json::json!({
+            "index": index + 1,
+            "total": total,
+            "name": name,
+        }));
+        let dest = new_dir.join(&name);
+        if dest.exists() {
+            skipped += 1;
+            continue;
+        }
+        match crate::fs::move_dir(src, &dest) {
+            Ok(()) => moved += 1,
+            Err(e) => failures.push(format!("{}: {}", name, e)),
+        }
+    }
+    let _ = app.emit("model-migrate:complete", serde_json::json!({
+        "moved": moved,
+        "skipped": skipped,
+        "failed": failures.len(),
+    }));
+    failures
 }
 
 #[tauri::command]
-pub fn set_export_path(path: Option<String>) -> Result<(), String> {
-    let mut config = load_config();
-    config.export_path = path;
-    save_config(&config)
+pub fn set_export_path(path: Option<String>) -> Result<ExportPathCheck, String> {
+    let path = path.filter(|p| !p.trim().is_empty());
+    if let Some(ref p) = path {
+        let check = check_dir_writable(std::path::Path::new(p));
+        if !check.ok {
+            return Ok(check);
+        }
+    }
+    update_config(|config| {
+        config.export_path = path;
+        Ok(())
+    })?;
+    Ok(ExportPathCheck { ok: true, reason: None, message: None })
+}
+
+/// Outcome of `set_export_path`; the path is only saved when `ok`.
+#[derive(Serialize)]
+pub struct ExportPathCheck {
+    pub ok: bool,
+    /// "missing", "permission", "read_only" or "other"
+    pub reason: Option<String>,
+    pub message: Option<String>,
+}
+
+/// Create `dir` if needed and write + remove a probe file in it.
+pub(crate) fn check_dir_writable(dir: &std::path::Path) -> ExportPathCheck {
+    let fail = |e: std::io::Error| {
+        let reason = match (e.kind(), e.raw_os_error()) {
+            (_, Some(libc::EROFS)) => "read_only",
+            (std::io::ErrorKind::NotFound, _) => "missing",
+            (std::io::ErrorKind::PermissionDenied, _) => "permission",
+            _ => "other",
+        };
+        ExportPathCheck {
+            ok: false,
+            reason: Some(reason.to_string()),
+            message: Some(format!("{}: {}", dir.display(), e)),
+        }
+    };
+    if let Err(e) = std::fs::create_dir_all(dir) {
+        return fail(e);
+    }
+    let probe = dir.join(format!(".courtyard_write_test_{}", std::process::id()));
+    match std::fs::write(&probe, b"ok") {
+        Ok(()) => {
+            let _ = std::fs::remove_file(&probe);
+            ExportPathCheck { ok: true, reason: None, message: None }
+        }
+        Err(e) => fail(e),
+    }
 }
 
 #[tauri::command]
-pub fn set_hf_source(source: String) -> Result<(), String> {
-    let valid = ["huggingface", "hf-mirror", "modelscope"];
+pub fn set_hf_source(source: String, custom_endpoint: Option<String>) -> Result<(), String> {
+    let valid = ["huggingface", "hf-mirror", "modelscope", "custom"];
     if !valid.contains(&source.as_str()) {
         return Err(format!("Invalid source: {}. Must be one of: {:?}", source, valid));
     }
-    let mut config = load_config();
-    config.hf_source = source;
-    save_config(&config)
+    update_config(|config| {
+        if let Some(endpoint) = custom_endpoint.filter(|s| !s.trim().is_empty()) {
+            config.hf_custom_endpoint = Some(validate_endpoint_url(&endpoint)?);
+        }
+        if source == "custom" && config.hf_custom_endpoint.is_none() {
+            return Err("A custom endpoint URL is required for the \"custom\" source.".to_string());
+        }
+        config.hf_source = source;
+        Ok(())
+    })
+}
+
+#[derive(Serialize)]
+pub struct HfSourceProbe {
+    pub endpoint: String,
+    pub reachable: bool,
+    pub latency_ms: Option<u64>,
+    pub status: Option<u16>,
+    pub error: Option<String>,
+}
+
+/// Probe a model download source with a short GET to its root. Any HTTP
+/// response counts as reachable; only connection failures and timeouts don't.
+/// `custom_endpoint` defaults to the saved one for the "custom" source.
+#[tauri::command]
+pub async fn test_hf_source(source: String, custom_endpoint: Option<String>) -> Result<HfSourceProbe, String> {
+    let saved_custom = load_config().hf_custom_endpoint;
+    let custom = custom_endpoint.filter(|s| !s.trim().is_empty()).or(saved_custom);
+    let endpoint = match source.as_str() {
+        "huggingface" => "https://huggingface.co".to_string(),
+        "modelscope" => "https://www.modelscope.cn".to_string(),
+        "hf-mirror" | "custom" => hf_endpoint_for_source(&source, custom.as_deref())
+            .ok_or_else(|| "A custom endpoint URL is required for the \"custom\" source.".to_string())?,
+        _ => return Err(format!("Invalid source: {}", source)),
+    };
+    let endpoint = validate_endpoint_url(&endpoint)?;
+
+    let started = std::time::Instant::now();
+    let result = reqwest::Client::new()
+        .get(format!("{}/", endpoint))
+        .timeout(std::time::Duration::from_secs(5))
+        .send()
+        .await;
+    Ok(match result {
+        Ok(resp) => HfSourceProbe {
+            endpoint,
+            reachable: true,
+            latency_ms: Some(started.elapsed().as_millis() as u64),
+            status: Some(resp.status().as_u16()),
+            error: None,
+        },
+        Err(e) => HfSourceProbe {
+            endpoint,
+            reachable: false,
+            latency_ms: None,
+            status: None,
+            error: Some(e.to_string()),
+        },
+    })
+}
+
+/// Validate an http(s) endpoint URL and return it without a trailing slash.
+fn validate_endpoint_url(url: &str) -> Result<String, String> {
+    let trimmed = url.trim().trim_end_matches('/');
+    let parsed = reqwest::Url::parse(trimmed)
+        .map_err(|e| format!("Invalid endpoint URL {}: {}", trimmed, e))?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        return Err(format!("Endpoint must be an http(s) URL with a host: {}", trimmed));
+    }
+    Ok(trimmed.to_string())
 }
 
 /// Resolve the ollama binary path: config override > auto-detect > bare name.
@@ -225,17 +503,19 @@ pub fn resolve_ollama_bin_status_from_config() -> (String, bool) {
 
 #[tauri::command]
 pub fn set_ollama_bin_path(path: Option<String>) -> Result<(), String> {
-    let mut config = load_config();
-    config.ollama_bin = path;
-    save_config(&config)
+    update_config(|config| {
+        config.ollama_bin = path;
+        Ok(())
+    })
 }
 
 /// Set LM Studio API base URL (or reset to default).
 #[tauri::command]
 pub fn set_lmstudio_api_url(url: Option<String>) -> Result<(), String> {
-    let mut config = load_config();
-    config.lmstudio_api_url = url;
-    save_config(&config)
+    update_config(|config| {
+        config.lmstudio_api_url = url;
+        Ok(())
+    })
 }
 
 /// Check LM Studio API connectivity by hitting GET /v1/models.
@@ -274,13 +554,22 @@ pub async fn check_lmstudio_api() -> Result<Vec<String>, String> {
 }
 
 /// Return the HF_ENDPOINT URL for the configured source (empty = default HuggingFace)
-pub fn hf_endpoint_for_source(source: &str) -> Option<String> {
+pub fn hf_endpoint_for_source(source: &str, custom_endpoint: Option<&str>) -> Option<String> {
     match source {
         "hf-mirror" => Some("https://hf-mirror.com".to_string()),
+        "custom" => custom_endpoint
+            .map(|s| s.trim().trim_end_matches('/').to_string())
+            .filter(|s| !s.is_empty()),
         _ => None, // huggingface uses default, modelscope not supported via HF_ENDPOINT
     }
 }
 
+/// HF_ENDPOINT for the current app config, for commands that spawn HF-aware scripts.
+pub fn configured_hf_endpoint() -> Option<String> {
+    let config = load_config();
+    hf_endpoint_for_source(&config.hf_source, config.hf_custom_endpoint.as_deref())
+}
+
 // ─── Network Config Commands ───
 
 #[tauri::command]
@@ -295,13 +584,142 @@ pub fn save_network_config(
     https_proxy: Option<String>,
     ssl_cert_file: Option<String>,
     ssl_cert_dir: Option<String>,
+    hf_transfer: Option<bool>,
 ) -> Result<(), String> {
-    let mut config = load_config();
-    config.network.http_proxy = http_proxy.filter(|s| !s.is_empty());
-    config.network.https_proxy = https_proxy.filter(|s| !s.is_empty());
-    config.network.ssl_cert_file = ssl_cert_file.filter(|s| !s.is_empty());
-    config.network.ssl_cert_dir = ssl_cert_dir.filter(|s| !s.is_empty());
-    save_config(&config)
+    update_config(|config| {
+        config.network.http_proxy = http_proxy.filter(|s| !s.is_empty());
+        config.network.https_proxy = https_proxy.filter(|s| !s.is_empty());
+        config.network.ssl_cert_file = ssl_cert_file.filter(|s| !s.is_empty());
+        config.network.ssl_cert_dir = ssl_cert_dir.filter(|s| !s.is_empty());
+        if let Some(enabled) = hf_transfer {
+            config.network.hf_transfer = enabled;
+        }
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub fn set_prevent_sleep(enabled: bool) -> Result<(), String> {
+    update_config(|config| {
+        config.prevent_sleep = Some(enabled);
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub fn set_notification_preferences(notify_on_complete: bool) -> Result<(), String> {
+    update_config(|config| {
+        config.notify_on_complete = notify_on_complete;
+        Ok(())
+    })
+}
+
+/// Turn automatic removal of fused files after a successful export on or off.
+#[tauri::command]
+pub fn set_autoclean_policy(autoclean_after_export: bool) -> Result<(), String> {
+    update_config(|config| {
+        config.autoclean_after_export = autoclean_after_export;
+        Ok(())
+    })
+}
+
+/// Save (or with `None`, clear) the settings used by `export_with_defaults`.
+#[tauri::command]
+pub fn set_default_export_format(default_export: Option<DefaultExport>) -> Result<(), String> {
+    let default_export = match default_export {
+        Some(mut d) => {
+            if !["ollama", "gguf", "mlx"].contains(&d.format.as_str()) {
+                return Err(format!("Unknown export format: {}", d.format));
+            }
+            d.quantization = d.quantization.filter(|q| !q.trim().is_empty());
+            d.tag = d.tag.filter(|t| !t.trim().is_empty());
+            Some(d)
+        }
+        None => None,
+    };
+    update_config(|config| {
+        config.default_export = default_export;
+        Ok(())
+    })
+}
+
+/// Show `alias` instead of `model_id` in the model pickers.
+#[tauri::command]
+pub fn set_model_alias(model_id: String, alias: String) -> Result<(), String> {
+    let model_id = model_id.trim().to_string();
+    let alias = alias.trim().to_string();
+    if model_id.is_empty() {
+        return Err("Model id is required".to_string());
+    }
+    if alias.is_empty() {
+        return Err("Alias cannot be empty".to_string());
+    }
+    update_config(|config| {
+        config.aliases.insert(model_id, alias);
+        Ok(())
+    })
+}
+
+/// Go back to showing the full id for `model_id`.
+#[tauri::command]
+pub fn clear_model_alias(model_id: String) -> Result<(), String> {
+    update_config(|config| {
+        config.aliases.remove(model_id.trim());
+        Ok(())
+    })
+}
+
+/// Replace the list of extra directories scanned for .gguf files.
+#[tauri::command]
+pub fn set_gguf_dirs(dirs: Vec<String>) -> Result<(), String> {
+    let mut cleaned: Vec<String> = Vec::new();
+    for dir in dirs.iter().map(|d| d.trim()).filter(|d| !d.is_empty()) {
+        if !std::path::Path::new(dir).is_dir() {
+            return Err(format!("Not a directory: {}", dir));
+        }
+        if !cleaned.iter().any(|d| d == dir) {
+            cleaned.push(dir.to_string());
+        }
+    }
+    update_config(|config| {
+        config.gguf_dirs = cleaned;
+        Ok(())
+    })
+}
+
+/// HF_HUB_ENABLE_HF_TRANSFER env pair when the user opted into hf_transfer.
+pub fn hf_transfer_env() -> Option<(&'static str, &'static str)> {
+    load_config()
+        .network
+        .hf_transfer
+        .then_some(("HF_HUB_ENABLE_HF_TRANSFER", "1"))
+}
+
+/// HTTP client honouring the user's proxy and custom CA settings, for
+/// downloads made by the app itself rather than by spawned tools.
+pub fn configured_http_client() -> Result<reqwest::Client, String> {
+    let net = load_config().network;
+    let mut builder = reqwest::Client::builder();
+    if let Some(ref proxy) = net.http_proxy {
+        builder = builder.proxy(
+            reqwest::Proxy::http(proxy).map_err(|e| format!("Invalid HTTP proxy {}: {}", proxy, e))?,
+        );
+    }
+    if let Some(ref proxy) = net.https_proxy {
+        builder = builder.proxy(
+            reqwest::Proxy::https(proxy).map_err(|e| format!("Invalid HTTPS proxy {}: {}", proxy, e))?,
+        );
+    }
+    if let Some(ref cert_file) = net.ssl_cert_file {
+        let pem = std::fs::read(cert_file)
+            .map_err(|e| format!("Failed to read CA bundle {}: {}", cert_file, e))?;
+        for cert in reqwest::Certificate::from_pem_bundle(&pem)
+            .map_err(|e| format!("Invalid CA bundle {}: {}", cert_file, e))?
+        {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+    builder.build().map_err(|e| format!("Failed to build HTTP client: {}", e))
 }
 /// enterprise networks: proxy, SSL certs, and shell env inheritance.
 /// This ensures that uv commands work behind corporate proxies and
@@ -1,10 +1,69 @@
 use tauri::Emitter;
 use crate::fs::ProjectDirManager;
 use crate::python::PythonExecutor;
+use crate::commands::native_notification::notify_job_finished;
+use crate::python::parser::{JsonLineParser, ParsedLine};
 use std::collections::{HashMap, HashSet};
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Mutex;
+use once_cell::sync::Lazy;
+use crate::commands::processes::maybe_caffeinate;
+use crate::commands::report::script_protocol_supported;
+
+/// A running generation: its PID (0 until the process has spawned) and the
+/// dataset versions it writes to, which pruning must leave alone.
+/// The entry stays until the run's task exits, so a stopped run still holds
+/// the project's slot and its task only ever releases its own entry.
+struct GenerationRun {
+    /// Identifies this run's entry, so a finished task never removes a newer run's.
+    token: u64,
+    pid: u32,
+    versions: Vec<String>,
+}
+
+static NEXT_GENERATION_TOKEN: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
+
+/// Running generations, keyed by project id.
+static GENERATION_PROCESSES: Lazy<Mutex<HashMap<String, GenerationRun>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+static CLEANING_PID: AtomicU32 = AtomicU32::new(0);
+/// Set when the running clean was stopped on request, so its exit is reported
+/// as a stop rather than judged from the exit code.
+static CLEANING_CANCELLED: AtomicBool = AtomicBool::new(false);
+
+/// Terminate every tracked generation process. Returns how many were stopped.
+pub fn stop_tracked_generation() -> u32 {
+    let pids: Vec<u32> = GENERATION_PROCESSES
+        .lock()
+        .map(|map| map.values().map(|run| run.pid).filter(|pid| *pid != 0).collect())
+        .unwrap_or_default();
+    for pid in &pids {
+        crate::commands::processes::terminate_pid(*pid);
+    }
+    pids.len() as u32
+}
+
+/// Whether any generation run, started or still starting, is tracked.
+pub fn any_generation_active() -> bool {
+    GENERATION_PROCESSES.lock().map(|map| !map.is_empty()).unwrap_or(false)
+}
+
+/// Whether a cleaning process is running.
+pub fn is_cleaning_active() -> bool {
+    CLEANING_PID.load(Ordering::SeqCst) != 0
+}
 
-static GENERATION_PID: AtomicU32 = AtomicU32::new(0);
+/// Terminate the tracked cleaning process, if any. Returns 1 if one was stopped.
+pub fn stop_tracked_cleaning() -> u32 {
+    match CLEANING_PID.swap(0, Ordering::SeqCst) {
+        0 => 0,
+        pid => {
+            CLEANING_CANCELLED.store(true, Ordering::SeqCst);
+            crate::commands::processes::terminate_pid(pid);
+            1
+        }
+    }
+}
 
 #[derive(Debug, Clone, serde::Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -14,11 +73,23 @@ pub struct CleaningOptions {
     pub fuzzy_dedup_threshold: Option<f64>,
 }
 
+/// Segmentation strategies accepted by clean_data.py's `--strategy`.
+const CLEANING_STRATEGIES: &[&str] = &["auto", "paragraph_balanced", "heading_split", "fixed"];
+
+/// Allowed `chunk_size` range, in estimated tokens per segment.
+const CLEANING_CHUNK_SIZE_RANGE: std::ops::RangeInclusive<usize> = 128..=8192;
+
 #[tauri::command]
-pub async fn stop_generation() -> Result<(), String> {
-    let pid = GENERATION_PID.swap(0, Ordering::SeqCst);
+pub async fn stop_generation(project_id: String) -> Result<(), String> {
+    // The run's task releases the entry once the process has exited.
+    let pid = GENERATION_PROCESSES
+        .lock()
+        .map_err(|e| e.to_string())?
+        .get(&project_id)
+        .ok_or("No generation process running for this project")?
+        .pid;
     if pid == 0 {
-        return Err("No generation process running".into());
+        return Err("Generation is still starting; try again in a moment".into());
     }
     unsafe {
         // Kill the process group (negative PID) to stop both caffeinate and python
@@ -29,13 +100,46 @@ pub async fn stop_generation() -> Result<(), String> {
     Ok(())
 }
 
+#[tauri::command]
+pub async fn stop_cleaning() -> Result<(), String> {
+    let pid = CLEANING_PID.swap(0, Ordering::SeqCst);
+    if pid == 0 {
+        return Err("No cleaning process running".into());
+    }
+    CLEANING_CANCELLED.store(true, Ordering::SeqCst);
+    crate::commands::processes::terminate_pid(pid);
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn start_cleaning(
     app: tauri::AppHandle,
     project_id: String,
     lang: Option<String>,
     options: Option<CleaningOptions>,
+    prevent_sleep: Option<bool>,
+    chunk_size: Option<usize>,
+    strategy: Option<String>,
 ) -> Result<(), String> {
+    if let Some(size) = chunk_size {
+        if !CLEANING_CHUNK_SIZE_RANGE.contains(&size) {
+            return Err(format!(
+                "chunk_size must be between {} and {}",
+                CLEANING_CHUNK_SIZE_RANGE.start(),
+                CLEANING_CHUNK_SIZE_RANGE.end()
+            ));
+        }
+    }
+    if let Some(s) = strategy.as_deref() {
+        if !CLEANING_STRATEGIES.contains(&s) {
+            return Err(format!(
+                "Unknown cleaning strategy '{}'. Expected one of: {}",
+                s,
+                CLEANING_STRATEGIES.join(", ")
+            ));
+        }
+    }
+
     let executor = PythonExecutor::default();
     if !executor.is_ready() {
         return Err("Python environment is not ready. Please set up the environment first.".into());
@@ -64,6 +168,10 @@ pub async fn start_cleaning(
         return Err(format!("Cleaning script not found at: {}", script.display()));
     }
     let supports_lang = script_supports_lang_arg(&script);
+    let supports_chunking = script_protocol_supported(&executor, "clean_data.py");
+    if !supports_chunking && (chunk_size.is_some() || strategy.is_some()) {
+        return Err("The installed cleaning script does not support chunk size or strategy selection.".into());
+    }
 
     let python_bin = executor.python_bin().clone();
 
@@ -76,25 +184,31 @@ pub async fn start_cleaning(
             .unwrap_or(0.85)
             .clamp(0.5, 1.0);
 
-        let mut caffeinate_args: Vec<String> = vec![
-            "-i".to_string(),
-            python_bin.to_string_lossy().to_string(),
+        let mut py_args: Vec<String> = vec![
             script.to_string_lossy().to_string(),
             "--project-dir".to_string(),
             project_path.to_string_lossy().to_string(),
         ];
         if enable_privacy_filter {
-            caffeinate_args.push("--privacy-filter".to_string());
+            py_args.push("--privacy-filter".to_string());
         }
         if enable_fuzzy_dedup {
-            caffeinate_args.push("--fuzzy-dedup".to_string());
-            caffeinate_args.push("--fuzzy-threshold".to_string());
-            caffeinate_args.push(format!("{:.2}", fuzzy_threshold));
+            py_args.push("--fuzzy-dedup".to_string());
+            py_args.push("--fuzzy-threshold".to_string());
+            py_args.push(format!("{:.2}", fuzzy_threshold));
+        }
+        if let Some(size) = chunk_size {
+            py_args.push("--chunk-size".to_string());
+            py_args.push(size.to_string());
+        }
+        if let Some(s) = strategy {
+            py_args.push("--strategy".to_string());
+            py_args.push(s);
         }
         let lang_value = lang.unwrap_or_else(|| "en".to_string());
         if supports_lang {
-            caffeinate_args.push("--lang".to_string());
-            caffeinate_args.push(lang_value);
+            py_args.push("--lang".to_string());
+            py_args.push(lang_value);
         } else {
             let _ = app.emit(
                 "cleaning:log",
@@ -105,14 +219,19 @@ pub async fn start_cleaning(
         }
 
         // Wrap with caffeinate -i to prevent idle sleep during cleaning
-        let result = tokio::process::Command::new("caffeinate")
-            .args(&caffeinate_args)
+        let result = maybe_caffeinate(&python_bin, prevent_sleep)
+            .args(&py_args)
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::piped())
             .spawn();
 
         match result {
             Ok(mut child) => {
+                CLEANING_CANCELLED.store(false, Ordering::SeqCst);
+                if let Some(pid) = child.id() {
+                    CLEANING_PID.store(pid, Ordering::SeqCst);
+                }
+
                 use tokio::io::{AsyncBufReadExt, BufReader};
 
                 let mut stdout_task = None;
@@ -121,15 +240,24 @@ pub async fn start_cleaning(
                     stdout_task = Some(tokio::spawn(async move {
                         let reader = BufReader::new(stdout);
                         let mut lines = reader.lines();
-                        while let Ok(Some(line)) = lines.next_line().await {
-                            // Parse JSON events from Python script
-                            if let Ok(event) = serde_json::from_str::<serde_json::Value>(&line) {
+                        // Parse JSON events from Python script
+                        let handle = |item: ParsedLine| match item {
+                            ParsedLine::Event(event) => {
                                 let event_type = event["type"].as_str().unwrap_or("unknown");
                                 let _ = app_stdout.emit(&format!("cleaning:{}", event_type), &event);
-                            } else {
+                            }
+                            ParsedLine::Text(line) => {
                                 let _ = app_stdout.emit("cleaning:log", serde_json::json!({ "line": line }));
                             }
+                            ParsedLine::ParseError(line) => {
+                                let _ = app_stdout.emit("cleaning:parse_error", serde_json::json!({ "line": line }));
+                            }
+                        };
+                        let mut parser = JsonLineParser::new();
+                        while let Ok(Some(line)) = lines.next_line().await {
+                            parser.push(&line).into_iter().for_each(handle);
                         }
+                        parser.flush().into_iter().for_each(handle);
                     }));
                 }
 
@@ -148,9 +276,19 @@ pub async fn start_cleaning(
                     }));
                 }
 
-                match child.wait().await {
+                let wait_result = child.wait().await;
+                CLEANING_PID.store(0, Ordering::SeqCst);
+                let cancelled = CLEANING_CANCELLED.swap(false, Ordering::SeqCst);
+
+                match wait_result {
                     Ok(status) => {
-                        if !status.success() {
+                        if cancelled {
+                            // Half-written segments would look like a finished clean.
+                            let _ = std::fs::remove_dir_all(&cleaned_dir);
+                            let _ = app.emit("cleaning:stopped", serde_json::json!({
+                                "message": "Cleaning stopped, partial output removed"
+                            }));
+                        } else if !status.success() {
                             let _ = app.emit("cleaning:error", serde_json::json!({
                                 "message": "Cleaning process exited with error"
                             }));
@@ -181,6 +319,113 @@ pub async fn start_cleaning(
     Ok(())
 }
 
+/// Per-project file holding the settings of the last successful generation.
+const LAST_GENERATION_FILE: &str = "last_generation.json";
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct LastGeneration {
+    pub model: String,
+    pub mode: String,
+    pub source: String,
+    #[serde(default)]
+    pub quality_scoring: bool,
+    #[serde(default)]
+    pub updated_at: String,
+}
+
+/// Settings of the project's last successful generation, for prefilling the
+/// generation form. `None` before the first run.
+#[tauri::command]
+pub fn get_last_generation(project_id: String) -> Result<Option<LastGeneration>, String> {
+    let path = ProjectDirManager::new()
+        .project_path(&project_id)
+        .join(LAST_GENERATION_FILE);
+    Ok(std::fs::read_to_string(path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok()))
+}
+
+/// Rough chars-per-token ratio, matching clean_data.py's segmenter.
+const CHARS_PER_TOKEN: f64 = 2.5;
+/// Generation scripts only send this many chars of each segment.
+const GENERATION_SEGMENT_CHARS: usize = 2000;
+
+#[derive(serde::Serialize)]
+pub struct GenerationEstimate {
+    pub segments: usize,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Only for local model sources ("ollama", "lmstudio"); None otherwise.
+    pub estimated_seconds: Option<u64>,
+}
+
+/// (system + template prompt tokens, expected reply tokens) per segment.
+fn mode_token_overhead(mode: &str) -> (u64, u64) {
+    match mode {
+        "style" => (450, 900),
+        "chat" => (450, 1200),
+        "instruct" => (350, 500),
+        _ => (350, 450),
+    }
+}
+
+/// Estimate the token volume of generating from the project's cleaned
+/// segments, before starting a run. Purely from `cleaned/segments.jsonl`.
+#[tauri::command]
+pub fn estimate_generation_cost(
+    project_id: String,
+    mode: Option<String>,
+    source: Option<String>,
+) -> Result<GenerationEstimate, String> {
+    let segments_path = ProjectDirManager::new()
+        .project_path(&project_id)
+        .join("cleaned")
+        .join("segments.jsonl");
+    let content = std::fs::read_to_string(&segments_path)
+        .map_err(|_| "No cleaned data found. Run cleaning first.".to_string())?;
+
+    let mut segments = 0usize;
+    let mut segment_tokens: u64 = 0;
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        let text_chars = serde_json::from_str::<serde_json::Value>(line)
+            .ok()
+            .and_then(|v| v["text"].as_str().map(|t| t.chars().count()))
+            .unwrap_or_else(|| line.chars().count());
+        segments += 1;
+        segment_tokens +=
+            (text_chars.min(GENERATION_SEGMENT_CHARS) as f64 / CHARS_PER_TOKEN).ceil() as u64;
+    }
+
+    let (prompt_overhead, reply_tokens) = mode_token_overhead(mode.as_deref().unwrap_or("qa"));
+    let input_tokens = segment_tokens + prompt_overhead * segments as u64;
+    let output_tokens = reply_tokens * segments as u64;
+
+    // Ballpark Apple Silicon throughput for a ~7B local model.
+    let estimated_seconds = match source.as_deref() {
+        Some("ollama") | Some("lmstudio") => Some(input_tokens / 400 + output_tokens / 25),
+        _ => None,
+    };
+
+    Ok(GenerationEstimate {
+        segments,
+        input_tokens,
+        output_tokens,
+        estimated_seconds,
+    })
+}
+
+/// Optional `generate_dataset` settings.
+#[derive(Debug, Clone, serde::Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerationOptions {
+    /// Merge the new examples into this existing version.
+    pub append_to_version: Option<String>,
+    /// Overrides the configured keep-awake setting for this run.
+    pub prevent_sleep: Option<bool>,
+    /// Continue this incomplete version in place.
+    pub resume_version: Option<String>,
+}
+
 #[tauri::command]
 pub async fn generate_dataset(
     app: tauri::AppHandle,
@@ -193,7 +438,10 @@ pub async fn generate_dataset(
     quality_scoring: Option<bool>,
     retry_failed_only: Option<bool>,
     retry_version: Option<String>,
+    options: Option<GenerationOptions>,
 ) -> Result<String, String> {
+    let GenerationOptions { append_to_version, prevent_sleep, resume_version } =
+        options.unwrap_or_default();
     let executor = PythonExecutor::default();
     if !executor.is_ready() {
         return Err("Python environment is not ready.".into());
@@ -213,6 +461,34 @@ pub async fn generate_dataset(
         }
     }
 
+    // Appending writes into an existing version instead of creating a new one.
+    let append_target = match append_to_version.filter(|v| !v.trim().is_empty()) {
+        Some(v) => {
+            if v == "legacy" || v.contains('/') || v.contains("..") {
+                return Err(format!("Cannot append to dataset version: {}", v));
+            }
+            if !dataset_root.join(&v).join("train.jsonl").exists() {
+                return Err(format!("Dataset version not found for append: {}", v));
+            }
+            Some(v)
+        }
+        None => None,
+    };
+
+    // Resuming continues inside an incomplete version's own directory.
+    let resume_target = match resume_version.filter(|v| !v.trim().is_empty()) {
+        Some(v) => {
+            if v == "legacy" || v.contains('/') || v.contains("..") {
+                return Err(format!("Cannot resume dataset version: {}", v));
+            }
+            if !dataset_root.join(&v).join("train.jsonl").exists() {
+                return Err(format!("Dataset version not found for resume: {}", v));
+            }
+            Some(v)
+        }
+        None => None,
+    };
+
     let mut effective_model = model;
     let mut effective_mode = mode;
     let mut effective_source = source;
@@ -287,11 +563,40 @@ pub async fn generate_dataset(
     };
 
     let python_bin = executor.python_bin().clone();
-    let should_resume = resume.unwrap_or(false);
+    let hf_endpoint = crate::commands::config::configured_hf_endpoint();
+    let should_resume = resume.unwrap_or(false) || resume_target.is_some();
     let enable_quality_scoring = quality_scoring.unwrap_or(false);
+    // A failed fresh run keeps its partial output (marked incomplete) for resume.
+    let resumed = resume_target.is_some();
+    let keep_partial_on_failure = resumed || (append_target.is_none() && !retry_failed);
+
+    let timestamp = resume_target
+        .clone()
+        .unwrap_or_else(|| chrono::Local::now().format("%Y%m%d_%H%M%S").to_string());
+
+    // Only one run per project, so stop_generation always reaches the live one.
+    // Claimed before anything is written, so a rejected start leaves no files.
+    let run_token = NEXT_GENERATION_TOKEN.fetch_add(1, Ordering::SeqCst);
+    {
+        let mut map = GENERATION_PROCESSES.lock().map_err(|e| e.to_string())?;
+        if map.contains_key(&project_id) {
+            return Err("Dataset generation is already running for this project.".into());
+        }
+        let versions = std::iter::once(timestamp.clone()).chain(append_target.clone()).collect();
+        map.insert(project_id.clone(), GenerationRun { token: run_token, pid: 0, versions });
+    }
+    let release_slot = {
+        let project_id = project_id.clone();
+        move || {
+            if let Ok(mut map) = GENERATION_PROCESSES.lock() {
+                if map.get(&project_id).is_some_and(|run| run.token == run_token) {
+                    map.remove(&project_id);
+                }
+            }
+        }
+    };
 
     // Create timestamped output directory for this generation run
-    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S").to_string();
     let output_dir = dataset_root.join(&timestamp);
     let _ = std::fs::create_dir_all(&output_dir);
 
@@ -315,12 +620,25 @@ pub async fn generate_dataset(
         "quality_scoring_enabled": enable_quality_scoring,
         "retry_failed_only": retry_failed,
         "retry_version": resolved_retry_version,
+        // Cleared once the run finishes successfully.
+        "incomplete": true,
     });
     let _ = std::fs::write(
         output_dir.join("meta.json"),
         serde_json::to_string_pretty(&meta).unwrap_or_default(),
     );
 
+    // Remembered as the project's form defaults once the run succeeds.
+    // Retries reuse an old version's settings, so they don't count.
+    let last_generation = (!retry_failed).then(|| LastGeneration {
+        model: effective_model.clone(),
+        mode: effective_mode.clone(),
+        source: effective_source.clone(),
+        quality_scoring: enable_quality_scoring,
+        updated_at: chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+    });
+    let last_generation_path = project_path.join(LAST_GENERATION_FILE);
+
     let ts_clone = timestamp.clone();
 
     tokio::spawn(async move {
@@ -359,20 +677,16 @@ pub async fn generate_dataset(
             let _ = app.emit(
                 "dataset:log",
                 serde_json::json!({
-                    "message": "⚠️ Dataset script does not support --lang, fallback to script default language."
+                    "message": "⚠️ Dataset script does not support --lang, fallback to script default language.",
+                    "project_id": project_id,
                 }),
             );
         }
 
         // Wrap with caffeinate -i to prevent idle sleep during generation
-        let mut caffeinate_args: Vec<String> = vec![
-            "-i".to_string(),
-            python_bin.to_string_lossy().to_string(),
-        ];
-        caffeinate_args.extend(py_args);
-
-        let result = tokio::process::Command::new("caffeinate")
-            .args(&caffeinate_args)
+        let result = maybe_caffeinate(&python_bin, prevent_sleep)
+            .args(&py_args)
+            .envs(hf_endpoint.as_ref().map(|e| ("HF_ENDPOINT", e)))
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::piped())
             .spawn();
@@ -380,8 +694,11 @@ pub async fn generate_dataset(
         match result {
             Ok(mut child) => {
                 // Store PID for stop_generation
-                if let Some(pid) = child.id() {
-                    GENERATION_PID.store(pid, Ordering::SeqCst);
+                let child_pid = child.id();
+                if let (Some(pid), Ok(mut map)) = (child_pid, GENERATION_PROCESSES.lock()) {
+                    if let Some(run) = map.get_mut(&project_id).filter(|run| run.token == run_token) {
+                        run.pid = pid;
+                    }
                 }
 
                 use tokio::io::{AsyncBufReadExt, BufReader};
@@ -389,30 +706,56 @@ pub async fn generate_dataset(
                 let mut stdout_task = None;
                 if let Some(stdout) = child.stdout.take() {
                     let app_stdout = app.clone();
+                    let project_id = project_id.clone();
                     stdout_task = Some(tokio::spawn(async move {
                         let reader = BufReader::new(stdout);
                         let mut lines = reader.lines();
-                        while let Ok(Some(line)) = lines.next_line().await {
-                            if let Ok(event) = serde_json::from_str::<serde_json::Value>(&line) {
-                                let event_type = event["type"].as_str().unwrap_or("unknown");
+                        // Parse JSON events from Python script
+                        let handle = |item: ParsedLine| match item {
+                            ParsedLine::Event(event) => {
+                                let mut event = event;
+                                let event_type = event["type"].as_str().unwrap_or("unknown").to_string();
+                                // Tag with the project so concurrent runs can be told apart.
+                                if let Some(obj) = event.as_object_mut() {
+                                    obj.insert("project_id".to_string(), project_id.clone().into());
+                                }
                                 let _ = app_stdout.emit(&format!("dataset:{}", event_type), &event);
-                            } else {
-                                let _ = app_stdout.emit("dataset:log", serde_json::json!({ "line": line }));
                             }
+                            ParsedLine::Text(line) => {
+                                let _ = app_stdout.emit("dataset:log", serde_json::json!({
+                                    "line": line,
+                                    "project_id": project_id,
+                                }));
+                            }
+                            ParsedLine::ParseError(line) => {
+                                let _ = app_stdout.emit("dataset:parse_error", serde_json::json!({
+                                    "line": line,
+                                    "project_id": project_id,
+                                }));
+                            }
+                        };
+                        let mut parser = JsonLineParser::new();
+                        while let Ok(Some(line)) = lines.next_line().await {
+                            parser.push(&line).into_iter().for_each(handle);
                         }
+                        parser.flush().into_iter().for_each(handle);
                     }));
                 }
 
                 let mut stderr_task = None;
                 if let Some(stderr) = child.stderr.take() {
                     let app_stderr = app.clone();
+                    let project_id = project_id.clone();
                     stderr_task = Some(tokio::spawn(async move {
                         let reader = BufReader::new(stderr);
                         let mut lines = reader.lines();
                         while let Ok(Some(line)) = lines.next_line().await {
                             let line = line.trim();
                             if !line.is_empty() {
-                                let _ = app_stderr.emit("dataset:log", serde_json::json!({ "line": line }));
+                                let _ = app_stderr.emit("dataset:log", serde_json::json!({
+                                    "line": line,
+                                    "project_id": project_id,
+                                }));
                             }
                         }
                     }));
@@ -420,30 +763,92 @@ pub async fn generate_dataset(
 
                 let wait_result = child.wait().await;
                 // Clear PID after process exits so stop_generation can use it while running
-                GENERATION_PID.store(0, Ordering::SeqCst);
+                release_slot();
 
                 match wait_result {
                     Ok(status) => {
                         if status.success() {
+                            if let Some(ref last) = last_generation {
+                                let _ = std::fs::write(
+                                    &last_generation_path,
+                                    serde_json::to_string_pretty(last).unwrap_or_default(),
+                                );
+                            }
+                        }
+                        if status.success() && append_target.is_some() {
+                            let target = append_target.clone().unwrap_or_default();
+                            let target_dir = dataset_root.join(&target);
+                            match merge_into_version(&output_dir, &target_dir) {
+                                Ok((added_train, added_valid)) => {
+                                    let _ = std::fs::remove_dir_all(&output_dir);
+                                    let (train_count, valid_count, train_size, valid_size) =
+                                        version_file_stats(&target_dir);
+                                    let _ = app.emit("dataset:version", serde_json::json!({
+                                        "version": target,
+                                        "appended": true,
+                                        "added_train": added_train,
+                                        "added_valid": added_valid,
+                                        "train_count": train_count,
+                                        "valid_count": valid_count,
+                                        "train_size": train_size,
+                                        "valid_size": valid_size,
+                                        "project_id": project_id,
+                                    }));
+                                    let outcome = format!(
+                                        "added {} train / {} valid examples to {}",
+                                        added_train, added_valid, target
+                                    );
+                                    notify_job_finished(&app, &project_id, "Dataset generated", &outcome).await;
+                                }
+                                Err(e) => {
+                                    let _ = app.emit("dataset:error", serde_json::json!({
+                                        "message": format!("Failed to append to version {}: {}", target, e),
+                                        "project_id": project_id,
+                                    }));
+                                }
+                            }
+                        } else if status.success() {
                             // Rename directory to completion timestamp
                             let final_ts = chrono::Local::now().format("%Y%m%d_%H%M%S").to_string();
                             let final_dir = dataset_root.join(&final_ts);
-                            let version_id = if std::fs::rename(&output_dir, &final_dir).is_ok() {
-                                final_ts
+                            let (version_id, version_dir) = if std::fs::rename(&output_dir, &final_dir).is_ok() {
+                                (final_ts, final_dir)
                             } else {
-                                ts_clone.clone()
+                                (ts_clone.clone(), output_dir.clone())
                             };
-                            // Success: emit with version id
+                            clear_incomplete_flag(&version_dir);
+                            let (train_count, valid_count, train_size, valid_size) =
+                                version_file_stats(&version_dir);
+                            // Success: emit with version id and counts
                             let _ = app.emit("dataset:version", serde_json::json!({
-                                "version": version_id
+                                "version": version_id,
+                                "train_count": train_count,
+                                "valid_count": valid_count,
+                                "train_size": train_size,
+                                "valid_size": valid_size,
+                                "project_id": project_id,
                             }));
+                            let outcome = format!(
+                                "generated {} train / {} valid examples",
+                                train_count, valid_count
+                            );
+                            notify_job_finished(&app, &project_id, "Dataset generated", &outcome).await;
                         } else {
                             let code = status.code().unwrap_or(-1);
-                            // Clean up incomplete directory on failure/stop
-                            let _ = std::fs::remove_dir_all(&output_dir);
-                            if code == 143 || code == -1 {
+                            let stopped = code == 143 || code == -1;
+                            // Clean up incomplete directory on failure/stop, unless
+                            // it holds progress that retry_generation can resume.
+                            let kept = if stopped { resumed } else { keep_partial_on_failure };
+                            let kept = kept && discard_unless_partial(&output_dir);
+                            if stopped {
                                 let _ = app.emit("dataset:stopped", serde_json::json!({
-                                    "message": "Generation stopped, incomplete data cleaned up"
+                                    "message": if kept {
+                                        "Generation stopped, partial data kept for resume"
+                                    } else {
+                                        "Generation stopped, incomplete data cleaned up"
+                                    },
+                                    "incomplete_version": kept.then(|| ts_clone.clone()),
+                                    "project_id": project_id,
                                 }));
                             } else {
                                 let msg = if code == 2 {
@@ -453,15 +858,20 @@ pub async fn generate_dataset(
                                     format!("Generation exited with code {}", code)
                                 };
                                 let _ = app.emit("dataset:error", serde_json::json!({
-                                    "message": msg
+                                    "message": msg,
+                                    "incomplete_version": kept.then(|| ts_clone.clone()),
+                                    "project_id": project_id,
                                 }));
                             }
                         }
                     }
                     Err(e) => {
-                        let _ = std::fs::remove_dir_all(&output_dir);
+                        if !resumed {
+                            let _ = std::fs::remove_dir_all(&output_dir);
+                        }
                         let _ = app.emit("dataset:error", serde_json::json!({
-                            "message": e.to_string()
+                            "message": e.to_string(),
+                            "project_id": project_id,
                         }));
                     }
                 }
@@ -474,9 +884,13 @@ pub async fn generate_dataset(
                 }
             }
             Err(e) => {
-                let _ = std::fs::remove_dir_all(&output_dir);
+                release_slot();
+                if !resumed {
+                    let _ = std::fs::remove_dir_all(&output_dir);
+                }
                 let _ = app.emit("dataset:error", serde_json::json!({
-                    "message": e.to_string()
+                    "message": e.to_string(),
+                    "project_id": project_id,
                 }));
             }
         }
@@ -485,6 +899,73 @@ pub async fn generate_dataset(
     Ok(timestamp)
 }
 
+/// Keep `dir` if it holds generated examples; otherwise remove it.
+/// Returns whether it was kept.
+fn discard_unless_partial(dir: &std::path::Path) -> bool {
+    if count_jsonl_lines(&dir.join("train.jsonl")) > 0 {
+        return true;
+    }
+    let _ = std::fs::remove_dir_all(dir);
+    false
+}
+
+/// Drop the `incomplete` marker from a version's meta.json after success.
+fn clear_incomplete_flag(version_dir: &std::path::Path) {
+    let meta_path = version_dir.join("meta.json");
+    let Some(mut meta) = std::fs::read_to_string(&meta_path)
+        .ok()
+        .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok())
+    else {
+        return;
+    };
+    if let Some(obj) = meta.as_object_mut() {
+        obj.remove("incomplete");
+    }
+    let _ = std::fs::write(&meta_path, serde_json::to_string_pretty(&meta).unwrap_or_default());
+}
+
+/// Continue an incomplete generation run in place with `--resume`, using the
+/// model/mode/source recorded in its meta.json. Returns the version id.
+#[tauri::command]
+pub async fn retry_generation(
+    app: tauri::AppHandle,
+    project_id: String,
+    version: String,
+    lang: Option<String>,
+) -> Result<String, String> {
+    let meta_path = ProjectDirManager::new()
+        .project_path(&project_id)
+        .join("dataset")
+        .join(&version)
+        .join("meta.json");
+    let meta: serde_json::Value = std::fs::read_to_string(&meta_path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .ok_or_else(|| format!("No metadata found for dataset version: {}", version))?;
+    if !meta["incomplete"].as_bool().unwrap_or(false) {
+        return Err(format!("Dataset version {} is already complete", version));
+    }
+    let source = meta["source"].as_str().unwrap_or("").to_string();
+    // Only the API-backed scripts can skip already-generated segments.
+    if source != "ollama" && source != "lmstudio" {
+        return Err(format!("Generation source '{}' does not support resume", source));
+    }
+    generate_dataset(
+        app,
+        project_id,
+        meta["model"].as_str().unwrap_or("").to_string(),
+        meta["mode"].as_str().unwrap_or("").to_string(),
+        source,
+        Some(true),
+        lang,
+        meta["quality_scoring_enabled"].as_bool(),
+        None,
+        None,
+        Some(GenerationOptions { resume_version: Some(version), ..Default::default() }),
+    )
+    .await
+}
+
 // Info about a single dataset version
 #[derive(serde::Serialize, Clone)]
 pub struct DatasetVersionInfo {
@@ -503,6 +984,49 @@ pub struct DatasetVersionInfo {
     pub quality_score: Option<f64>,
     pub quality_grade: String,
     pub quality_scoring_enabled: bool,
+    pub appended: bool,
+    /// A failed run's partial output, resumable with `retry_generation`.
+    pub incomplete: bool,
+    /// Adapters in this project whose training_meta.json names this version.
+    pub trained_adapters: Vec<String>,
+}
+
+/// Map dataset version -> adapter names trained on it, from each adapter's
+/// training_meta.json. Adapters predating `dataset_version` are matched by
+/// their recorded `dataset_path`.
+fn adapters_by_dataset_version(project_path: &std::path::Path) -> HashMap<String, Vec<String>> {
+    let dataset_root = project_path.join("dataset");
+    let mut map: HashMap<String, Vec<String>> = HashMap::new();
+    let Ok(entries) = std::fs::read_dir(project_path.join("adapters")) else {
+        return map;
+    };
+    for entry in entries.filter_map(|e| e.ok()) {
+        let Some(meta) = std::fs::read_to_string(entry.path().join("training_meta.json"))
+            .ok()
+            .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok())
+        else {
+            continue;
+        };
+        let version = meta["dataset_version"].as_str().map(String::from).or_else(|| {
+            let path = std::path::PathBuf::from(meta["dataset_path"].as_str()?);
+            if path == dataset_root {
+                Some("legacy".to_string())
+            } else if path.parent() == Some(dataset_root.as_path()) {
+                path.file_name().map(|n| n.to_string_lossy().to_string())
+            } else {
+                None
+            }
+        });
+        if let Some(version) = version {
+            map.entry(version)
+                .or_default()
+                .push(entry.file_name().to_string_lossy().to_string());
+        }
+    }
+    for names in map.values_mut() {
+        names.sort();
+    }
+    map
 }
 
 /// List all dataset versions for a project, sorted newest first
@@ -518,6 +1042,7 @@ pub fn list_dataset_versions(
     }
 
     let mut versions: Vec<DatasetVersionInfo> = Vec::new();
+    let mut trained_by = adapters_by_dataset_version(&dir_manager.project_path(&project_id));
 
     let entries = std::fs::read_dir(&dataset_root)
         .map_err(|e| format!("Failed to read dataset directory: {}", e))?;
@@ -566,6 +1091,12 @@ pub fn list_dataset_versions(
 
         let failed_path = path.join("failed_segments.jsonl");
         let failed_count = count_jsonl_lines(&failed_path);
+        let meta_json = std::fs::read_to_string(&meta_path)
+            .ok()
+            .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok())
+            .unwrap_or_default();
+        let appended = meta_json["appended"].as_bool().unwrap_or(false);
+        let incomplete = meta_json["incomplete"].as_bool().unwrap_or(false);
 
         let quality_path = path.join("quality.json");
         if quality_path.exists() {
@@ -579,6 +1110,7 @@ pub fn list_dataset_versions(
             }
         }
 
+        let trained_adapters = trained_by.remove(&dir_name).unwrap_or_default();
         versions.push(DatasetVersionInfo {
             version: dir_name,
             path: path.to_string_lossy().to_string(),
@@ -595,6 +1127,9 @@ pub fn list_dataset_versions(
             quality_score,
             quality_grade,
             quality_scoring_enabled,
+            appended,
+            incomplete,
+            trained_adapters,
         });
     }
 
@@ -633,6 +1168,9 @@ pub fn list_dataset_versions(
             quality_score: None,
             quality_grade: String::new(),
             quality_scoring_enabled: false,
+            appended: false,
+            incomplete: false,
+            trained_adapters: trained_by.remove("legacy").unwrap_or_default(),
         });
     }
 
@@ -641,6 +1179,60 @@ pub fn list_dataset_versions(
     Ok(versions)
 }
 
+#[derive(serde::Serialize)]
+pub struct PruneVersionsResult {
+    pub freed_bytes: u64,
+    pub removed: Vec<String>,
+}
+
+/// Dataset versions a running generation in `project_id` is writing to.
+fn active_generation_versions(project_id: &str) -> Vec<String> {
+    GENERATION_PROCESSES
+        .lock()
+        .ok()
+        .and_then(|map| map.get(project_id).map(|run| run.versions.clone()))
+        .unwrap_or_default()
+}
+
+/// Delete dataset versions whose timestamped name is older than `older_than_days`.
+/// The newest complete version is always kept, as are versions a running
+/// generation writes to and versions with unparseable names.
+#[tauri::command]
+pub fn prune_dataset_versions_by_age(
+    project_id: String,
+    older_than_days: u64,
+) -> Result<PruneVersionsResult, String> {
+    let cutoff = chrono::Local::now().naive_local()
+        - chrono::Duration::days(older_than_days.min(i64::MAX as u64) as i64);
+    let active = active_generation_versions(&project_id);
+    let versions = list_dataset_versions(project_id)?;
+    // Versions are listed newest first; "legacy" sorts ahead but is never pruned.
+    let newest_complete = versions
+        .iter()
+        .find(|v| !v.incomplete && parse_version_timestamp(&v.version).is_some())
+        .map(|v| v.version.clone());
+
+    let mut result = PruneVersionsResult { freed_bytes: 0, removed: Vec::new() };
+    for version in &versions {
+        if newest_complete.as_ref() == Some(&version.version) || active.contains(&version.version) {
+            continue;
+        }
+        let Some(created) = parse_version_timestamp(&version.version) else {
+            continue;
+        };
+        if created >= cutoff {
+            continue;
+        }
+        let path = std::path::Path::new(&version.path);
+        let size = crate::commands::storage::dir_size(path);
+        std::fs::remove_dir_all(path)
+            .map_err(|e| format!("Failed to delete dataset version {}: {}", version.version, e))?;
+        result.freed_bytes += size;
+        result.removed.push(version.version.clone());
+    }
+    Ok(result)
+}
+
 /// Binary document extensions that need Python-based text extraction for snippets.
 const BINARY_SNIPPET_EXTS: &[&str] = &["pdf", "docx", "doc"];
 
@@ -695,6 +1287,184 @@ pub struct RawFileSample {
     pub snippet: String,
 }
 
+#[derive(serde::Serialize)]
+pub struct ModeRecommendation {
+    /// Best-fit mode: "qa", "instruct", "style" or "chat"
+    pub recommended: String,
+    /// Per-mode status: "recommended" | "available" | "cautious"
+    pub status: HashMap<String, String>,
+    /// i18n key for the hint shown under the mode picker
+    pub hint_key: String,
+    pub rationale: String,
+}
+
+/// Content signals gathered from raw file snippets.
+#[derive(Default)]
+struct ModeSignals {
+    prose: u32,
+    structured: u32,
+    dialogue: u32,
+    headings: u32,
+    snippet_chars: usize,
+}
+
+fn collect_mode_signals(samples: &[RawFileSample]) -> ModeSignals {
+    const STRUCTURED_EXTS: &[&str] = &["json", "jsonl", "csv", "tsv", "xml", "yaml", "yml"];
+    const PROSE_EXTS: &[&str] = &["txt", "md", "markdown", "doc", "docx", "pdf", "rtf"];
+    const QUOTE_CHARS: &[char] = &['"', '\u{201C}', '\u{201D}', '\u{300C}', '\u{300E}'];
+    const SPEECH_CHARS: &str = "说道问答叫喊笑哭";
+    const NARRATIVE_CHARS: &str = "然而但是因此所以接着随后于是不过";
+    const SPEECH_WORDS: &[&str] = &["said", "asked", "replied", "exclaimed"];
+
+    let mut sig = ModeSignals::default();
+    for sample in samples {
+        if STRUCTURED_EXTS.contains(&sample.ext.as_str()) { sig.structured += 2; }
+        if PROSE_EXTS.contains(&sample.ext.as_str()) { sig.prose += 2; }
+
+        let text = sample.snippet.as_str();
+        if text.is_empty() { continue; }
+        let char_count = text.chars().count();
+        sig.snippet_chars += char_count;
+
+        let lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+        let avg_line_len = if lines.is_empty() { 0 } else { char_count / lines.len() };
+        // Long paragraphs → prose; many short lines → structured/list
+        if avg_line_len > 80 { sig.prose += 2; }
+        if avg_line_len < 30 && lines.len() > 5 { sig.structured += 1; }
+
+        let trimmed = text.trim();
+        if trimmed.starts_with(['[', '{']) && trimmed.ends_with([']', '}']) { sig.structured += 3; }
+        let chars: Vec<char> = text.chars().collect();
+        let key_values = chars
+            .iter()
+            .enumerate()
+            .filter(|(i, c)| {
+                QUOTE_CHARS.contains(c)
+                    && chars[i + 1..].iter().find(|n| !n.is_whitespace()) == Some(&':')
+            })
+            .count();
+        if key_values > 3 { sig.structured += 2; }
+
+        // Dialogue → chat mode
+        if chars.iter().filter(|c| QUOTE_CHARS.contains(c)).count() > 2 { sig.dialogue += 2; }
+        if chars.windows(2).any(|w| SPEECH_CHARS.contains(w[0]) && w[1] == '：') { sig.dialogue += 2; }
+        if text
+            .split(|c: char| !c.is_alphanumeric())
+            .any(|w| SPEECH_WORDS.contains(&w.to_lowercase().as_str()))
+        {
+            sig.dialogue += 2;
+        }
+
+        // Headings → QA extraction
+        let is_md_heading = |l: &str| {
+            let hashes = l.chars().take_while(|c| *c == '#').count();
+            (1..=6).contains(&hashes) && l[hashes..].starts_with(char::is_whitespace)
+        };
+        let is_cn_heading = |l: &str| {
+            let Some(rest) = l.strip_prefix('第') else { return false };
+            let num_len: usize = rest
+                .chars()
+                .take_while(|c| c.is_ascii_digit() || "一二三四五六七八九十".contains(*c))
+                .map(char::len_utf8)
+                .sum();
+            num_len > 0 && rest[num_len..].starts_with(['章', '节', '部', '分'])
+        };
+        if text.lines().any(is_md_heading) { sig.headings += 2; }
+        if text.lines().any(is_cn_heading) { sig.headings += 2; }
+
+        if chars.iter().filter(|c| NARRATIVE_CHARS.contains(**c)).count() > 2 { sig.prose += 1; }
+    }
+    sig
+}
+
+/// Average cleaned segment length in chars, if cleaning has run.
+fn average_segment_chars(project_path: &std::path::Path) -> Option<usize> {
+    let content = std::fs::read_to_string(project_path.join("cleaned").join("segments.jsonl")).ok()?;
+    let lengths: Vec<usize> = content
+        .lines()
+        .filter_map(|l| serde_json::from_str::<serde_json::Value>(l).ok())
+        .map(|v| v["text"].as_str().unwrap_or("").chars().count())
+        .collect();
+    if lengths.is_empty() {
+        return None;
+    }
+    Some(lengths.iter().sum::<usize>() / lengths.len())
+}
+
+/// Suggest a generation mode from raw file samples and the cleaned segment
+/// length distribution. Single source of truth for the DataPrep mode picker.
+#[tauri::command]
+pub fn recommend_generation_mode(project_id: String) -> Result<ModeRecommendation, String> {
+    let samples = sample_raw_files(project_id.clone())?;
+    let mut status: HashMap<String, String> = ["qa", "style", "chat", "instruct"]
+        .iter()
+        .map(|m| (m.to_string(), "available".to_string()))
+        .collect();
+    if samples.is_empty() {
+        return Ok(ModeRecommendation {
+            recommended: "qa".to_string(),
+            status,
+            hint_key: "generate.modeCheckNoFiles".to_string(),
+            rationale: "No raw files yet; every mode is available.".to_string(),
+        });
+    }
+
+    let sig = collect_mode_signals(&samples);
+    let mainly_structured = sig.structured > sig.prose * 2 && sig.prose < 3;
+    let mainly_prose = sig.prose > sig.structured * 2;
+    let has_dialogue = sig.dialogue >= 2;
+    let has_headings = sig.headings >= 2;
+    let avg_segment = average_segment_chars(&ProjectDirManager::new().project_path(&project_id));
+
+    let mut set = |mode: &str, value: &str| {
+        status.insert(mode.to_string(), value.to_string());
+    };
+    // QA: best with headings or structured knowledge
+    set("qa", if mainly_structured && !has_headings { "available" } else { "recommended" });
+    // Style: rich narrative prose; needs segments long enough to carry a voice
+    if mainly_prose && avg_segment.is_none_or(|n| n >= 200) {
+        set("style", "recommended");
+    } else if mainly_structured {
+        set("style", "cautious");
+    }
+    // Chat: dialogue-like content
+    if has_dialogue {
+        set("chat", "recommended");
+    } else if mainly_structured {
+        set("chat", "cautious");
+    }
+    // Instruct: versatile, but needs some substance
+    let thin = sig.snippet_chars < 100 || avg_segment.is_some_and(|n| n < 50);
+    set("instruct", if thin { "available" } else { "recommended" });
+
+    let hint_key = if mainly_structured {
+        "generate.modeCheckStructuredHint"
+    } else if mainly_prose && has_dialogue {
+        "generate.modeCheckDialogueHint"
+    } else if mainly_prose {
+        "generate.modeCheckProseHint"
+    } else {
+        "generate.modeCheckGeneralHint"
+    };
+
+    let recommended = ["qa", "instruct", "style", "chat"]
+        .into_iter()
+        .find(|m| status.get(*m).map(String::as_str) == Some("recommended"))
+        .unwrap_or("qa")
+        .to_string();
+
+    let mut reasons: Vec<String> = Vec::new();
+    if mainly_structured { reasons.push("content is mostly structured data".into()); }
+    if mainly_prose { reasons.push("content is mostly narrative prose".into()); }
+    if has_headings { reasons.push("headings suggest extractable knowledge".into()); }
+    if has_dialogue { reasons.push("dialogue markers found".into()); }
+    if let Some(n) = avg_segment { reasons.push(format!("cleaned segments average {} chars", n)); }
+    if reasons.is_empty() { reasons.push("mixed content with no strong signal".into()); }
+    let rationale = format!("{} fits best: {}.", recommended, reasons.join("; "));
+
+    Ok(ModeRecommendation { recommended, status, hint_key: hint_key.to_string(), rationale })
+}
+
 #[derive(serde::Serialize, Clone)]
 pub struct SegmentPreviewItem {
     pub id: usize,
@@ -714,6 +1484,40 @@ pub struct SegmentPreviewSummary {
     pub short_segments: usize,
     pub long_segments: usize,
     pub primary_strategy: String,
+    pub strategy_counts: HashMap<String, usize>,
+    /// "good", "short-heavy", "long-heavy" or "mixed"
+    pub quality: String,
+    /// What to change when the distribution is off; empty when it looks fine.
+    pub advice: String,
+}
+
+/// Segments under this many chars count as short in the preview summary.
+const SHORT_SEGMENT_CHARS: usize = 160;
+/// Segments over this many chars count as long in the preview summary.
+const LONG_SEGMENT_CHARS: usize = 1800;
+
+/// Judge the segment length distribution from the short/long shares.
+fn segment_quality(total: usize, short: usize, long: usize) -> (&'static str, &'static str) {
+    if total == 0 {
+        return ("good", "");
+    }
+    let short_heavy = short as f64 / total as f64 > 0.3;
+    let long_heavy = long as f64 / total as f64 > 0.2;
+    match (short_heavy, long_heavy) {
+        (true, true) => (
+            "mixed",
+            "Segment lengths vary widely — check the source formatting (e.g. stray line breaks or missing paragraph breaks).",
+        ),
+        (true, false) => (
+            "short-heavy",
+            "Many very short segments — consider a larger chunk size or merging short paragraphs.",
+        ),
+        (false, true) => (
+            "long-heavy",
+            "Many very long segments — consider a smaller chunk size so each fits the generation context.",
+        ),
+        (false, false) => ("good", ""),
+    }
 }
 
 #[derive(serde::Serialize, Clone)]
@@ -733,12 +1537,115 @@ impl SegmentPreviewResponse {
                 short_segments: 0,
                 long_segments: 0,
                 primary_strategy: "paragraph_balanced".to_string(),
+                strategy_counts: HashMap::new(),
+                quality: "good".to_string(),
+                advice: String::new(),
             },
             items: vec![],
         }
     }
 }
 
+#[derive(serde::Serialize, Clone)]
+pub struct CleaningManifestFile {
+    pub name: String,
+    pub size_bytes: u64,
+    pub modified_ts: u64,
+}
+
+#[derive(serde::Serialize, Clone)]
+pub struct CleaningManifest {
+    pub generated_at: Option<u64>,
+    pub total_segments: Option<u64>,
+    pub raw_files: Vec<CleaningManifestFile>,
+    /// Segment size and strategy the clean ran with (absent in older manifests).
+    pub chunk_size: Option<u64>,
+    pub strategy: Option<String>,
+    /// True when raw/ holds exactly the files recorded, unchanged.
+    pub fresh: bool,
+    /// Raw files added, modified, or removed since the last clean.
+    pub changed_files: Vec<String>,
+}
+
+/// (name, size_bytes, modified_ts) for each file directly under `raw_dir`.
+fn raw_file_signatures(raw_dir: &std::path::Path) -> HashMap<String, (u64, u64)> {
+    let mut signatures = HashMap::new();
+    let Ok(entries) = std::fs::read_dir(raw_dir) else {
+        return signatures;
+    };
+    for entry in entries.flatten() {
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        if !meta.is_file() {
+            continue;
+        }
+        let modified_ts = meta
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        signatures.insert(entry.file_name().to_string_lossy().to_string(), (meta.len(), modified_ts));
+    }
+    signatures
+}
+
+/// Parsed `cleaned/segments_manifest.json` plus its freshness against raw/.
+/// Returns None when the project has not been cleaned with a manifest.
+#[tauri::command]
+pub fn get_cleaning_manifest(project_id: String) -> Result<Option<CleaningManifest>, String> {
+    let project_path = ProjectDirManager::new().project_path(&project_id);
+    let manifest_path = project_path.join("cleaned").join("segments_manifest.json");
+    let Ok(content) = std::fs::read_to_string(&manifest_path) else {
+        return Ok(None);
+    };
+    let manifest: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid cleaning manifest: {}", e))?;
+
+    let raw_files: Vec<CleaningManifestFile> = manifest["raw_files"]
+        .as_array()
+        .map(|files| {
+            files
+                .iter()
+                .filter_map(|f| {
+                    let name = f["name"].as_str()?.trim();
+                    if name.is_empty() {
+                        return None;
+                    }
+                    Some(CleaningManifestFile {
+                        name: name.to_string(),
+                        size_bytes: f["size_bytes"].as_u64().unwrap_or(0),
+                        modified_ts: f["modified_ts"].as_u64().unwrap_or(0),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut current = raw_file_signatures(&project_path.join("raw"));
+    let mut changed_files: Vec<String> = Vec::new();
+    for file in &raw_files {
+        match current.remove(&file.name) {
+            Some(sig) if sig == (file.size_bytes, file.modified_ts) => {}
+            _ => changed_files.push(file.name.clone()),
+        }
+    }
+    // Whatever is left in raw/ was added after the clean.
+    changed_files.extend(current.into_keys());
+    changed_files.sort();
+
+    Ok(Some(CleaningManifest {
+        generated_at: manifest["generated_at"].as_u64(),
+        total_segments: manifest["total_segments"].as_u64(),
+        raw_files,
+        chunk_size: manifest["chunk_size"].as_u64(),
+        strategy: manifest["strategy"].as_str().map(String::from),
+        fresh: changed_files.is_empty(),
+        changed_files,
+    }))
+}
+
 /// Read cleaned segments and return a compact visual preview payload.
 #[tauri::command]
 pub fn preview_clean_segments(
@@ -903,10 +1810,10 @@ pub fn preview_clean_segments(
         total_chars += char_count;
         min_chars = min_chars.min(char_count);
         max_chars = max_chars.max(char_count);
-        if char_count < 160 {
+        if char_count < SHORT_SEGMENT_CHARS {
             short_segments += 1;
         }
-        if char_count > 1800 {
+        if char_count > LONG_SEGMENT_CHARS {
             long_segments += 1;
         }
 
@@ -943,10 +1850,11 @@ pub fn preview_clean_segments(
     }
 
     let primary_strategy = strategy_count
-        .into_iter()
-        .max_by_key(|(_, count)| *count)
-        .map(|(key, _)| key)
+        .iter()
+        .max_by_key(|(_, count)| **count)
+        .map(|(key, _)| key.clone())
         .unwrap_or_else(|| "paragraph_balanced".to_string());
+    let (quality, advice) = segment_quality(total_segments, short_segments, long_segments);
 
     Ok(SegmentPreviewResponse {
         summary: SegmentPreviewSummary {
@@ -957,11 +1865,64 @@ pub fn preview_clean_segments(
             short_segments,
             long_segments,
             primary_strategy,
+            strategy_counts: strategy_count,
+            quality: quality.to_string(),
+            advice: advice.to_string(),
         },
         items,
     })
 }
 
+#[derive(serde::Serialize, Clone)]
+pub struct HistogramBin {
+    /// Inclusive lower bound in chars.
+    pub start: usize,
+    /// Exclusive upper bound in chars.
+    pub end: usize,
+    pub count: usize,
+}
+
+/// Char-count histogram of all cleaned segments, split into `buckets`
+/// equal-width ranges between the shortest and longest segment.
+#[tauri::command]
+pub fn get_segment_distribution(project_id: String, buckets: usize) -> Result<Vec<HistogramBin>, String> {
+    let segments_path = ProjectDirManager::new()
+        .project_path(&project_id)
+        .join("cleaned")
+        .join("segments.jsonl");
+    if !segments_path.exists() {
+        return Ok(vec![]);
+    }
+    let content = std::fs::read_to_string(&segments_path)
+        .map_err(|e| format!("Failed to read segments.jsonl: {}", e))?;
+
+    let lengths: Vec<usize> = content
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line.trim()).ok())
+        .filter_map(|obj| obj["text"].as_str().map(|t| t.trim().chars().count()))
+        .filter(|&n| n > 0)
+        .collect();
+    let (Some(&min), Some(&max)) = (lengths.iter().min(), lengths.iter().max()) else {
+        return Ok(vec![]);
+    };
+
+    // Integer ranges can't be split finer than one char per bin.
+    let range = max - min + 1;
+    let buckets = buckets.clamp(1, 100).min(range);
+    let bound = |i: usize| min + (range * i).div_ceil(buckets);
+    let mut bins: Vec<HistogramBin> = (0..buckets)
+        .map(|i| HistogramBin {
+            start: bound(i),
+            end: bound(i + 1),
+            count: 0,
+        })
+        .collect();
+    for n in lengths {
+        bins[(n - min) * buckets / range].count += 1;
+    }
+    Ok(bins)
+}
+
 /// Open the dataset root directory in Finder
 #[tauri::command]
 pub fn open_dataset_folder(project_id: String) -> Result<(), String> {
@@ -970,53 +1931,271 @@ pub fn open_dataset_folder(project_id: String) -> Result<(), String> {
     if !dataset_root.exists() {
         std::fs::create_dir_all(&dataset_root).map_err(|e| e.to_string())?;
     }
-    std::process::Command::new("open")
-        .arg(&dataset_root)
-        .spawn()
-        .map_err(|e| format!("Failed to open folder: {}", e))?;
-    Ok(())
+    crate::fs::open_in_file_manager(&dataset_root)
 }
 
+/// Write the cleaned segments out as a standalone corpus: "txt" joins segment
+/// text with blank lines, "jsonl" keeps `{text, source_file}` per line.
+/// Lands in `dest` if given, else the configured export path (or the project's
+/// export/corpus/). Returns the written file path.
+#[tauri::command]
+pub fn export_cleaned_corpus(
+    project_id: String,
+    format: String,
+    dest: Option<String>,
+) -> Result<String, String> {
+    let format = format.trim().to_lowercase();
+    if format != "txt" && format != "jsonl" {
+        return Err(format!("Unsupported corpus format '{}'. Use \"txt\" or \"jsonl\".", format));
+    }
+
+    let project_path = ProjectDirManager::new().project_path(&project_id);
+    let segments_path = project_path.join("cleaned").join("segments.jsonl");
+    let content = std::fs::read_to_string(&segments_path)
+        .map_err(|_| "No cleaned segments found. Run data cleaning first.".to_string())?;
+
+    let mut out = String::new();
+    let mut written = 0usize;
+    for line in content.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let Ok(obj) = serde_json::from_str::<serde_json::Value>(line) else {
+            continue;
+        };
+        let text = obj.get("text").and_then(|v| v.as_str()).unwrap_or("").trim();
+        if text.is_empty() {
+            continue;
+        }
+        if format == "txt" {
+            if written > 0 {
+                out.push_str("\n\n");
+            }
+            out.push_str(text);
+        } else {
+            let record = serde_json::json!({
+                "text": text,
+                "source_file": obj.get("source_file").and_then(|v| v.as_str()).unwrap_or(""),
+            });
+            out.push_str(&record.to_string());
+            out.push('\n');
+        }
+        written += 1;
+    }
+    if written == 0 {
+        return Err("Cleaned segments are empty; nothing to export.".into());
+    }
+    if format == "txt" {
+        out.push('\n');
+    }
+
+    let out_dir = match dest.filter(|d| !d.trim().is_empty()) {
+        Some(d) => std::path::PathBuf::from(d.trim()),
+        None => match crate::commands::config::load_config().export_path {
+            Some(ep) => std::path::PathBuf::from(ep).join(&project_id).join("corpus"),
+            None => project_path.join("export").join("corpus"),
+        },
+    };
+    std::fs::create_dir_all(&out_dir)
+        .map_err(|e| format!("Failed to create corpus output dir: {}", e))?;
+
+    let file_name = format!(
+        "corpus_{}.{}",
+        chrono::Local::now().format("%Y%m%d_%H%M%S"),
+        format
+    );
+    let out_path = out_dir.join(file_name);
+    std::fs::write(&out_path, out).map_err(|e| format!("Failed to write corpus: {}", e))?;
+    Ok(out_path.to_string_lossy().to_string())
+}
+
+/// Rows per page cap for `get_dataset_preview`.
+const DATASET_PREVIEW_MAX_COUNT: usize = 200;
+
+#[derive(serde::Serialize)]
+pub struct DatasetPreview {
+    pub items: Vec<serde_json::Value>,
+    /// Non-empty lines in the whole split, for pagination
+    pub total: usize,
+    pub offset: usize,
+    pub split: String,
+}
+
+/// Page through a dataset split ("train" default, or "valid") without loading
+/// the whole file: `count` (default 50, max 200) rows starting at `offset`.
 #[tauri::command]
 pub async fn get_dataset_preview(
     project_id: String,
     version: Option<String>,
-) -> Result<Vec<serde_json::Value>, String> {
+    offset: Option<usize>,
+    count: Option<usize>,
+    split: Option<String>,
+) -> Result<DatasetPreview, String> {
+    use std::io::BufRead;
+
+    let split = split.unwrap_or_else(|| "train".to_string());
+    if split != "train" && split != "valid" {
+        return Err(format!("Unknown split '{}'. Use \"train\" or \"valid\".", split));
+    }
+    let file_name = format!("{}.jsonl", split);
+    let offset = offset.unwrap_or(0);
+    let count = count.unwrap_or(50).clamp(1, DATASET_PREVIEW_MAX_COUNT);
+
     let dir_manager = ProjectDirManager::new();
     let dataset_root = dir_manager.project_path(&project_id).join("dataset");
 
-    // Determine train.jsonl path based on version
-    let train_path = match version.as_deref() {
+    // Determine the split's path based on version
+    let split_path = match version.as_deref() {
         Some("legacy") | None => {
             // Try legacy flat path first, then find latest versioned
             let legacy = dataset_root.join("train.jsonl");
             if legacy.exists() {
-                legacy
+                dataset_root.join(&file_name)
             } else {
                 // Find latest versioned dataset
                 find_latest_train_path(&dataset_root)
                     .ok_or_else(|| "No dataset found".to_string())?
+                    .with_file_name(&file_name)
             }
         }
-        Some(v) => dataset_root.join(v).join("train.jsonl"),
+        Some(v) => dataset_root.join(v).join(&file_name),
     };
 
-    if !train_path.exists() {
-        return Ok(vec![]);
+    let mut preview = DatasetPreview { items: Vec::new(), total: 0, offset, split };
+    if !split_path.exists() {
+        return Ok(preview);
+    }
+
+    let file = std::fs::File::open(&split_path)
+        .map_err(|e| format!("Failed to read {}: {}", file_name, e))?;
+    for line in std::io::BufReader::new(file).lines() {
+        let line = line.map_err(|e| format!("Failed to read {}: {}", file_name, e))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let index = preview.total;
+        preview.total += 1;
+        if index >= offset && index < offset + count {
+            if let Ok(val) = serde_json::from_str::<serde_json::Value>(&line) {
+                preview.items.push(val);
+            }
+        }
     }
 
-    let content = std::fs::read_to_string(&train_path)
-        .map_err(|e| format!("Failed to read train.jsonl: {}", e))?;
+    Ok(preview)
+}
+
+#[derive(serde::Serialize)]
+pub struct ShuffleResult {
+    pub seed: u64,
+    pub train_count: usize,
+    pub valid_count: usize,
+}
+
+/// Fisher-Yates shuffle driven by splitmix64, so a recorded seed reproduces
+/// the same order.
+fn seeded_shuffle<T>(items: &mut [T], seed: u64) {
+    let mut state = seed;
+    let mut next = || {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    };
+    for i in (1..items.len()).rev() {
+        let j = (next() % (i as u64 + 1)) as usize;
+        items.swap(i, j);
+    }
+}
+
+/// Shuffle a version's train.jsonl in place. With `split_ratio` (train share,
+/// e.g. 0.9) train and valid are pooled, shuffled and re-split instead.
+/// The seed is recorded under `shuffle_history` in meta.json.
+#[tauri::command]
+pub fn shuffle_dataset_version(
+    project_id: String,
+    version: String,
+    seed: Option<u64>,
+    split_ratio: Option<f64>,
+) -> Result<ShuffleResult, String> {
+    if version.is_empty() || version.contains(['/', '\\']) || version.starts_with('.') {
+        return Err(format!("Invalid dataset version: {}", version));
+    }
+    if let Some(r) = split_ratio {
+        if !(r > 0.0 && r < 1.0) {
+            return Err(format!("split_ratio must be between 0 and 1, got {}", r));
+        }
+    }
+    let version_dir = ProjectDirManager::new()
+        .project_path(&project_id)
+        .join("dataset")
+        .join(&version);
+    let train_path = version_dir.join("train.jsonl");
+    let valid_path = version_dir.join("valid.jsonl");
+    if !train_path.exists() {
+        return Err(format!("train.jsonl not found in dataset version {}", version));
+    }
 
-    let mut items = Vec::new();
-    for (i, line) in content.lines().enumerate() {
-        if i >= 50 { break; }
-        if let Ok(val) = serde_json::from_str::<serde_json::Value>(line) {
-            items.push(val);
+    let read_rows = |path: &std::path::Path| -> Result<Vec<String>, String> {
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        std::fs::read_to_string(path)
+            .map(|c| c.lines().map(str::trim).filter(|l| !l.is_empty()).map(String::from).collect())
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+    };
+    let write_rows = |path: &std::path::Path, rows: &[String]| -> Result<(), String> {
+        let mut out = rows.join("\n");
+        if !out.is_empty() {
+            out.push('\n');
         }
+        // Write then rename so an interrupted write can't truncate the split.
+        let tmp = path.with_extension("jsonl.tmp");
+        std::fs::write(&tmp, out)
+            .and_then(|_| std::fs::rename(&tmp, path))
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    };
+
+    let seed = seed.unwrap_or_else(|| {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0)
+    });
+
+    let mut train = read_rows(&train_path)?;
+    let mut valid = read_rows(&valid_path)?;
+    if let Some(ratio) = split_ratio {
+        let mut pool = train;
+        pool.append(&mut valid);
+        seeded_shuffle(&mut pool, seed);
+        let train_len = ((pool.len() as f64 * ratio).round() as usize).clamp(1, pool.len().max(1));
+        valid = pool.split_off(train_len.min(pool.len()));
+        train = pool;
+        write_rows(&valid_path, &valid)?;
+    } else {
+        seeded_shuffle(&mut train, seed);
     }
+    write_rows(&train_path, &train)?;
 
-    Ok(items)
+    let meta_path = version_dir.join("meta.json");
+    let mut meta: serde_json::Value = std::fs::read_to_string(&meta_path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_else(|| serde_json::json!({}));
+    let entry = serde_json::json!({
+        "shuffled_at": chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+        "seed": seed,
+        "split_ratio": split_ratio,
+        "train_count": train.len(),
+        "valid_count": valid.len(),
+    });
+    match meta["shuffle_history"].as_array_mut() {
+        Some(history) => history.push(entry),
+        None => meta["shuffle_history"] = serde_json::json!([entry]),
+    }
+    std::fs::write(&meta_path, serde_json::to_string_pretty(&meta).unwrap_or_default())
+        .map_err(|e| format!("Failed to update meta.json: {}", e))?;
+
+    Ok(ShuffleResult { seed, train_count: train.len(), valid_count: valid.len() })
 }
 
 fn count_jsonl_lines(path: &std::path::Path) -> usize {
@@ -1026,6 +2205,97 @@ fn count_jsonl_lines(path: &std::path::Path) -> usize {
         .unwrap_or(0)
 }
 
+/// Merge a freshly generated run directory into an existing dataset version.
+/// Rows already present (exact line match) are skipped, raw_files in meta.json
+/// are unioned, and the append is recorded under `append_history`.
+/// Returns the number of (train, valid) rows added.
+fn merge_into_version(
+    new_dir: &std::path::Path,
+    target_dir: &std::path::Path,
+) -> Result<(usize, usize), String> {
+    use std::io::Write;
+
+    let mut added = [0usize; 2];
+    for (idx, split) in ["train.jsonl", "valid.jsonl"].iter().enumerate() {
+        let new_path = new_dir.join(split);
+        if !new_path.exists() {
+            continue;
+        }
+        let target_path = target_dir.join(split);
+        let existing = std::fs::read_to_string(&target_path).unwrap_or_default();
+        let mut seen: HashSet<String> = existing
+            .lines()
+            .map(|l| l.trim().to_string())
+            .filter(|l| !l.is_empty())
+            .collect();
+        let incoming = std::fs::read_to_string(&new_path)
+            .map_err(|e| format!("Failed to read {}: {}", new_path.display(), e))?;
+
+        let mut out = String::new();
+        if !existing.is_empty() && !existing.ends_with('\n') {
+            out.push('\n');
+        }
+        for line in incoming.lines() {
+            let line = line.trim();
+            if line.is_empty() || !seen.insert(line.to_string()) {
+                continue;
+            }
+            out.push_str(line);
+            out.push('\n');
+            added[idx] += 1;
+        }
+        if added[idx] > 0 {
+            let mut file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&target_path)
+                .map_err(|e| format!("Failed to open {}: {}", target_path.display(), e))?;
+            file.write_all(out.as_bytes())
+                .map_err(|e| format!("Failed to write {}: {}", target_path.display(), e))?;
+        }
+    }
+
+    let meta_path = target_dir.join("meta.json");
+    let mut meta: serde_json::Value = std::fs::read_to_string(&meta_path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_else(|| serde_json::json!({}));
+    let new_meta: serde_json::Value = std::fs::read_to_string(new_dir.join("meta.json"))
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+
+    let mut raw_files: Vec<String> = meta["raw_files"]
+        .as_array()
+        .map(|a| a.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+        .unwrap_or_default();
+    if let Some(new_files) = new_meta["raw_files"].as_array() {
+        for f in new_files.iter().filter_map(|v| v.as_str()) {
+            if !raw_files.iter().any(|existing| existing == f) {
+                raw_files.push(f.to_string());
+            }
+        }
+    }
+    meta["raw_files"] = serde_json::json!(raw_files);
+    meta["appended"] = serde_json::Value::Bool(true);
+    let entry = serde_json::json!({
+        "appended_at": chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+        "added_train": added[0],
+        "added_valid": added[1],
+        "mode": new_meta["mode"],
+        "source": new_meta["source"],
+        "model": new_meta["model"],
+    });
+    match meta["append_history"].as_array_mut() {
+        Some(history) => history.push(entry),
+        None => meta["append_history"] = serde_json::json!([entry]),
+    }
+    std::fs::write(&meta_path, serde_json::to_string_pretty(&meta).unwrap_or_default())
+        .map_err(|e| format!("Failed to update meta.json: {}", e))?;
+
+    Ok((added[0], added[1]))
+}
+
 fn script_supports_lang_arg(script_path: &std::path::Path) -> bool {
     std::fs::read_to_string(script_path)
         .map(|s| s.contains("--lang") || s.contains("add_lang_arg"))
@@ -1059,6 +2329,23 @@ fn parse_timestamp_display(ts: &str) -> String {
     }
 }
 
+/// (train_count, valid_count, train_size, valid_size) for a dataset version dir.
+fn version_file_stats(version_dir: &std::path::Path) -> (usize, usize, u64, u64) {
+    let train_path = version_dir.join("train.jsonl");
+    let valid_path = version_dir.join("valid.jsonl");
+    (
+        count_jsonl_lines(&train_path),
+        count_jsonl_lines(&valid_path),
+        std::fs::metadata(&train_path).map(|m| m.len()).unwrap_or(0),
+        std::fs::metadata(&valid_path).map(|m| m.len()).unwrap_or(0),
+    )
+}
+
+/// Parse a version directory name ("20260211_103031", local time) to a DateTime.
+fn parse_version_timestamp(ts: &str) -> Option<chrono::NaiveDateTime> {
+    chrono::NaiveDateTime::parse_from_str(ts.get(..15)?, "%Y%m%d_%H%M%S").ok()
+}
+
 fn find_latest_train_path(dataset_root: &std::path::Path) -> Option<std::path::PathBuf> {
     let mut dirs: Vec<_> = std::fs::read_dir(dataset_root).ok()?
         .filter_map(|e| e.ok())
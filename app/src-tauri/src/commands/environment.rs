@@ -3,9 +3,18 @@ use tauri::Emitter;
 use crate::python::PythonExecutor;
 use crate::fs::ProjectDirManager;
 use crate::commands::config::{resolve_ollama_bin_status_from_config, build_uv_env};
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::Mutex;
+use once_cell::sync::Lazy;
 
 pub const MIN_MLX_LM_VERSION: &str = "0.31.2";
+/// Python range MLX wheels are published for; the venv is created with 3.11.
+pub const MIN_PYTHON_VERSION: &str = "3.9";
+pub const MAX_PYTHON_VERSION: &str = "3.13";
+/// Older Ollama releases fail to load models from current converters
+/// (e.g. Qwen3 / Gemma 3 architectures) even though `ollama create` succeeds.
+pub const MIN_OLLAMA_VERSION: &str = "0.6.0";
 
 #[derive(Clone, Serialize)]
 pub struct EnvironmentStatus {
@@ -14,6 +23,8 @@ pub struct EnvironmentStatus {
     pub mlx_lm_version: Option<String>,
     pub mlx_lm_version_supported: bool,
     pub mlx_lm_min_version: String,
+    pub python_version: Option<String>,
+    pub python_version_supported: bool,
     pub chip: String,
     pub memory_gb: f64,
     pub os_version: String,
@@ -25,8 +36,15 @@ pub struct EnvironmentStatus {
 pub struct OllamaStatus {
     pub installed: bool,
     pub running: bool,
+    /// True when `ollama list` did not answer in time (daemon wedged).
+    pub stalled: bool,
+    /// Parsed from `ollama --version`, e.g. "0.6.2".
+    pub version: Option<String>,
 }
 
+/// Upper bound for the `ollama list` liveness probe.
+const OLLAMA_STATUS_TIMEOUT_SECS: u64 = 5;
+
 #[derive(Clone, Serialize)]
 pub struct OllamaModel {
     pub name: String,
@@ -40,9 +58,28 @@ pub struct OllamaPathInfo {
     pub configured_path: Option<String>,
     pub configured_has_layout: bool,
     pub configured_model_count: usize,
+    /// False when a temp file cannot be created in the configured dir.
+    pub configured_writable: bool,
+}
+
+/// Config vs launchctl vs running daemon view of the Ollama models dir.
+#[derive(Clone, Serialize)]
+pub struct OllamaDirHealth {
+    /// Dir the app expects: the configured path, else the Ollama default.
+    pub expected_path: String,
+    pub configured_path: Option<String>,
+    /// `launchctl getenv OLLAMA_MODELS` (what a freshly started daemon gets)
+    pub launchctl_path: Option<String>,
+    /// Dir the running daemon uses; None when no daemon is running.
+    pub daemon_path: Option<String>,
+    /// "daemon_vs_config", "launchctl_vs_config" and/or "daemon_vs_launchctl"
+    pub mismatches: Vec<String>,
+    /// "fix_ollama_models_path", "reset_ollama_models_path" or None when healthy
+    pub suggested_action: Option<String>,
+    pub message: String,
 }
 
-fn detect_mlx_lm_version(executor: &PythonExecutor) -> Option<String> {
+pub fn detect_mlx_lm_version(executor: &PythonExecutor) -> Option<String> {
     if !executor.is_ready() {
         return None;
     }
@@ -56,6 +93,47 @@ fn detect_mlx_lm_version(executor: &PythonExecutor) -> Option<String> {
         .filter(|ver| !ver.is_empty())
 }
 
+/// Version of the venv's interpreter, from `python --version` ("Python 3.11.9").
+pub fn detect_python_version(executor: &PythonExecutor) -> Option<String> {
+    if !executor.is_ready() {
+        return None;
+    }
+
+    std::process::Command::new(executor.python_bin())
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| {
+            // Python < 3.4 printed the version to stderr.
+            let text = if output.stdout.is_empty() { output.stderr } else { output.stdout };
+            String::from_utf8_lossy(&text).trim().trim_start_matches("Python").trim().to_string()
+        })
+        .filter(|ver| !ver.is_empty())
+}
+
+/// Whether major.minor lies within MIN_PYTHON_VERSION..=MAX_PYTHON_VERSION.
+pub fn is_python_version_supported(version: &str) -> bool {
+    let minor = |v: &str| parse_version_parts(v)[..2].to_vec();
+    let v = minor(version);
+    v >= minor(MIN_PYTHON_VERSION) && v <= minor(MAX_PYTHON_VERSION)
+}
+
+/// Version number in `ollama --version` output ("ollama version is 0.6.2",
+/// possibly after a "client version" warning line).
+pub fn parse_ollama_version(output: &str) -> Option<String> {
+    output
+        .lines()
+        .rev()
+        .flat_map(|line| line.split_whitespace())
+        .find(|token| token.starts_with(|c: char| c.is_ascii_digit()) && token.contains('.'))
+        .map(|token| token.to_string())
+}
+
+pub fn is_ollama_version_supported(version: &str) -> bool {
+    parse_version_parts(version) >= parse_version_parts(MIN_OLLAMA_VERSION)
+}
+
 fn parse_version_parts(version: &str) -> Vec<u32> {
     let mut parts = version
         .split(|c: char| !c.is_ascii_digit())
@@ -109,6 +187,12 @@ pub async fn check_environment() -> Result<EnvironmentStatus, String> {
         .map(is_mlx_lm_version_supported)
         .unwrap_or(false);
 
+    let python_version = detect_python_version(&executor);
+    let python_version_supported = python_version
+        .as_deref()
+        .map(is_python_version_supported)
+        .unwrap_or(false);
+
     let (_, ollama_installed) = resolve_ollama_bin_status_from_config();
 
     Ok(EnvironmentStatus {
@@ -117,6 +201,8 @@ pub async fn check_environment() -> Result<EnvironmentStatus, String> {
         mlx_lm_version,
         mlx_lm_version_supported,
         mlx_lm_min_version: MIN_MLX_LM_VERSION.to_string(),
+        python_version,
+        python_version_supported,
         chip,
         memory_gb,
         os_version,
@@ -156,15 +242,40 @@ pub async fn setup_environment(app: tauri::AppHandle) -> Result<(), String> {
 
     if !venv_result.status.success() {
         let stderr = String::from_utf8_lossy(&venv_result.stderr);
+        let _ = std::fs::remove_dir_all(&venv_dir);
         return Err(format!("uv venv failed: {}", stderr));
     }
 
+    // A venv without a working mlx-lm still passes `is_ready()`, so any
+    // failure past this point removes it rather than leaving a half install.
+    if let Err(e) = install_venv_packages(&app, &uv_path, &executor).await {
+        let _ = std::fs::remove_dir_all(&venv_dir);
+        return Err(format!(
+            "{} The incomplete Python environment was removed; please retry setup.",
+            e
+        ));
+    }
+
+    let _ = app.emit("env:setup-progress", serde_json::json!({
+        "step": "Environment ready!",
+        "percent": 100
+    }));
+
+    Ok(())
+}
+
+/// Install the training packages into a fresh venv and confirm `import mlx_lm`.
+async fn install_venv_packages(
+    app: &tauri::AppHandle,
+    uv_path: &std::path::Path,
+    executor: &PythonExecutor,
+) -> Result<(), String> {
     let _ = app.emit("env:setup-progress", serde_json::json!({
         "step": "Installing mlx-lm...",
         "percent": 30
     }));
 
-    let pip_result = tokio::process::Command::new(&uv_path)
+    let pip_result = tokio::process::Command::new(uv_path)
         .args([
             "pip", "install", "--upgrade", "mlx-lm[train]>=0.31.2", "PyPDF2", "python-docx",
             "--python", &executor.python_bin().to_string_lossy(),
@@ -176,17 +287,120 @@ pub async fn setup_environment(app: tauri::AppHandle) -> Result<(), String> {
 
     if !pip_result.status.success() {
         let stderr = String::from_utf8_lossy(&pip_result.stderr);
-        return Err(format!("mlx-lm install failed: {}", stderr));
+        return Err(format!("mlx-lm install failed: {}", stderr.trim()));
     }
 
     let _ = app.emit("env:setup-progress", serde_json::json!({
-        "step": "Environment ready!",
-        "percent": 100
+        "step": "Verifying mlx-lm...",
+        "percent": 90
     }));
 
+    let verify = tokio::process::Command::new(executor.python_bin())
+        .args(["-c", "import mlx_lm"])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to verify mlx-lm: {}", e))?;
+
+    if !verify.status.success() {
+        let stderr = String::from_utf8_lossy(&verify.stderr);
+        return Err(format!("mlx-lm was installed but cannot be imported: {}", stderr.trim()));
+    }
+
     Ok(())
 }
 
+/// Forced clean reinstall for a broken venv (e.g. `python` present but
+/// `import mlx_lm` fails): delete `.venv`, then run the normal setup.
+/// Returns the reinstalled mlx-lm version.
+#[tauri::command]
+pub async fn repair_venv(app: tauri::AppHandle) -> Result<String, String> {
+    ensure_no_python_jobs("repair the Python environment")?;
+    let venv_dir = PythonExecutor::default().venv_dir();
+    if venv_dir.exists() {
+        let _ = app.emit("env:setup-progress", serde_json::json!({
+            "step": "Removing the existing Python environment...",
+            "percent": 5
+        }));
+        std::fs::remove_dir_all(&venv_dir)
+            .map_err(|e| format!("Failed to remove {}: {}", venv_dir.display(), e))?;
+    }
+
+    setup_environment(app).await?;
+
+    detect_mlx_lm_version(&PythonExecutor::default())
+        .ok_or_else(|| "Environment was recreated but mlx-lm still cannot be imported.".to_string())
+}
+
+/// Refuse to touch the venv while jobs are running on its Python.
+fn ensure_no_python_jobs(action: &str) -> Result<(), String> {
+    let active = crate::commands::processes::active_python_jobs();
+    if active.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Cannot {} while jobs are running ({}). Stop them first.",
+            action,
+            active.join(", ")
+        ))
+    }
+}
+
+/// Lines of uv/pip stderr kept in an `update_mlx_lm` failure message.
+const UPDATE_STDERR_TAIL_LINES: usize = 20;
+
+/// Upgrade mlx-lm in place inside the existing venv.
+/// Returns the installed version after the upgrade.
+#[tauri::command]
+pub async fn update_mlx_lm(app: tauri::AppHandle) -> Result<String, String> {
+    ensure_no_python_jobs("update mlx-lm")?;
+    let executor = PythonExecutor::default();
+    if !executor.is_ready() {
+        return Err("Python environment not ready. Please configure it in Settings.".into());
+    }
+    let uv_path = PythonExecutor::find_uv()
+        .ok_or_else(|| "uv not found. Please install uv first: curl -LsSf https://astral.sh/uv/install.sh | sh".to_string())?;
+    let previous = detect_mlx_lm_version(&executor);
+
+    let _ = app.emit("env:setup-progress", serde_json::json!({
+        "step": "Upgrading mlx-lm...",
+        "percent": 30
+    }));
+
+    let pip_result = tokio::process::Command::new(&uv_path)
+        .args([
+            "pip", "install", "--upgrade", "mlx-lm",
+            "--python", &executor.python_bin().to_string_lossy(),
+        ])
+        .envs(build_uv_env())
+        .output()
+        .await
+        .map_err(|e| format!("Failed to upgrade mlx-lm: {}", e))?;
+
+    if !pip_result.status.success() {
+        let stderr = String::from_utf8_lossy(&pip_result.stderr);
+        let lines: Vec<&str> = stderr.trim().lines().collect();
+        let tail = lines[lines.len().saturating_sub(UPDATE_STDERR_TAIL_LINES)..].join("\n");
+        return Err(format!("mlx-lm upgrade failed: {}", tail));
+    }
+
+    let _ = app.emit("env:setup-progress", serde_json::json!({
+        "step": "Verifying mlx-lm...",
+        "percent": 90
+    }));
+
+    let version = detect_mlx_lm_version(&executor)
+        .ok_or_else(|| "mlx-lm was upgraded but cannot be imported.".to_string())?;
+
+    let _ = app.emit("env:setup-progress", serde_json::json!({
+        "step": format!("mlx-lm {} installed", version),
+        "percent": 100,
+        "mlx_lm_version": version,
+        "previous_version": previous,
+    }));
+
+    Ok(version)
+}
+
 /// Install uv package manager via the official installer script.
 /// Uses `curl -LsSf https://astral.sh/uv/install.sh | sh` which installs to ~/.local/bin/uv.
 #[tauri::command]
@@ -234,22 +448,51 @@ pub async fn check_ollama_status() -> Result<OllamaStatus, String> {
         return Ok(OllamaStatus {
             installed: false,
             running: false,
+            stalled: false,
+            version: None,
         });
     }
 
-    let running = std::process::Command::new(&ollama_bin)
-        .arg("list")
-        .output()
-        .map(|output| output.status.success())
-        .unwrap_or(false);
+    let version = tokio::time::timeout(
+        tokio::time::Duration::from_secs(OLLAMA_STATUS_TIMEOUT_SECS),
+        tokio::process::Command::new(&ollama_bin)
+            .arg("--version")
+            .kill_on_drop(true)
+            .output(),
+    )
+    .await
+    .ok()
+    .and_then(|r| r.ok())
+    .and_then(|o| {
+        let text = format!("{}{}", String::from_utf8_lossy(&o.stdout), String::from_utf8_lossy(&o.stderr));
+        parse_ollama_version(&text)
+    });
+
+    // A wedged daemon can leave `ollama list` hanging forever; bound it so the
+    // Settings screen never freezes waiting on the status refresh.
+    let probe = tokio::time::timeout(
+        tokio::time::Duration::from_secs(OLLAMA_STATUS_TIMEOUT_SECS),
+        tokio::process::Command::new(&ollama_bin)
+            .arg("list")
+            .kill_on_drop(true)
+            .output(),
+    ).await;
+
+    let (running, stalled) = match probe {
+        Ok(Ok(output)) => (output.status.success(), false),
+        Ok(Err(_)) => (false, false),
+        Err(_) => (false, true),
+    };
 
     Ok(OllamaStatus {
         installed: true,
         running,
+        stalled,
+        version,
     })
 }
 
-fn get_chip_name() -> String {
+pub fn get_chip_name() -> String {
     #[cfg(target_os = "macos")]
     {
         std::process::Command::new("sysctl")
@@ -266,7 +509,7 @@ fn get_chip_name() -> String {
     }
 }
 
-fn get_system_memory_gb() -> f64 {
+pub fn get_system_memory_gb() -> f64 {
     #[cfg(target_os = "macos")]
     {
         std::process::Command::new("sysctl")
@@ -284,7 +527,7 @@ fn get_system_memory_gb() -> f64 {
     }
 }
 
-fn get_os_version() -> String {
+pub fn get_os_version() -> String {
     #[cfg(target_os = "macos")]
     {
         std::process::Command::new("sw_vers")
@@ -313,6 +556,7 @@ fn config_ollama_models_dir() -> Option<PathBuf> {
     cfg.model_paths.ollama.map(PathBuf::from)
 }
 
+#[cfg(target_os = "macos")]
 fn launchctl_update_ollama_models(path: Option<&str>) -> Result<(), String> {
     let mut cmd = std::process::Command::new("launchctl");
     if let Some(p) = path {
@@ -333,6 +577,176 @@ fn launchctl_update_ollama_models(path: Option<&str>) -> Result<(), String> {
     Ok(())
 }
 
+#[cfg(target_os = "macos")]
+fn launchctl_ollama_models() -> Option<PathBuf> {
+    let out = std::process::Command::new("launchctl")
+        .args(["getenv", "OLLAMA_MODELS"])
+        .output()
+        .ok()?;
+    let s = String::from_utf8_lossy(&out.stdout).trim().to_string();
+    if s.is_empty() { None } else { Some(PathBuf::from(s)) }
+}
+
+/// Drop-in written for the `ollama` systemd user unit on Linux.
+#[cfg(target_os = "linux")]
+fn systemd_ollama_override_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|d| {
+        d.join("systemd")
+            .join("user")
+            .join("ollama.service.d")
+            .join("override.conf")
+    })
+}
+
+/// Marker for the OLLAMA_MODELS line we manage in ~/.profile.
+#[cfg(target_os = "linux")]
+const PROFILE_MARKER: &str = "# added by M-Courtyard";
+
+#[cfg(target_os = "linux")]
+fn systemctl_user(args: &[&str]) -> Result<(), String> {
+    let out = std::process::Command::new("systemctl")
+        .arg("--user")
+        .args(args)
+        .output()
+        .map_err(|e| format!("systemctl failed: {}", e))?;
+    if !out.status.success() {
+        let stderr = String::from_utf8_lossy(&out.stderr).trim().to_string();
+        let stdout = String::from_utf8_lossy(&out.stdout).trim().to_string();
+        let detail = if !stderr.is_empty() { stderr } else { stdout };
+        return Err(format!("systemctl error: {}", detail));
+    }
+    Ok(())
+}
+
+/// Whether Ollama runs as a systemd user service we can configure and restart.
+#[cfg(target_os = "linux")]
+fn has_ollama_user_unit() -> bool {
+    std::process::Command::new("systemctl")
+        .args(["--user", "cat", "ollama.service"])
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+#[cfg(target_os = "linux")]
+fn systemd_update_ollama_models(path: Option<&str>) -> Result<(), String> {
+    let conf = systemd_ollama_override_path()
+        .ok_or_else(|| "Cannot determine the systemd user config directory".to_string())?;
+    match path {
+        Some(p) => {
+            if let Some(parent) = conf.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+            }
+            let body = format!("[Service]\nEnvironment=\"OLLAMA_MODELS={}\"\n", p);
+            std::fs::write(&conf, body)
+                .map_err(|e| format!("Failed to write {}: {}", conf.display(), e))?;
+        }
+        None => {
+            if conf.exists() {
+                std::fs::remove_file(&conf)
+                    .map_err(|e| format!("Failed to remove {}: {}", conf.display(), e))?;
+            }
+        }
+    }
+    systemctl_user(&["daemon-reload"])
+}
+
+#[cfg(target_os = "linux")]
+fn systemd_ollama_models() -> Option<PathBuf> {
+    let content = std::fs::read_to_string(systemd_ollama_override_path()?).ok()?;
+    content.lines().find_map(|line| {
+        let value = line.trim().strip_prefix("Environment=")?.trim_matches('"');
+        let path = value.strip_prefix("OLLAMA_MODELS=")?;
+        if path.is_empty() { None } else { Some(PathBuf::from(path)) }
+    })
+}
+
+/// Rewrite our OLLAMA_MODELS export in ~/.profile for Ollama started by hand.
+#[cfg(target_os = "linux")]
+fn profile_update_ollama_models(path: Option<&str>) -> Result<(), String> {
+    let profile = dirs::home_dir()
+        .ok_or_else(|| "Cannot determine the home directory".to_string())?
+        .join(".profile");
+    // Write through a symlinked ~/.profile (dotfile repos) instead of replacing it.
+    let profile = profile.canonicalize().unwrap_or(profile);
+    let existing = std::fs::read_to_string(&profile).unwrap_or_default();
+    let mut lines: Vec<String> = existing
+        .lines()
+        .filter(|l| !l.ends_with(PROFILE_MARKER))
+        .map(ToString::to_string)
+        .collect();
+    if let Some(p) = path {
+        lines.push(format!("export OLLAMA_MODELS={} {}", shell_single_quote(p), PROFILE_MARKER));
+    }
+    let mut body = lines.join("\n");
+    body.push('\n');
+    // Temp file + rename, like save_config, so a failed write can't truncate the profile.
+    let tmp_path = profile.with_extension("courtyard.tmp");
+    std::fs::write(&tmp_path, body)
+        .and_then(|_| std::fs::rename(&tmp_path, &profile))
+        .map_err(|e| {
+            let _ = std::fs::remove_file(&tmp_path);
+            format!("Failed to write {}: {}", profile.display(), e)
+        })
+}
+
+/// Quote `value` for a POSIX shell: wrapped in single quotes, with each
+/// embedded `'` written as `'\''`.
+#[cfg(target_os = "linux")]
+fn shell_single_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Persist OLLAMA_MODELS where the Ollama daemon will read it on next start.
+fn update_daemon_ollama_models(path: Option<&str>) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    {
+        launchctl_update_ollama_models(path)
+    }
+    #[cfg(target_os = "linux")]
+    {
+        if has_ollama_user_unit() {
+            systemd_update_ollama_models(path)
+        } else {
+            profile_update_ollama_models(path)
+        }
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    {
+        let _ = path;
+        Err("Setting OLLAMA_MODELS for the Ollama daemon is not supported on this platform".to_string())
+    }
+}
+
+/// OLLAMA_MODELS the daemon will start with (launchctl env / systemd override).
+fn daemon_env_ollama_models() -> Option<PathBuf> {
+    #[cfg(target_os = "macos")]
+    {
+        launchctl_ollama_models()
+    }
+    #[cfg(target_os = "linux")]
+    {
+        systemd_ollama_models()
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    {
+        None
+    }
+}
+
+/// Block until `ollama serve` is running (or `up` is false: gone), up to `timeout`.
+fn wait_for_ollama_daemon(up: bool, timeout: std::time::Duration) {
+    let deadline = std::time::Instant::now() + timeout;
+    while std::time::Instant::now() < deadline {
+        std::thread::sleep(std::time::Duration::from_millis(300));
+        if running_ollama_daemon_pids().is_empty() != up {
+            break;
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
 fn restart_ollama_app() -> Result<(), String> {
     // 1) Graceful quit of the Ollama GUI app.
     let _ = std::process::Command::new("osascript")
@@ -346,13 +760,7 @@ fn restart_ollama_app() -> Result<(), String> {
         .output();
 
     // 3) Wait until all `ollama serve` processes are gone (up to 4 s).
-    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(4);
-    while std::time::Instant::now() < deadline {
-        std::thread::sleep(std::time::Duration::from_millis(300));
-        if running_ollama_daemon_pids().is_empty() {
-            break;
-        }
-    }
+    wait_for_ollama_daemon(false, std::time::Duration::from_secs(4));
 
     // 4) Relaunch Ollama.
     let out = std::process::Command::new("open")
@@ -367,24 +775,40 @@ fn restart_ollama_app() -> Result<(), String> {
     }
 
     // 5) Wait until the new `ollama serve` daemon appears (up to 6 s).
-    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(6);
-    while std::time::Instant::now() < deadline {
-        std::thread::sleep(std::time::Duration::from_millis(500));
-        if !running_ollama_daemon_pids().is_empty() {
-            break;
-        }
-    }
+    wait_for_ollama_daemon(true, std::time::Duration::from_secs(6));
+
+    Ok(())
+}
 
+#[cfg(target_os = "linux")]
+fn restart_ollama_app() -> Result<(), String> {
+    if !has_ollama_user_unit() {
+        return Err(
+            "Ollama is not running as a systemd user service. OLLAMA_MODELS was written to ~/.profile; restart Ollama manually to apply it.".to_string(),
+        );
+    }
+    systemctl_user(&["restart", "ollama"])?;
+    wait_for_ollama_daemon(true, std::time::Duration::from_secs(6));
     Ok(())
 }
 
-/// Apply OLLAMA_MODELS into launchctl env and restart Ollama app.
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn restart_ollama_app() -> Result<(), String> {
+    Err("Restarting Ollama is not supported on this platform; restart it manually.".to_string())
+}
+
+/// Apply OLLAMA_MODELS to the daemon environment and restart Ollama.
+/// macOS uses launchctl; Linux uses a systemd user override (or ~/.profile).
 /// - Some(path): set custom OLLAMA_MODELS
 /// - None: unset OLLAMA_MODELS (daemon falls back to ~/.ollama/models)
 pub fn apply_ollama_models_dir_and_restart(path: Option<&std::path::Path>) -> Result<(), String> {
+    invalidate_ollama_models_dir_cache();
     let value = path.map(|p| p.to_string_lossy().to_string());
-    launchctl_update_ollama_models(value.as_deref())?;
-    restart_ollama_app()
+    update_daemon_ollama_models(value.as_deref())?;
+    let result = restart_ollama_app();
+    // The daemon may have picked up the new path while we were restarting it.
+    invalidate_ollama_models_dir_cache();
+    result
 }
 
 fn ollama_library_dir(base: &std::path::Path) -> PathBuf {
@@ -455,11 +879,18 @@ fn running_ollama_models_dir() -> Option<PathBuf> {
     Some(default_ollama_models_dir())
 }
 
-/// Get OLLAMA_MODELS from the user's shell env (sources .zshrc + .zprofile).
-/// Returns None when not set.
+/// Get OLLAMA_MODELS from the user's shell env (sources .zshrc + .zprofile,
+/// or ~/.profile off macOS). Returns None when not set.
 pub fn get_ollama_models_dir() -> Option<String> {
-    let out = std::process::Command::new("/bin/zsh")
-        .args(["-c", "source ~/.zprofile 2>/dev/null; source ~/.zshrc 2>/dev/null; printf '%s' \"$OLLAMA_MODELS\""])
+    #[cfg(target_os = "macos")]
+    let (shell, script) = (
+        "/bin/zsh",
+        "source ~/.zprofile 2>/dev/null; source ~/.zshrc 2>/dev/null; printf '%s' \"$OLLAMA_MODELS\"",
+    );
+    #[cfg(not(target_os = "macos"))]
+    let (shell, script) = ("/bin/sh", ". ~/.profile 2>/dev/null; printf '%s' \"$OLLAMA_MODELS\"");
+    let out = std::process::Command::new(shell)
+        .args(["-c", script])
         .output()
         .ok()?;
     let s = String::from_utf8_lossy(&out.stdout).trim().to_string();
@@ -473,6 +904,35 @@ pub fn get_ollama_models_dir() -> Option<String> {
 /// 4) app config model_paths.ollama,
 /// 5) default ~/.ollama/models.
 pub fn resolve_ollama_models_dir() -> PathBuf {
+    if let Ok(cache) = OLLAMA_MODELS_DIR_CACHE.lock() {
+        if let Some((path, at)) = cache.as_ref() {
+            if at.elapsed() < OLLAMA_MODELS_DIR_TTL {
+                return path.clone();
+            }
+        }
+    }
+    let path = resolve_ollama_models_dir_uncached();
+    if let Ok(mut cache) = OLLAMA_MODELS_DIR_CACHE.lock() {
+        *cache = Some((path.clone(), std::time::Instant::now()));
+    }
+    path
+}
+
+/// How long a resolved Ollama models dir is reused. Resolving shells out to
+/// pgrep/ps/zsh, and scans and exports call it several times per action.
+const OLLAMA_MODELS_DIR_TTL: std::time::Duration = std::time::Duration::from_secs(5);
+
+static OLLAMA_MODELS_DIR_CACHE: Lazy<Mutex<Option<(PathBuf, std::time::Instant)>>> =
+    Lazy::new(|| Mutex::new(None));
+
+/// Forget the cached models dir after a deliberate path change.
+pub fn invalidate_ollama_models_dir_cache() {
+    if let Ok(mut cache) = OLLAMA_MODELS_DIR_CACHE.lock() {
+        *cache = None;
+    }
+}
+
+fn resolve_ollama_models_dir_uncached() -> PathBuf {
     if let Some(path) = running_ollama_models_dir() {
         return path;
     }
@@ -496,6 +956,7 @@ pub fn get_ollama_path_info() -> Result<OllamaPathInfo, String> {
     } else {
         (false, 0)
     };
+    let configured_writable = configured_path_buf.as_deref().is_none_or(dir_is_writable);
 
     Ok(OllamaPathInfo {
         default_path: default_path.to_string_lossy().to_string(),
@@ -503,9 +964,22 @@ pub fn get_ollama_path_info() -> Result<OllamaPathInfo, String> {
         configured_path: configured_path_buf.map(|p| p.to_string_lossy().to_string()),
         configured_has_layout,
         configured_model_count,
+        configured_writable,
     })
 }
 
+/// Probe writability by creating and removing a temp file in `dir`.
+fn dir_is_writable(dir: &std::path::Path) -> bool {
+    let probe = dir.join(format!(".courtyard_write_test_{}", std::process::id()));
+    match std::fs::write(&probe, b"ok") {
+        Ok(()) => {
+            let _ = std::fs::remove_file(&probe);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
 #[tauri::command]
 pub async fn list_ollama_models() -> Result<Vec<OllamaModel>, String> {
     let (ollama_bin, installed) = resolve_ollama_bin_status_from_config();
@@ -542,6 +1016,124 @@ pub async fn list_ollama_models() -> Result<Vec<OllamaModel>, String> {
     Ok(models)
 }
 
+/// PIDs of running `ollama pull` processes, keyed by model name.
+static OLLAMA_PULLS: Lazy<Mutex<HashMap<String, u32>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Terminate every tracked `ollama pull`. Returns how many were signalled.
+pub fn stop_all_ollama_pulls() -> u32 {
+    let pids: Vec<u32> = OLLAMA_PULLS
+        .lock()
+        .map(|mut map| map.drain().map(|(_, pid)| pid).collect())
+        .unwrap_or_default();
+    for pid in &pids {
+        crate::commands::processes::terminate_pid(*pid);
+    }
+    pids.len() as u32
+}
+
+/// Pull a model into Ollama, streaming `ollama pull` output as
+/// `ollama-pull:progress` events.
+#[tauri::command]
+pub async fn pull_ollama_model(app: tauri::AppHandle, name: String) -> Result<(), String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Model name is required".into());
+    }
+    let (ollama_bin, installed) = resolve_ollama_bin_status_from_config();
+    if !installed {
+        return Err("Ollama is not installed".into());
+    }
+    if OLLAMA_PULLS.lock().map_err(|e| e.to_string())?.contains_key(&name) {
+        return Err(format!("{} is already being pulled", name));
+    }
+
+    let mut child = tokio::process::Command::new(&ollama_bin)
+        .args(["pull", &name])
+        .env("OLLAMA_MODELS", resolve_ollama_models_dir())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run ollama pull: {}", e))?;
+
+    if let Some(pid) = child.id() {
+        OLLAMA_PULLS.lock().map_err(|e| e.to_string())?.insert(name.clone(), pid);
+    }
+
+    tokio::spawn(async move {
+        use tokio::io::AsyncBufReadExt;
+
+        // Progress bars are redrawn with '\r', so split on that as well as '\n'.
+        let forward = |reader: Option<Box<dyn tokio::io::AsyncRead + Unpin + Send>>| {
+            let app = app.clone();
+            let name = name.clone();
+            tokio::spawn(async move {
+                let mut last = String::new();
+                let Some(reader) = reader else { return last };
+                let mut segments = tokio::io::BufReader::new(reader).split(b'\r');
+                while let Ok(Some(segment)) = segments.next_segment().await {
+                    for line in String::from_utf8_lossy(&segment).split('\n') {
+                        let line = line.trim();
+                        if line.is_empty() || line == last {
+                            continue;
+                        }
+                        last = line.to_string();
+                        let _ = app.emit("ollama-pull:progress", serde_json::json!({
+                            "name": name, "line": line
+                        }));
+                    }
+                }
+                last
+            })
+        };
+        let stdout = forward(child.stdout.take().map(|s| Box::new(s) as _));
+        let stderr = forward(child.stderr.take().map(|s| Box::new(s) as _));
+        let status = child.wait().await;
+        let _ = stdout.await;
+        let last_err = stderr.await.unwrap_or_default();
+
+        // A missing entry means cancel_pull_ollama_model already reported it.
+        let tracked = OLLAMA_PULLS
+            .lock()
+            .map(|mut map| map.remove(&name).is_some())
+            .unwrap_or(false);
+        if !tracked {
+            return;
+        }
+        match status {
+            Ok(s) if s.success() => {
+                let _ = app.emit("ollama-pull:complete", serde_json::json!({ "name": name }));
+            }
+            Ok(_) => {
+                let _ = app.emit("ollama-pull:error", serde_json::json!({
+                    "name": name, "message": last_err
+                }));
+            }
+            Err(e) => {
+                let _ = app.emit("ollama-pull:error", serde_json::json!({
+                    "name": name, "message": e.to_string()
+                }));
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Cancel an in-flight `ollama pull`. Partially downloaded blobs are left in
+/// place; Ollama resumes them on the next pull.
+#[tauri::command]
+pub async fn cancel_pull_ollama_model(app: tauri::AppHandle, name: String) -> Result<bool, String> {
+    let pid = OLLAMA_PULLS.lock().map_err(|e| e.to_string())?.remove(name.trim());
+    match pid {
+        Some(pid) => {
+            crate::commands::processes::terminate_pid(pid);
+            let _ = app.emit("ollama-pull:stopped", serde_json::json!({ "name": name.trim() }));
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
 /// Apply the user's configured custom Ollama models path to the running daemon
 /// by setting the launchctl environment variable and restarting the Ollama app.
 /// Returns the path that was applied, or an error string.
@@ -554,6 +1146,72 @@ pub async fn fix_ollama_models_path() -> Result<String, String> {
     Ok(custom_dir.to_string_lossy().to_string())
 }
 
+/// Compare the configured Ollama models dir with the launchctl env (systemd
+/// override on Linux) and the running daemon, so exports landing in the "wrong" dir can be diagnosed.
+#[tauri::command]
+pub fn health_check_ollama_models_dir() -> Result<OllamaDirHealth, String> {
+    let default_path = default_ollama_models_dir();
+    let configured = config_ollama_models_dir();
+    let expected = configured.clone().unwrap_or_else(|| default_path.clone());
+    let launchctl = daemon_env_ollama_models();
+    // A running daemon without OLLAMA_MODELS uses the default dir.
+    let daemon = running_ollama_models_dir();
+
+    let same = |a: &std::path::Path, b: &std::path::Path| {
+        let norm = |p: &std::path::Path| p.canonicalize().unwrap_or_else(|_| p.to_path_buf());
+        norm(a) == norm(b)
+    };
+    // Unset launchctl env means the daemon will start with the default dir.
+    let launchctl_effective = launchctl.clone().unwrap_or_else(|| default_path.clone());
+
+    let mut mismatches = Vec::new();
+    if daemon.as_deref().is_some_and(|d| !same(d, &expected)) {
+        mismatches.push("daemon_vs_config".to_string());
+    }
+    if !same(&launchctl_effective, &expected) {
+        mismatches.push("launchctl_vs_config".to_string());
+    }
+    if daemon.as_deref().is_some_and(|d| !same(d, &launchctl_effective)) {
+        mismatches.push("daemon_vs_launchctl".to_string());
+    }
+
+    let (suggested_action, message) = if mismatches.is_empty() {
+        let message = if daemon.is_some() {
+            "Ollama is using the configured models directory.".to_string()
+        } else {
+            "Ollama is not running; it will use the configured models directory when started.".to_string()
+        };
+        (None, message)
+    } else if configured.is_some() {
+        (
+            Some("fix_ollama_models_path".to_string()),
+            format!(
+                "Ollama is not using the configured directory {}. Apply the path and restart Ollama so exported models show up.",
+                expected.display()
+            ),
+        )
+    } else {
+        (
+            Some("reset_ollama_models_path".to_string()),
+            format!(
+                "Ollama uses a custom OLLAMA_MODELS directory instead of the default {}. Reset it and restart Ollama, or set that directory in Settings.",
+                expected.display()
+            ),
+        )
+    };
+
+    let to_string = |p: PathBuf| p.to_string_lossy().to_string();
+    Ok(OllamaDirHealth {
+        expected_path: to_string(expected),
+        configured_path: configured.map(to_string),
+        launchctl_path: launchctl.map(to_string),
+        daemon_path: daemon.map(to_string),
+        mismatches,
+        suggested_action,
+        message,
+    })
+}
+
 /// Clear OLLAMA_MODELS from launchctl and restart Ollama, so daemon falls back
 /// to the default ~/.ollama/models path.
 #[tauri::command]
@@ -561,3 +1219,19 @@ pub async fn reset_ollama_models_path() -> Result<String, String> {
     apply_ollama_models_dir_and_restart(None)?;
     Ok(default_ollama_models_dir().to_string_lossy().to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn shell_single_quote_round_trips_through_sh() {
+        for value in ["/data/ollama models", "/it's/$HOME/`x`/\"q\"", ""] {
+            let quoted = super::shell_single_quote(value);
+            let out = std::process::Command::new("/bin/sh")
+                .args(["-c", &format!("printf '%s' {}", quoted)])
+                .output()
+                .unwrap();
+            assert_eq!(String::from_utf8_lossy(&out.stdout), value);
+        }
+    }
+}
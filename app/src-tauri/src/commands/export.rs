@@ -1,7 +1,9 @@
 use tauri::Emitter;
 use crate::python::PythonExecutor;
+use crate::commands::native_notification::notify_job_finished;
+use crate::python::parser::{JsonLineParser, ParsedLine};
 use crate::fs::ProjectDirManager;
-use crate::commands::config::{load_config, resolve_ollama_bin_path, resolve_ollama_bin_status_from_config};
+use crate::commands::config::{configured_hf_endpoint, hf_transfer_env, load_config, resolve_ollama_bin_path, resolve_ollama_bin_status_from_config};
 use crate::commands::environment::{
     apply_ollama_models_dir_and_restart,
     default_ollama_models_dir,
@@ -9,6 +11,320 @@ use crate::commands::environment::{
     resolve_ollama_models_dir,
 };
 
+/// Marker `fused_cache.py` writes into a finished fused model dir.
+const FUSED_MARKER: &str = ".courtyard_fused.json";
+
+/// A running export/fuse process.
+struct ExportRun {
+    project_id: String,
+    /// Set when the run was stopped on purpose, so its exit is reported as
+    /// `<prefix>:stopped` rather than an error.
+    cancelled: bool,
+}
+
+/// Running export/fuse processes by PID.
+static EXPORT_PIDS: once_cell::sync::Lazy<std::sync::Mutex<std::collections::HashMap<u32, ExportRun>>> =
+    once_cell::sync::Lazy::new(|| std::sync::Mutex::new(std::collections::HashMap::new()));
+
+fn track_export_pid(pid: Option<u32>, project_id: &str) {
+    if let (Some(pid), Ok(mut map)) = (pid, EXPORT_PIDS.lock()) {
+        map.insert(pid, ExportRun { project_id: project_id.to_string(), cancelled: false });
+    }
+}
+
+/// Forget a finished export process; returns whether it had been cancelled.
+fn untrack_export_pid(pid: Option<u32>) -> bool {
+    match (pid, EXPORT_PIDS.lock()) {
+        (Some(pid), Ok(mut map)) => map.remove(&pid).map(|run| run.cancelled).unwrap_or(false),
+        _ => false,
+    }
+}
+
+/// Mark matching exports cancelled and terminate them. Cleanup of their
+/// partial output happens in `run_python_and_emit` once each process exits.
+fn cancel_exports_where(matches: impl Fn(&ExportRun) -> bool) -> u32 {
+    let pids: Vec<u32> = EXPORT_PIDS
+        .lock()
+        .map(|mut map| {
+            map.iter_mut()
+                .filter(|(_, run)| matches(run))
+                .map(|(pid, run)| {
+                    run.cancelled = true;
+                    *pid
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    for pid in &pids {
+        crate::commands::processes::terminate_pid(*pid);
+    }
+    pids.len() as u32
+}
+
+/// Whether an export or fuse of `project_id` is running.
+pub fn is_export_active(project_id: &str) -> bool {
+    EXPORT_PIDS
+        .lock()
+        .map(|map| map.values().any(|run| run.project_id == project_id))
+        .unwrap_or(false)
+}
+
+/// Whether any export or fuse is running.
+pub fn any_export_active() -> bool {
+    EXPORT_PIDS.lock().map(|map| !map.is_empty()).unwrap_or(false)
+}
+
+/// Terminate every running export/fuse process. Returns how many were stopped.
+pub fn stop_all_exports() -> u32 {
+    cancel_exports_where(|_| true)
+}
+
+/// Cancel the running export(s) of a project. Partially written output is
+/// removed and reported via `<prefix>:stopped` with `freed_bytes`.
+#[tauri::command]
+pub fn cancel_export(project_id: String) -> Result<bool, String> {
+    Ok(cancel_exports_where(|run| run.project_id == project_id) > 0)
+}
+
+/// What an export run may leave half-written, recorded before it starts so a
+/// cancelled or timed-out run removes only what it created.
+struct ExportScratch {
+    /// Directories the run writes into, with the entries they held beforehand.
+    watched: Vec<(std::path::PathBuf, std::collections::HashSet<std::ffi::OsString>)>,
+    /// Fused model dir; incomplete (and removable) while it has no marker.
+    fused_dir: Option<std::path::PathBuf>,
+    /// The user asked to keep the fused model (`--keep-fused`).
+    keep_fused: bool,
+}
+
+impl ExportScratch {
+    fn snapshot(dirs: &[&std::path::Path], fused_dir: Option<std::path::PathBuf>) -> Self {
+        let watched = dirs
+            .iter()
+            .map(|dir| {
+                let existing = std::fs::read_dir(dir)
+                    .map(|rd| rd.flatten().map(|e| e.file_name()).collect())
+                    .unwrap_or_default();
+                (dir.to_path_buf(), existing)
+            })
+            .collect();
+        Self { watched, fused_dir, keep_fused: false }
+    }
+
+    /// After a successful export with `autoclean_after_export` on, delete the
+    /// run's fused model dir; returns the bytes freed.
+    fn autoclean_fused(&self) -> u64 {
+        if self.keep_fused || !crate::commands::config::load_config().autoclean_after_export {
+            return 0;
+        }
+        match self.fused_dir {
+            Some(ref fused) if fused.is_dir() => {
+                let size = crate::commands::storage::dir_size(fused);
+                if std::fs::remove_dir_all(fused).is_ok() { size } else { 0 }
+            }
+            _ => 0,
+        }
+    }
+
+    /// Delete what the run created; returns the bytes freed.
+    fn remove_partial(&self) -> u64 {
+        use crate::commands::storage::dir_size;
+
+        let remove = |path: &std::path::Path| -> u64 {
+            if path.is_dir() {
+                let size = dir_size(path);
+                if std::fs::remove_dir_all(path).is_ok() { size } else { 0 }
+            } else {
+                let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+                if std::fs::remove_file(path).is_ok() { size } else { 0 }
+            }
+        };
+        let mut freed = 0;
+        if let Some(ref fused) = self.fused_dir {
+            if fused.is_dir() && !fused.join(FUSED_MARKER).exists() {
+                freed += remove(fused);
+            }
+        }
+        for (dir, existing) in &self.watched {
+            let Ok(entries) = std::fs::read_dir(dir) else { continue };
+            for entry in entries.flatten() {
+                if !existing.contains(&entry.file_name()) {
+                    freed += remove(&entry.path());
+                }
+            }
+        }
+        freed
+    }
+}
+
+/// The `--fused-dir` value from `fused_cache_args`, if any.
+fn fused_dir_arg(args: &[String]) -> Option<std::path::PathBuf> {
+    args.iter()
+        .position(|a| a == "--fused-dir")
+        .and_then(|i| args.get(i + 1))
+        .map(std::path::PathBuf::from)
+}
+
+/// The export log is cleared at the start of a run once it grows past this.
+const EXPORT_LOG_MAX_BYTES: u64 = 4 * 1024 * 1024;
+
+fn export_log_path(project_id: &str) -> std::path::PathBuf {
+    ProjectDirManager::new()
+        .project_path(project_id)
+        .join("export")
+        .join("export.log")
+}
+
+/// Export script output teed to `export/export.log`, so failures can be
+/// reviewed after the events are gone. Writes are best-effort.
+#[derive(Clone)]
+struct ExportLog(Option<std::sync::Arc<std::sync::Mutex<std::fs::File>>>);
+
+impl ExportLog {
+    fn open(project_id: &str, event_prefix: &str) -> Self {
+        use std::io::Write;
+
+        let path = export_log_path(project_id);
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        let oversized = std::fs::metadata(&path)
+            .map(|m| m.len() > EXPORT_LOG_MAX_BYTES)
+            .unwrap_or(false);
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(!oversized)
+            .write(true)
+            .truncate(oversized)
+            .open(&path)
+            .ok()
+            .map(|mut f| {
+                let _ = writeln!(
+                    f,
+                    "\n=== {} export started {} ===",
+                    event_prefix,
+                    chrono::Local::now().format("%Y-%m-%d %H:%M:%S")
+                );
+                std::sync::Arc::new(std::sync::Mutex::new(f))
+            });
+        Self(file)
+    }
+
+    fn line(&self, stream: &str, line: &str) {
+        use std::io::Write;
+
+        if let Some(Ok(mut f)) = self.0.as_ref().map(|f| f.lock()) {
+            let _ = writeln!(f, "[{}] {}", stream, line);
+        }
+    }
+}
+
+/// Saved export script output for a project; `tail` limits it to the last N lines.
+#[tauri::command]
+pub fn get_export_log(project_id: String, tail: Option<usize>) -> Result<String, String> {
+    let path = export_log_path(&project_id);
+    if !path.exists() {
+        return Ok(String::new());
+    }
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Ok(match tail {
+        Some(n) => {
+            let lines: Vec<&str> = content.lines().collect();
+            lines[lines.len().saturating_sub(n)..].join("\n")
+        }
+        None => content,
+    })
+}
+
+/// Per-project record of finished exports: `[{format, adapter, artifact, exported_at}]`.
+const EXPORT_RECORDS_FILE: &str = "exports.json";
+
+fn export_records_path(project_id: &str) -> std::path::PathBuf {
+    ProjectDirManager::new()
+        .project_path(project_id)
+        .join("export")
+        .join(EXPORT_RECORDS_FILE)
+}
+
+/// Path whose existence shows a finished export is still there, taken from the
+/// script's `complete` event: the .gguf file, the MLX model dir, or the Ollama
+/// manifest.
+fn export_artifact(event_prefix: &str, event: &serde_json::Value) -> Option<String> {
+    match event_prefix {
+        "gguf" => event["gguf_path"].as_str().map(str::to_string),
+        "mlx" => event["output_dir"].as_str().map(str::to_string),
+        _ => {
+            let manifest_dir = event["manifest_dir"].as_str()?;
+            let tag = event["model_name"]
+                .as_str()
+                .and_then(|name| name.rsplit_once(':'))
+                .map_or("latest", |(_, tag)| tag);
+            Some(std::path::Path::new(manifest_dir).join(tag).to_string_lossy().to_string())
+        }
+    }
+}
+
+fn read_export_records(path: &std::path::Path) -> Vec<serde_json::Value> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+/// Whether a record's artifact is still on disk. Records without one can't be
+/// checked and count as stale.
+fn export_record_live(record: &serde_json::Value) -> bool {
+    record["artifact"]
+        .as_str()
+        .is_some_and(|a| std::path::Path::new(a).exists())
+}
+
+/// Note that `adapter_path` was exported as `format` ("ollama", "gguf", "mlx")
+/// to `artifact`.
+fn record_export(project_id: &str, format: &str, adapter_path: &str, artifact: &str) {
+    let Some(adapter) = std::path::Path::new(adapter_path).file_name() else {
+        return;
+    };
+    let path = export_records_path(project_id);
+    let mut records = read_export_records(&path);
+    let adapter = adapter.to_string_lossy();
+    records.retain(|r| export_record_live(r) && !(r["format"] == format && r["adapter"] == *adapter));
+    records.push(serde_json::json!({
+        "format": format,
+        "adapter": adapter,
+        "artifact": artifact,
+        "exported_at": chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+    }));
+    if let Ok(body) = serde_json::to_string_pretty(&records) {
+        let _ = std::fs::write(&path, body);
+    }
+}
+
+/// Adapter name → formats it has been exported to, from `exports.json`.
+/// Records whose artifact was deleted or moved are dropped from the file.
+pub(crate) fn exported_formats(project_id: &str) -> std::collections::HashMap<String, Vec<String>> {
+    let path = export_records_path(project_id);
+    let mut records = read_export_records(&path);
+    let before = records.len();
+    records.retain(export_record_live);
+    if records.len() != before {
+        if let Ok(body) = serde_json::to_string_pretty(&records) {
+            let _ = std::fs::write(&path, body);
+        }
+    }
+    let mut formats: std::collections::HashMap<String, Vec<String>> = std::collections::HashMap::new();
+    for record in &records {
+        if let (Some(adapter), Some(format)) = (record["adapter"].as_str(), record["format"].as_str()) {
+            let entry = formats.entry(adapter.to_string()).or_default();
+            if !entry.iter().any(|f| f == format) {
+                entry.push(format.to_string());
+            }
+        }
+    }
+    formats
+}
+
 // ── Shared helper: read process stdout with timeout, emit events ──────────────
 async fn run_python_and_emit(
     app: tauri::AppHandle,
@@ -16,57 +332,123 @@ async fn run_python_and_emit(
     event_prefix: &str,
     project_id: String,
     timeout_secs: u64,
+    scratch: ExportScratch,
+    adapter_path: String,
 ) {
     use tokio::io::{AsyncBufReadExt, BufReader};
 
+    let child_pid = child.id();
+    track_export_pid(child_pid, &project_id);
+    let log = ExportLog::open(&project_id, event_prefix);
+
     let stderr_handle = if let Some(stderr) = child.stderr.take() {
+        let log_err = log.clone();
         let h = tokio::spawn(async move {
             let mut lines = BufReader::new(stderr).lines();
             let mut out = Vec::new();
-            while let Ok(Some(l)) = lines.next_line().await { out.push(l); }
+            while let Ok(Some(l)) = lines.next_line().await {
+                log_err.line("stderr", &l);
+                out.push(l);
+            }
             out
         });
         Some(h)
     } else { None };
 
-    let (emitted_error, emitted_complete, timed_out) =
+    let (emitted_error, emitted_complete, artifact, timed_out) =
         if let Some(stdout) = child.stdout.take() {
             let mut lines = BufReader::new(stdout).lines();
             let app2 = app.clone();
             let pid2 = project_id.clone();
             let prefix2 = event_prefix.to_string();
+            let log_out = log.clone();
             let read_fut = async move {
                 let mut emitted_error = false;
                 let mut emitted_complete = false;
-                while let Ok(Some(line)) = lines.next_line().await {
-                    if let Ok(mut event) = serde_json::from_str::<serde_json::Value>(&line) {
+                let mut artifact = None;
+                let mut handle = |item: ParsedLine| match item {
+                    ParsedLine::Event(mut event) => {
                         let event_type = event["type"].as_str().unwrap_or("unknown").to_string();
                         if event_type == "error" { emitted_error = true; }
-                        else if event_type == "complete" { emitted_complete = true; }
+                        else if event_type == "complete" {
+                            emitted_complete = true;
+                            artifact = export_artifact(&prefix2, &event);
+                        }
                         if let Some(obj) = event.as_object_mut() {
                             obj.insert("project_id".to_string(), serde_json::Value::String(pid2.clone()));
                         }
                         let _ = app2.emit(&format!("{}:{}", prefix2, event_type), &event);
                     }
+                    ParsedLine::ParseError(line) => {
+                        let _ = app2.emit(&format!("{}:parse_error", prefix2), serde_json::json!({
+                            "line": line, "project_id": pid2
+                        }));
+                    }
+                    ParsedLine::Text(_) => {}
+                };
+                let mut parser = JsonLineParser::new();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    log_out.line("stdout", &line);
+                    parser.push(&line).into_iter().for_each(&mut handle);
                 }
-                (emitted_error, emitted_complete)
+                parser.flush().into_iter().for_each(&mut handle);
+                (emitted_error, emitted_complete, artifact)
             };
             match tokio::time::timeout(tokio::time::Duration::from_secs(timeout_secs), read_fut).await {
-                Ok((e, c)) => (e, c, false),
-                Err(_) => (false, false, true),
+                Ok((e, c, a)) => (e, c, a, false),
+                Err(_) => (false, false, None, true),
             }
-        } else { (false, false, false) };
+        } else { (false, false, None, false) };
 
     if timed_out {
         let _ = child.kill().await;
+        untrack_export_pid(child_pid);
+        let freed_bytes = scratch.remove_partial();
+        log.line("courtyard", &format!("timed out after {} minutes; process killed", timeout_secs / 60));
         let _ = app.emit(&format!("{}:error", event_prefix), serde_json::json!({
-            "message": "Export timed out after 30 minutes and was cancelled.",
-            "project_id": project_id
+            "message": format!("Export timed out after {} minutes and was cancelled.", timeout_secs / 60),
+            "project_id": project_id,
+            "freed_bytes": freed_bytes,
         }));
         return;
     }
 
-    match child.wait().await {
+    let wait_result = child.wait().await;
+    if let Ok(ref status) = wait_result {
+        log.line("courtyard", &format!("process exited: {}", status));
+    }
+    if untrack_export_pid(child_pid) {
+        let freed_bytes = scratch.remove_partial();
+        log.line("courtyard", &format!("cancelled; removed {} bytes of partial output", freed_bytes));
+        let _ = app.emit(&format!("{}:stopped", event_prefix), serde_json::json!({
+            "project_id": project_id,
+            "freed_bytes": freed_bytes,
+        }));
+        return;
+    }
+    if emitted_complete {
+        let (format, outcome) = match event_prefix {
+            "gguf" => ("gguf", "GGUF export completed"),
+            "mlx" => ("mlx", "MLX export completed"),
+            _ => ("ollama", "Ollama export completed"),
+        };
+        if let Some(ref artifact) = artifact {
+            record_export(&project_id, format, &adapter_path, artifact);
+        }
+        notify_job_finished(&app, &project_id, "Export finished", outcome).await;
+    }
+    // Only a clean success: a failed run keeps its fused model for the retry.
+    if emitted_complete && wait_result.as_ref().is_ok_and(|s| s.success()) {
+        let freed_bytes = scratch.autoclean_fused();
+        if freed_bytes > 0 {
+            log.line("courtyard", &format!("autoclean removed {} bytes of fused files", freed_bytes));
+            let _ = app.emit(&format!("{}:autoclean", event_prefix), serde_json::json!({
+                "project_id": project_id,
+                "freed_bytes": freed_bytes,
+            }));
+        }
+    }
+    match wait_result {
         Ok(status) => {
             let silent = !emitted_error && !emitted_complete;
             if (!status.success() || silent) && !emitted_error {
@@ -93,6 +475,119 @@ async fn run_python_and_emit(
     }
 }
 
+/// Cache key for a fused model: hashes the adapter weight files and the base
+/// model id, so a retrained adapter or a different base forces a re-fuse.
+fn fused_cache_key(model: &str, adapter_path: &str) -> Option<String> {
+    use std::hash::Hasher;
+
+    let mut weights: Vec<std::path::PathBuf> = std::fs::read_dir(adapter_path)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| {
+            p.is_file()
+                && p.extension()
+                    .map(|ext| ext == "safetensors" || ext == "npz")
+                    .unwrap_or(false)
+        })
+        .collect();
+    // Checkpoints like 0000100_adapters.safetensors don't affect the final fuse.
+    let final_weights = std::path::Path::new(adapter_path).join("adapters.safetensors");
+    if final_weights.is_file() {
+        weights = vec![final_weights];
+    }
+    if weights.is_empty() {
+        return None;
+    }
+    weights.sort();
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    hasher.write(model.trim().as_bytes());
+    for path in &weights {
+        hasher.write_u64(crate::commands::files::hash_file_contents(path).ok()?);
+    }
+    Some(format!("{:016x}", hasher.finish()))
+}
+
+/// `--fused-dir`/`--cache-key` args pointing the export scripts at the shared
+/// `export/fused/<key>` cache. Empty when the adapter can't be hashed.
+fn fused_cache_args(project_path: &std::path::Path, model: &str, adapter_path: &str) -> Vec<String> {
+    match fused_cache_key(model, adapter_path) {
+        Some(key) => vec![
+            "--fused-dir".to_string(),
+            project_path.join("export").join("fused").join(&key).to_string_lossy().to_string(),
+            "--cache-key".to_string(),
+            key,
+        ],
+        None => Vec::new(),
+    }
+}
+
+/// Extra seconds a GGUF export may run when it builds an importance matrix;
+/// export_gguf.py gives llama-imatrix up to an hour on its own.
+const GGUF_IMATRIX_EXTRA_SECS: u64 = 3600;
+
+/// Script args for importance-matrix quantization of a GGUF export. Without
+/// llama-imatrix the export is quantized plainly; without llama-quantize it
+/// stays f16. Either fallback is reported via `gguf:log`.
+fn gguf_quant_args(
+    app: &tauri::AppHandle,
+    project_id: &str,
+    imatrix_dataset: Option<String>,
+) -> Result<Vec<String>, String> {
+    let Some(dataset) = imatrix_dataset.filter(|d| !d.trim().is_empty()) else {
+        return Ok(Vec::new());
+    };
+    if !std::path::Path::new(&dataset).is_file() {
+        return Err(format!("imatrix dataset not found: {}", dataset));
+    }
+    let log = |message: &str| {
+        let _ = app.emit("gguf:log", serde_json::json!({
+            "message": message, "project_id": project_id
+        }));
+    };
+    let Some(quantize) = PythonExecutor::find_llama_tool("llama-quantize") else {
+        log("llama-quantize not found; exporting unquantized f16 GGUF. Install llama.cpp (e.g. `brew install llama.cpp`) for imatrix quantization.");
+        return Ok(Vec::new());
+    };
+    let mut args = vec!["--quantize-bin".to_string(), quantize.to_string_lossy().to_string()];
+    match PythonExecutor::find_llama_tool("llama-imatrix") {
+        Some(imatrix) => args.extend([
+            "--imatrix-bin".to_string(),
+            imatrix.to_string_lossy().to_string(),
+            "--imatrix-dataset".to_string(),
+            dataset,
+        ]),
+        None => log("llama-imatrix not found; quantizing without an importance matrix."),
+    }
+    Ok(args)
+}
+
+/// Filename stem for a GGUF export: `<model>-<adapter>[-iter<N>]`, with N read
+/// from the adapter's training_result.json.
+fn gguf_output_stem(model: &str, adapter_path: &str) -> String {
+    let clean = |s: &str| -> String {
+        s.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '_' || c == '-' { c } else { '-' })
+            .collect::<String>()
+            .trim_matches('-')
+            .to_string()
+    };
+    let adapter = std::path::Path::new(adapter_path);
+    let model_name = model.trim_end_matches('/').rsplit('/').next().unwrap_or(model);
+    let adapter_name = adapter.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let mut stem = format!("{}-{}", clean(model_name), clean(&adapter_name));
+    let iters = std::fs::read_to_string(adapter.join("training_result.json"))
+        .ok()
+        .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok())
+        .and_then(|v| v["total_iters_completed"].as_u64())
+        .filter(|n| *n > 0);
+    if let Some(n) = iters {
+        stem.push_str(&format!("-iter{}", n));
+    }
+    stem.trim_matches('-').to_string()
+}
+
 /// Resolve target OLLAMA_MODELS for export.
 /// Uses model_paths.ollama (Ollama 模型目录) as the export destination.
 /// export_path is reserved for GGUF-only exports and is NOT used here.
@@ -122,6 +617,136 @@ pub struct VerifyResult {
     pub ok: bool,
     pub preview: String,
     pub error: Option<String>,
+    /// Per-capability results; only filled in when checks were requested.
+    #[serde(default)]
+    pub checks: Vec<VerifyCheck>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct VerifyCheck {
+    pub name: String,
+    pub passed: bool,
+    pub detail: String,
+}
+
+/// Base URL of the local Ollama server, honouring OLLAMA_HOST.
+fn ollama_api_base() -> String {
+    let host = std::env::var("OLLAMA_HOST")
+        .ok()
+        .map(|h| h.trim().trim_end_matches('/').to_string())
+        .filter(|h| !h.is_empty())
+        .unwrap_or_else(|| "127.0.0.1:11434".to_string());
+    let host = host.replace("0.0.0.0", "127.0.0.1");
+    if host.starts_with("http://") || host.starts_with("https://") {
+        host
+    } else {
+        format!("http://{}", host)
+    }
+}
+
+/// One non-streaming /api/chat call; returns the assistant's reply.
+async fn ollama_chat(
+    client: &reqwest::Client,
+    model_ref: &str,
+    messages: serde_json::Value,
+    options: serde_json::Value,
+) -> Result<String, String> {
+    let resp = client
+        .post(format!("{}/api/chat", ollama_api_base()))
+        .json(&serde_json::json!({
+            "model": model_ref,
+            "messages": messages,
+            "stream": false,
+            "options": options,
+        }))
+        .send()
+        .await
+        .map_err(|e| format!("Ollama request failed: {}", e))?;
+    let status = resp.status();
+    let body: serde_json::Value = resp
+        .json()
+        .await
+        .map_err(|e| format!("Invalid Ollama response: {}", e))?;
+    if !status.is_success() {
+        return Err(body["error"].as_str().unwrap_or("Ollama returned an error").to_string());
+    }
+    Ok(body["message"]["content"].as_str().unwrap_or("").trim().to_string())
+}
+
+/// Run the capability battery against an exported model: a plain prompt, a
+/// stop sequence and a multi-turn exchange.
+async fn run_verify_checks(model_ref: &str) -> Vec<VerifyCheck> {
+    let client = match reqwest::Client::builder()
+        .no_proxy()
+        .timeout(std::time::Duration::from_secs(60))
+        .build()
+    {
+        Ok(c) => c,
+        Err(e) => {
+            return vec![VerifyCheck {
+                name: "responds".into(),
+                passed: false,
+                detail: format!("Failed to build HTTP client: {}", e),
+            }]
+        }
+    };
+    let snippet = |s: &str| -> String { s.chars().take(120).collect() };
+    let mut checks = Vec::new();
+
+    let responds = ollama_chat(
+        &client,
+        model_ref,
+        serde_json::json!([{ "role": "user", "content": "Reply with exactly one word: OK" }]),
+        serde_json::json!({ "num_predict": 16, "temperature": 0 }),
+    )
+    .await;
+    checks.push(match responds {
+        Ok(reply) if !reply.is_empty() => VerifyCheck { name: "responds".into(), passed: true, detail: snippet(&reply) },
+        Ok(_) => VerifyCheck { name: "responds".into(), passed: false, detail: "Empty response".into() },
+        Err(e) => VerifyCheck { name: "responds".into(), passed: false, detail: e },
+    });
+
+    // Generation must halt at the stop sequence: nothing from "6" on may appear.
+    let stop = ollama_chat(
+        &client,
+        model_ref,
+        serde_json::json!([{ "role": "user", "content": "Count from 1 to 10, separated by spaces. Output only the numbers." }]),
+        serde_json::json!({ "num_predict": 48, "temperature": 0, "stop": ["6"] }),
+    )
+    .await;
+    checks.push(match stop {
+        Ok(reply) => {
+            let leaked = ["6", "7", "8", "9", "10"].iter().any(|n| reply.contains(n));
+            VerifyCheck {
+                name: "stop_token".into(),
+                passed: !reply.is_empty() && !leaked,
+                detail: if reply.is_empty() { "Empty response".into() } else { snippet(&reply) },
+            }
+        }
+        Err(e) => VerifyCheck { name: "stop_token".into(), passed: false, detail: e },
+    });
+
+    let multi_turn = ollama_chat(
+        &client,
+        model_ref,
+        serde_json::json!([
+            { "role": "user", "content": "My name is Ada. Please remember it." },
+            { "role": "assistant", "content": "Got it, your name is Ada." },
+            { "role": "user", "content": "What is my name? Answer with just the name." },
+        ]),
+        serde_json::json!({ "num_predict": 16, "temperature": 0 }),
+    )
+    .await;
+    checks.push(match multi_turn {
+        Ok(reply) => VerifyCheck {
+            name: "multi_turn".into(),
+            passed: reply.to_lowercase().contains("ada"),
+            detail: if reply.is_empty() { "Empty response".into() } else { snippet(&reply) },
+        },
+        Err(e) => VerifyCheck { name: "multi_turn".into(), passed: false, detail: e },
+    });
+
+    checks
 }
 
 fn ollama_server_log_tail(max_lines: usize) -> Option<String> {
@@ -166,14 +791,50 @@ This usually means stale or duplicate safetensors shards were imported. Please r
     Some(format!("Ollama server diagnostics: {}", lines.join(" | ")))
 }
 
+/// Full Ollama model reference `<name>:<tag>`. An explicit `tag` replaces any
+/// tag already in `model_name`; with neither, the tag is "latest".
+fn ollama_model_ref(model_name: &str, tag: Option<&str>) -> Result<String, String> {
+    let (name, existing_tag) = match model_name.trim().rsplit_once(':') {
+        Some((name, t)) => (name, Some(t)),
+        None => (model_name.trim(), None),
+    };
+    if name.is_empty() {
+        return Err("Model name is required".into());
+    }
+    let tag = tag
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .or(existing_tag)
+        .unwrap_or("latest");
+    // Ollama tags: word characters, '.' and '-', not leading with '.' or '-', max 128.
+    let valid = tag.len() <= 128
+        && !tag.starts_with(['.', '-'])
+        && tag.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'));
+    if !valid {
+        return Err(format!(
+            "Invalid tag \"{}\": use letters, digits, '_', '.' or '-' (max 128, not starting with '.' or '-').",
+            tag
+        ));
+    }
+    Ok(format!("{}:{}", name, tag))
+}
+
+/// With `run_checks`, the single smoke prompt is replaced by a battery of
+/// capability checks reported in `checks`.
 #[tauri::command]
-pub async fn verify_export_model(model_name: String) -> Result<VerifyResult, String> {
+pub async fn verify_export_model(
+    model_name: String,
+    tag: Option<String>,
+    run_checks: Option<bool>,
+) -> Result<VerifyResult, String> {
+    let model_name = ollama_model_ref(&model_name, tag.as_deref())?;
     let (ollama_bin, installed) = resolve_ollama_bin_status_from_config();
     if !installed {
         return Ok(VerifyResult {
             ok: false,
             preview: String::new(),
             error: Some("Ollama binary not found. Please set Ollama Binary Path in Settings.".into()),
+            checks: Vec::new(),
         });
     }
     let ollama_models_dir_str = resolve_ollama_models_dir().to_string_lossy().to_string();
@@ -193,20 +854,33 @@ pub async fn verify_export_model(model_name: String) -> Result<VerifyResult, Str
             let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
             let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
             let err = if !stderr.is_empty() { stderr } else if !stdout.is_empty() { stdout } else { "Failed to inspect exported model".into() };
-            return Ok(VerifyResult { ok: false, preview: String::new(), error: Some(err) });
+            return Ok(VerifyResult { ok: false, preview: String::new(), error: Some(err), checks: Vec::new() });
         }
         Ok(Err(e)) => {
-            return Ok(VerifyResult { ok: false, preview: String::new(), error: Some(e.to_string()) });
+            return Ok(VerifyResult { ok: false, preview: String::new(), error: Some(e.to_string()), checks: Vec::new() });
         }
         Err(_) => {
             return Ok(VerifyResult {
                 ok: false,
                 preview: String::new(),
                 error: Some("Verification timed out while checking model metadata (15 s).".into()),
+                checks: Vec::new(),
             });
         }
     }
 
+    if run_checks.unwrap_or(false) {
+        let checks = run_verify_checks(&model_name).await;
+        let failed: Vec<&str> = checks.iter().filter(|c| !c.passed).map(|c| c.name.as_str()).collect();
+        let preview = checks.first().filter(|c| c.passed).map(|c| c.detail.clone()).unwrap_or_default();
+        let error = if failed.is_empty() {
+            None
+        } else {
+            Some(format!("{} of {} checks failed: {}", failed.len(), checks.len(), failed.join(", ")))
+        };
+        return Ok(VerifyResult { ok: failed.is_empty(), preview, error, checks });
+    }
+
     // 2) Runtime smoke tests with multiple prompts.
     let prompts = [
         "Reply with exactly one word: OK",
@@ -233,7 +907,7 @@ pub async fn verify_export_model(model_name: String) -> Result<VerifyResult, Str
                     } else {
                         stdout.chars().take(120).collect()
                     };
-                    return Ok(VerifyResult { ok: true, preview, error: None });
+                    return Ok(VerifyResult { ok: true, preview, error: None, checks: Vec::new() });
                 }
                 last_error = if !stderr.is_empty() { stderr } else if !stdout.is_empty() { stdout } else { "Model returned no output".into() };
                 if last_error.to_lowercase().contains("unable to load model") {
@@ -261,9 +935,97 @@ pub async fn verify_export_model(model_name: String) -> Result<VerifyResult, Str
         } else {
             last_error
         }),
+        checks: Vec::new(),
+    })
+}
+
+/// Smoke-test an exported .gguf by loading it with llama.cpp and generating a
+/// few tokens. Without llama-cli the result says it could not verify rather
+/// than reporting the file as broken.
+#[tauri::command]
+pub async fn verify_gguf_model(gguf_path: String) -> Result<VerifyResult, String> {
+    let path = std::path::Path::new(&gguf_path);
+    if !path.is_file() {
+        return Ok(VerifyResult {
+            ok: false,
+            preview: String::new(),
+            error: Some(format!("GGUF file not found: {}", gguf_path)),
+            checks: Vec::new(),
+        });
+    }
+    let Some(llama_cli) = PythonExecutor::find_llama_cli() else {
+        return Ok(VerifyResult {
+            ok: false,
+            preview: String::new(),
+            error: Some("Cannot verify: llama.cpp not found. Install it (e.g. `brew install llama.cpp`) to test GGUF files.".into()),
+            checks: Vec::new(),
+        });
+    };
+
+    let result = tokio::time::timeout(
+        tokio::time::Duration::from_secs(90),
+        tokio::process::Command::new(&llama_cli)
+            .args([
+                "-m", &gguf_path,
+                "-p", "Say OK",
+                "-n", "8",
+                "--no-display-prompt",
+                "-no-cnv",
+            ])
+            .stdin(std::process::Stdio::null())
+            .kill_on_drop(true)
+            .output(),
+    ).await;
+
+    Ok(match result {
+        Ok(Ok(output)) if output.status.success() => {
+            let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            VerifyResult {
+                ok: true,
+                preview: if stdout.is_empty() {
+                    "(model loaded; empty response)".to_string()
+                } else {
+                    stdout.chars().take(120).collect()
+                },
+                error: None,
+                checks: Vec::new(),
+            }
+        }
+        Ok(Ok(output)) => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let tail: Vec<&str> = stderr.lines().rev().take(6)
+                .collect::<Vec<_>>().into_iter().rev().collect();
+            VerifyResult {
+                ok: false,
+                preview: String::new(),
+                error: Some(if tail.is_empty() {
+                    "llama.cpp failed to load the GGUF file".to_string()
+                } else {
+                    tail.join("\n")
+                }),
+                checks: Vec::new(),
+            }
+        }
+        Ok(Err(e)) => VerifyResult { ok: false, preview: String::new(), error: Some(e.to_string()), checks: Vec::new() },
+        Err(_) => VerifyResult {
+            ok: false,
+            preview: String::new(),
+            error: Some("Verification timed out (90 s) while loading the GGUF file.".into()),
+            checks: Vec::new(),
+        },
     })
 }
 
+/// Optional `export_to_ollama` settings.
+#[derive(Debug, Clone, serde::Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct OllamaExportOptions {
+    /// Modelfile TEMPLATE; defaults to one picked from the model family.
+    pub template: Option<String>,
+    /// Ollama tag, so `name:v2` can sit next to `name:v1`.
+    pub tag: Option<String>,
+}
+
 #[tauri::command]
 pub async fn export_to_ollama(
     app: tauri::AppHandle,
@@ -273,8 +1035,12 @@ pub async fn export_to_ollama(
     adapter_path: Option<String>,
     quantization: Option<String>,
     keep_fused: Option<bool>,
+    options: Option<OllamaExportOptions>,
     lang: Option<String>,
 ) -> Result<(), String> {
+    let OllamaExportOptions { template, tag } = options.unwrap_or_default();
+    // Tagged so a re-export as `name:v2` leaves `name:v1` in place.
+    let model_name = ollama_model_ref(&model_name, tag.as_deref())?;
     let executor = PythonExecutor::default();
     if !executor.is_ready() {
         return Err("Python environment is not ready.".into());
@@ -290,25 +1056,7 @@ pub async fn export_to_ollama(
     let dir_manager = ProjectDirManager::new();
     let project_path = dir_manager.project_path(&project_id);
 
-    // Use provided adapter path or find latest
-    let adapter_path = if let Some(ap) = adapter_path {
-        if !std::path::Path::new(&ap).exists() {
-            return Err(format!("Adapter path not found: {}", ap));
-        }
-        ap
-    } else {
-        let adapters_dir = project_path.join("adapters");
-        std::fs::read_dir(&adapters_dir)
-            .ok()
-            .and_then(|entries| {
-                entries
-                    .filter_map(|e| e.ok())
-                    .filter(|e| e.file_type().map(|t| t.is_dir()).unwrap_or(false))
-                    .max_by_key(|e| e.metadata().ok().and_then(|m| m.modified().ok()))
-                    .map(|e| e.path().to_string_lossy().to_string())
-            })
-            .ok_or_else(|| "No trained adapter found. Complete training first.".to_string())?
-    };
+    let adapter_path = resolve_export_adapter(&project_path, adapter_path)?;
 
     // Intermediate fused files always go into the project's own export/ollama/ dir.
     // We deliberately do NOT use the user-configured export_path here — that path is
@@ -349,8 +1097,35 @@ pub async fn export_to_ollama(
 
     let ollama_models_dir_str = ollama_models_dir.to_string_lossy().to_string();
     let keep_fused_flag = keep_fused.unwrap_or(false);
+    let hf_endpoint = configured_hf_endpoint();
+    let cache_args = fused_cache_args(&project_path, &model, &adapter_path);
+
+    // A mismatched chat template is the usual cause of garbled Ollama output,
+    // so fall back to one matching the base model's family.
+    let mut template_args: Vec<String> = Vec::new();
+    match template.filter(|t| !t.trim().is_empty()) {
+        Some(custom) => {
+            template_args.extend(["--template".to_string(), custom]);
+            let _ = app.emit("export:log", serde_json::json!({
+                "message": "Using the custom chat template provided.",
+                "project_id": project_id
+            }));
+        }
+        None => {
+            let chosen = crate::commands::chat_templates::template_for_model(&model);
+            template_args.extend(["--template".to_string(), chosen.template.to_string()]);
+            for stop in chosen.stop {
+                template_args.extend(["--template-stop".to_string(), stop.to_string()]);
+            }
+            let _ = app.emit("export:log", serde_json::json!({
+                "message": format!("Using default {} chat template for {}.", chosen.family, model),
+                "project_id": project_id
+            }));
+        }
+    }
 
     let pid = project_id.clone();
+    let exported_adapter = adapter_path.clone();
     tokio::spawn(async move {
         let mut cmd = tokio::process::Command::new(&python_bin);
         let mut args_vec = vec![
@@ -368,14 +1143,21 @@ pub async fn export_to_ollama(
         if keep_fused_flag {
             args_vec.push("--keep-fused".to_string());
         }
+        let fused_dir = fused_dir_arg(&cache_args).unwrap_or_else(|| output_dir.join("fused"));
+        let mut scratch = ExportScratch::snapshot(&[&output_dir], Some(fused_dir));
+        scratch.keep_fused = keep_fused_flag;
+        args_vec.extend(cache_args);
+        args_vec.extend(template_args);
         cmd.args(&args_vec)
             .env("PYTHONUNBUFFERED", "1")
             .env("OLLAMA_MODELS", &ollama_models_dir_str)
+            .envs(hf_endpoint.as_ref().map(|e| ("HF_ENDPOINT", e)))
+            .envs(hf_transfer_env())
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::piped());
         match cmd.spawn()
         {
-            Ok(child) => run_python_and_emit(app, child, "export", pid, 1800).await,
+            Ok(child) => run_python_and_emit(app, child, "export", pid, 1800, scratch, exported_adapter).await,
             Err(e) => {
                 let _ = app.emit("export:error", serde_json::json!({
                     "message": e.to_string(), "project_id": pid
@@ -387,6 +1169,72 @@ pub async fn export_to_ollama(
     Ok(())
 }
 
+/// Use the provided adapter path, or the project's most recently modified adapter.
+fn resolve_export_adapter(project_path: &std::path::Path, adapter_path: Option<String>) -> Result<String, String> {
+    if let Some(ap) = adapter_path {
+        if !std::path::Path::new(&ap).exists() {
+            return Err(format!("Adapter path not found: {}", ap));
+        }
+        return Ok(ap);
+    }
+    let adapters_dir = project_path.join("adapters");
+    std::fs::read_dir(&adapters_dir)
+        .ok()
+        .and_then(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().map(|t| t.is_dir()).unwrap_or(false))
+                .max_by_key(|e| e.metadata().ok().and_then(|m| m.modified().ok()))
+                .map(|e| e.path().to_string_lossy().to_string())
+        })
+        .ok_or_else(|| "No trained adapter found. Complete training first.".to_string())
+}
+
+/// One-click fix for the "duplicate tensor name" diagnosis: wipe the project's
+/// `export/ollama/` dir and the cached fuse of this adapter and base model,
+/// then run `export_to_ollama` again.
+/// A tagged `model_name` (`name:v2`) keeps its tag.
+#[tauri::command]
+pub async fn repair_ollama_export(
+    app: tauri::AppHandle,
+    project_id: String,
+    model_name: String,
+    model: String,
+    adapter_path: Option<String>,
+    quantization: Option<String>,
+    lang: Option<String>,
+) -> Result<(), String> {
+    if is_export_active(&project_id) {
+        return Err("An export is still running for this project; wait for it to finish or cancel it first.".into());
+    }
+    let project_path = ProjectDirManager::new().project_path(&project_id);
+    let adapter_path = resolve_export_adapter(&project_path, adapter_path)?;
+    let export_dir = project_path.join("export");
+
+    let ollama_dir = export_dir.join("ollama");
+    if ollama_dir.exists() {
+        std::fs::remove_dir_all(&ollama_dir)
+            .map_err(|e| format!("Failed to clean Ollama export dir: {}", e))?;
+    }
+    // This fuse would otherwise be reused as-is; other adapters' cached fuses
+    // and fuse_adapter outputs stay intact.
+    if let Some(key) = fused_cache_key(&model, &adapter_path) {
+        let cached = export_dir.join("fused").join(key);
+        if cached.join(FUSED_MARKER).exists() {
+            std::fs::remove_dir_all(&cached)
+                .map_err(|e| format!("Failed to clean cached fuse: {}", e))?;
+        }
+    }
+
+    let _ = app.emit("export:progress", serde_json::json!({
+        "step": "repair",
+        "desc": "Performing a clean re-export: previous intermediate files were removed.",
+        "project_id": project_id
+    }));
+
+    export_to_ollama(app, project_id, model_name, model, Some(adapter_path), quantization, None, None, lang).await
+}
+
 // ── GGUF export ───────────────────────────────────────────────────────────────
 
 #[tauri::command]
@@ -396,6 +1244,7 @@ pub async fn export_to_gguf(
     model: String,
     adapter_path: Option<String>,
     lang: Option<String>,
+    imatrix_dataset: Option<String>,
 ) -> Result<(), String> {
     let executor = PythonExecutor::default();
     if !executor.is_ready() {
@@ -460,6 +1309,16 @@ pub async fn export_to_gguf(
     }
 
     let python_bin = executor.python_bin().clone();
+    let hf_endpoint = configured_hf_endpoint();
+    let cache_args = fused_cache_args(&project_path, &model, &adapter_path);
+    let output_name = gguf_output_stem(&model, &adapter_path);
+    let quant_args = gguf_quant_args(&app, &project_id, imatrix_dataset)?;
+    let timeout_secs = if quant_args.iter().any(|a| a == "--imatrix-bin") {
+        1800 + GGUF_IMATRIX_EXTRA_SECS
+    } else {
+        1800
+    };
+    let scratch = ExportScratch::snapshot(&[&output_dir], fused_dir_arg(&cache_args));
     let pid = project_id.clone();
     tokio::spawn(async move {
         match tokio::process::Command::new(&python_bin)
@@ -469,14 +1328,19 @@ pub async fn export_to_gguf(
                 "--model", &model,
                 "--adapter-path", &adapter_path,
                 "--output-dir", &output_dir.to_string_lossy(),
+                "--output-name", &output_name,
                 "--lang", &lang.unwrap_or_else(|| "en".to_string()),
             ])
+            .args(&cache_args)
+            .args(&quant_args)
             .env("PYTHONUNBUFFERED", "1")
+            .envs(hf_endpoint.as_ref().map(|e| ("HF_ENDPOINT", e)))
+            .envs(hf_transfer_env())
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::piped())
             .spawn()
         {
-            Ok(child) => run_python_and_emit(app, child, "gguf", pid, 1800).await,
+            Ok(child) => run_python_and_emit(app, child, "gguf", pid, timeout_secs, scratch, adapter_path).await,
             Err(e) => {
                 let _ = app.emit("gguf:error", serde_json::json!({
                     "message": e.to_string(), "project_id": pid
@@ -537,6 +1401,8 @@ pub async fn export_to_mlx(
         .map_err(|e| format!("Failed to create MLX export dir: {}", e))?;
 
     let python_bin = executor.python_bin().clone();
+    let hf_endpoint = configured_hf_endpoint();
+    let scratch = ExportScratch::snapshot(&[&output_dir], None);
     let pid = project_id.clone();
     tokio::spawn(async move {
         match tokio::process::Command::new(&python_bin)
@@ -549,11 +1415,13 @@ pub async fn export_to_mlx(
                 "--lang", &lang.unwrap_or_else(|| "en".to_string()),
             ])
             .env("PYTHONUNBUFFERED", "1")
+            .envs(hf_endpoint.as_ref().map(|e| ("HF_ENDPOINT", e)))
+            .envs(hf_transfer_env())
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::piped())
             .spawn()
         {
-            Ok(child) => run_python_and_emit(app, child, "mlx", pid, 1800).await,
+            Ok(child) => run_python_and_emit(app, child, "mlx", pid, 1800, scratch, adapter_path).await,
             Err(e) => {
                 let _ = app.emit("mlx:error", serde_json::json!({
                     "message": e.to_string(), "project_id": pid
@@ -565,6 +1433,422 @@ pub async fn export_to_mlx(
     Ok(())
 }
 
+/// One-click export: run the exporter chosen in `default_export` with its
+/// remembered settings. The base model comes from the adapter's metadata and
+/// the Ollama model name follows the Export page's `<base>-finetuned` default.
+#[tauri::command]
+pub async fn export_with_defaults(
+    app: tauri::AppHandle,
+    project_id: String,
+    adapter_path: String,
+    lang: Option<String>,
+) -> Result<(), String> {
+    let defaults = load_config()
+        .default_export
+        .ok_or_else(|| "No default export settings saved yet.".to_string())?;
+    let model = crate::commands::training::read_adapter_base_model(std::path::Path::new(&adapter_path))
+        .ok_or_else(|| "Cannot determine the adapter's base model.".to_string())?;
+
+    match defaults.format.as_str() {
+        "ollama" => {
+            let base_name: String = model
+                .rsplit('/')
+                .next()
+                .unwrap_or("my-model")
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() || "._-".contains(c) { c } else { '-' })
+                .collect();
+            let model_name = format!("{}-finetuned", base_name);
+            export_to_ollama(
+                app,
+                project_id,
+                model_name,
+                model,
+                Some(adapter_path),
+                defaults.quantization,
+                None,
+                Some(OllamaExportOptions { template: None, tag: defaults.tag }),
+                lang,
+            )
+            .await
+        }
+        "gguf" => export_to_gguf(app, project_id, model, Some(adapter_path), lang, None).await,
+        "mlx" => export_to_mlx(app, project_id, model, Some(adapter_path), lang).await,
+        other => Err(format!("Unknown export format: {}", other)),
+    }
+}
+
+/// Relocate a finished export to `dest`, leaving intermediate fused files.
+/// GGUF files go to `<dest>/<project_id>/gguf/` and the MLX model to
+/// `<dest>/<project_id>/mlx`; for "ollama", `dest` is a new OLLAMA_MODELS
+/// root that receives `model_name`'s manifest and blobs; Ollama is then
+/// switched to it and only after that is the model removed from the old
+/// root. Returns the new location.
+#[tauri::command]
+pub async fn move_export(
+    project_id: String,
+    format: String,
+    dest: String,
+    model_name: Option<String>,
+) -> Result<String, String> {
+    use crate::commands::storage::dir_size;
+
+    let dest = std::path::PathBuf::from(dest.trim());
+    if dest.as_os_str().is_empty() {
+        return Err("Destination is required".into());
+    }
+    let check = crate::commands::config::check_dir_writable(&dest);
+    if !check.ok {
+        return Err(format!(
+            "Destination is not writable: {}",
+            check.message.unwrap_or_default()
+        ));
+    }
+    let ensure_room = |needed: u64| -> Result<(), String> {
+        match crate::commands::report::disk_free_bytes(&dest) {
+            Some(free) if free < needed => Err(format!(
+                "Not enough space at {}: need {} MB, {} MB free",
+                dest.display(),
+                needed / 1_048_576,
+                free / 1_048_576
+            )),
+            _ => Ok(()),
+        }
+    };
+    let project_path = ProjectDirManager::new().project_path(&project_id);
+
+    match format.as_str() {
+        "gguf" => {
+            let mut sources = vec![project_path.join("export").join("gguf")];
+            if let Some(ep) = load_config().export_path {
+                sources.push(std::path::PathBuf::from(ep).join(&project_id).join("gguf"));
+            }
+            let files: Vec<std::path::PathBuf> = sources
+                .iter()
+                .filter_map(|dir| std::fs::read_dir(dir).ok())
+                .flat_map(|rd| rd.flatten().map(|e| e.path()))
+                .filter(|p| p.extension().is_some_and(|e| e == "gguf") && p.is_file())
+                .collect();
+            if files.is_empty() {
+                return Err("No GGUF export found for this project".into());
+            }
+            ensure_room(files.iter().filter_map(|f| std::fs::metadata(f).ok()).map(|m| m.len()).sum())?;
+            let target = dest.join(&project_id).join("gguf");
+            std::fs::create_dir_all(&target)
+                .map_err(|e| format!("Failed to create {}: {}", target.display(), e))?;
+            for file in &files {
+                let name = file.file_name().ok_or("Invalid GGUF file name")?;
+                let to = target.join(name);
+                if to != *file {
+                    crate::fs::move_file(file, &to)?;
+                }
+            }
+            Ok(target.to_string_lossy().to_string())
+        }
+        "mlx" => {
+            let src = project_path.join("export").join("mlx");
+            if !src.join("config.json").exists() {
+                return Err("No MLX export found for this project".into());
+            }
+            ensure_room(dir_size(&src))?;
+            let target = dest.join(&project_id).join("mlx");
+            crate::fs::move_dir(&src, &target)?;
+            Ok(target.to_string_lossy().to_string())
+        }
+        "ollama" => {
+            let name = model_name
+                .filter(|n| !n.trim().is_empty())
+                .ok_or("An Ollama model name is required to move an Ollama export")?;
+            let model_ref = ollama_model_ref(&name, None)?;
+            let (repo, tag) = model_ref.rsplit_once(':').ok_or("Invalid model name")?;
+            let models_dir = resolve_ollama_models_dir();
+            if models_dir == dest {
+                return Ok(dest.to_string_lossy().to_string());
+            }
+            // Un-namespaced models live under library/, like `ollama create` writes them.
+            let manifest_rel = {
+                let base = std::path::Path::new("manifests").join("registry.ollama.ai");
+                if repo.contains('/') { base.join(repo) } else { base.join("library").join(repo) }
+            }
+            .join(tag);
+            let manifest_src = models_dir.join(&manifest_rel);
+            let manifest: serde_json::Value = std::fs::read_to_string(&manifest_src)
+                .ok()
+                .and_then(|s| serde_json::from_str(&s).ok())
+                .ok_or_else(|| format!("Ollama model {} not found in {}", model_ref, models_dir.display()))?;
+
+            let blobs = ollama_manifest_blobs(&manifest);
+            let needed: u64 = blobs
+                .iter()
+                .filter(|b| !dest.join("blobs").join(b).exists())
+                .filter_map(|b| std::fs::metadata(models_dir.join("blobs").join(b)).ok())
+                .map(|m| m.len())
+                .sum();
+            ensure_room(needed)?;
+
+            // Blobs may be shared with other models, so they are copied, not moved.
+            std::fs::create_dir_all(dest.join("blobs"))
+                .map_err(|e| format!("Failed to create blobs dir: {}", e))?;
+            for blob in &blobs {
+                let to = dest.join("blobs").join(blob);
+                if !to.exists() {
+                    std::fs::copy(models_dir.join("blobs").join(blob), &to)
+                        .map_err(|e| format!("Failed to copy blob {}: {}", blob, e))?;
+                }
+            }
+            let manifest_dest = dest.join(&manifest_rel);
+            if let Some(parent) = manifest_dest.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+            }
+            std::fs::copy(&manifest_src, &manifest_dest)
+                .map_err(|e| format!("Failed to copy manifest: {}", e))?;
+            let file_len = |p: std::path::PathBuf| std::fs::metadata(p).map(|m| m.len()).ok();
+            if let Some(blob) = blobs.iter().find(|b| {
+                file_len(dest.join("blobs").join(b)) != file_len(models_dir.join("blobs").join(b))
+            }) {
+                return Err(format!("Copied blob {} does not match the original; the original was kept", blob));
+            }
+
+            // The model is only registered where the daemon looks, so point
+            // Ollama at `dest`; the original stays until that has worked.
+            apply_ollama_models_dir_and_restart(Some(&dest)).map_err(|e| {
+                format!(
+                    "Copied {} to {}, but Ollama could not be switched to it; the original was kept: {}",
+                    model_ref,
+                    dest.display(),
+                    e
+                )
+            })?;
+            crate::commands::config::update_config(|c| {
+                c.model_paths.ollama = Some(dest.to_string_lossy().to_string());
+                Ok(())
+            })?;
+            remove_unserved_ollama_model(&models_dir, &manifest_src, &blobs)
+                .map_err(|e| format!("Moved {} to {}, but removing the original failed: {}", model_ref, dest.display(), e))?;
+            Ok(dest.to_string_lossy().to_string())
+        }
+        other => Err(format!("Unknown export format: {}", other)),
+    }
+}
+
+/// Blob file names (`sha256-…`) an Ollama manifest references.
+fn ollama_manifest_blobs(manifest: &serde_json::Value) -> Vec<String> {
+    std::iter::once(&manifest["config"])
+        .chain(manifest["layers"].as_array().into_iter().flatten())
+        .filter_map(|layer| layer["digest"].as_str())
+        .map(|digest| digest.replace(':', "-"))
+        .collect()
+}
+
+/// Delete `manifest` from a models dir Ollama no longer serves, along with
+/// those of `blobs` no other manifest there references. Blobs are all kept if
+/// any remaining manifest can't be read.
+fn remove_unserved_ollama_model(
+    models_dir: &std::path::Path,
+    manifest: &std::path::Path,
+    blobs: &[String],
+) -> Result<(), String> {
+    std::fs::remove_file(manifest).map_err(|e| format!("Failed to remove manifest: {}", e))?;
+    let mut referenced = std::collections::HashSet::new();
+    let mut dirs = vec![models_dir.join("manifests")];
+    while let Some(dir) = dirs.pop() {
+        for entry in std::fs::read_dir(&dir).into_iter().flatten().flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                dirs.push(path);
+                continue;
+            }
+            let parsed: Option<serde_json::Value> = std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|s| serde_json::from_str(&s).ok());
+            match parsed {
+                Some(other) => referenced.extend(ollama_manifest_blobs(&other)),
+                None => return Ok(()),
+            }
+        }
+    }
+    for blob in blobs.iter().filter(|b| !referenced.contains(*b)) {
+        std::fs::remove_file(models_dir.join("blobs").join(blob))
+            .map_err(|e| format!("Failed to remove blob {}: {}", blob, e))?;
+    }
+    Ok(())
+}
+
+// ── Standalone fuse (adapter + base → reusable MLX model) ───────────────────
+
+/// Run `mlx_lm fuse` once and leave the fused model on disk for inference or
+/// later exports. Defaults to `export/fused/<adapter name>` in the project.
+/// Streams output as `fuse:progress` and returns the fused model path.
+#[tauri::command]
+pub async fn fuse_adapter(
+    app: tauri::AppHandle,
+    project_id: String,
+    model: String,
+    adapter_path: String,
+    dest: Option<String>,
+) -> Result<String, String> {
+    use tokio::io::{AsyncBufReadExt, BufReader};
+
+    let executor = PythonExecutor::default();
+    if !executor.is_ready() {
+        return Err("Python environment is not ready.".into());
+    }
+    ensure_mlx_lm_minimum_version(&executor)?;
+
+    let adapter = std::path::Path::new(&adapter_path);
+    if !adapter.is_dir() {
+        return Err(format!("Adapter path not found: {}", adapter_path));
+    }
+
+    let custom_dest = dest.as_deref().is_some_and(|d| !d.trim().is_empty());
+    let save_path = match dest {
+        Some(d) if custom_dest => std::path::PathBuf::from(d.trim()),
+        _ => {
+            let adapter_name = adapter
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "adapter".to_string());
+            ProjectDirManager::new()
+                .project_path(&project_id)
+                .join("export")
+                .join("fused")
+                .join(adapter_name)
+        }
+    };
+    // A chosen `dest` is only cleared when it holds an earlier fuse; any other
+    // non-empty path is refused rather than deleted.
+    let dest_is_empty = std::fs::read_dir(&save_path)
+        .map(|mut rd| rd.next().is_none())
+        .unwrap_or(false);
+    if save_path.exists() && !dest_is_empty {
+        if custom_dest && !save_path.join(FUSED_MARKER).exists() {
+            return Err(format!(
+                "Destination {} already exists and is not empty; choose an empty or new folder.",
+                save_path.display()
+            ));
+        }
+        std::fs::remove_dir_all(&save_path)
+            .map_err(|e| format!("Failed to clear fused output dir: {}", e))?;
+    }
+    // An existing empty `dest` is kept on cancel; only its new contents go.
+    let keep_dest_dir = save_path.is_dir();
+    if let Some(parent) = save_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create fused output dir: {}", e))?;
+    }
+    let save_path_str = save_path.to_string_lossy().to_string();
+
+    let _ = app.emit("fuse:progress", serde_json::json!({
+        "project_id": project_id,
+        "step": "start",
+        "desc": format!("Fusing {} into {}", adapter_path, model),
+    }));
+
+    let mut child = tokio::process::Command::new(executor.python_bin())
+        .args([
+            "-m", "mlx_lm", "fuse",
+            "--model", &model,
+            "--adapter-path", &adapter_path,
+            "--save-path", &save_path_str,
+        ])
+        .env("PYTHONUNBUFFERED", "1")
+        .envs(configured_hf_endpoint().as_ref().map(|e| ("HF_ENDPOINT", e)))
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Failed to start mlx_lm fuse: {}", e))?;
+    let child_pid = child.id();
+    track_export_pid(child_pid, &project_id);
+
+    let stderr_handle = child.stderr.take().map(|stderr| {
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stderr).lines();
+            let mut out = Vec::new();
+            while let Ok(Some(l)) = lines.next_line().await { out.push(l); }
+            out
+        })
+    });
+    if let Some(stdout) = child.stdout.take() {
+        let mut lines = BufReader::new(stdout).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            let line = line.trim();
+            if line.is_empty() { continue; }
+            let _ = app.emit("fuse:progress", serde_json::json!({
+                "project_id": project_id,
+                "step": "fuse",
+                "desc": line,
+            }));
+        }
+    }
+
+    let wait_result = child.wait().await;
+    if untrack_export_pid(child_pid) {
+        // save_path was empty before the run, so everything in it is partial.
+        let freed_bytes = crate::commands::storage::dir_size(&save_path);
+        if keep_dest_dir {
+            for entry in std::fs::read_dir(&save_path).into_iter().flatten().flatten() {
+                let path = entry.path();
+                let _ = if path.is_dir() {
+                    std::fs::remove_dir_all(&path)
+                } else {
+                    std::fs::remove_file(&path)
+                };
+            }
+        } else {
+            let _ = std::fs::remove_dir_all(&save_path);
+        }
+        let _ = app.emit("fuse:stopped", serde_json::json!({
+            "project_id": project_id,
+            "freed_bytes": freed_bytes,
+        }));
+        return Err("Fuse cancelled".to_string());
+    }
+    let status = wait_result.map_err(|e| e.to_string())?;
+    let stderr_text = match stderr_handle {
+        Some(h) => h.await.unwrap_or_default().join("\n"),
+        None => String::new(),
+    };
+
+    let fail = |msg: String| -> Result<String, String> {
+        let _ = app.emit("fuse:error", serde_json::json!({
+            "project_id": project_id, "message": msg
+        }));
+        Err(msg)
+    };
+    if !status.success() {
+        let tail: Vec<&str> = stderr_text.lines().rev().take(12)
+            .collect::<Vec<_>>().into_iter().rev().collect();
+        return fail(if tail.is_empty() {
+            "mlx_lm fuse exited unexpectedly.".to_string()
+        } else {
+            tail.join("\n")
+        });
+    }
+    if !crate::commands::training::validate_model_path(save_path_str.clone())? {
+        return fail(format!("Fused output at {} is not a loadable model.", save_path_str));
+    }
+    // Marks the dir as a Courtyard fuse, so fusing into it again may replace it.
+    let marker = serde_json::json!({
+        "model": model,
+        "adapter_path": adapter_path,
+        "artifact": ".",
+        "format": "mlx",
+    });
+    let _ = std::fs::write(
+        save_path.join(FUSED_MARKER),
+        serde_json::to_string_pretty(&marker).unwrap_or_default(),
+    );
+
+    let _ = app.emit("fuse:complete", serde_json::json!({
+        "project_id": project_id,
+        "output_dir": save_path_str,
+        "size_mb": crate::commands::storage::dir_size(&save_path) / (1024 * 1024),
+    }));
+    Ok(save_path_str)
+}
+
 // ── E-6: mlx-lm.server management ────────────────────────────────────────────
 
 use std::sync::Mutex;
@@ -715,3 +1999,59 @@ pub async fn get_mlx_server_status(
         None => Ok(MlxServerInfo::default()),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remove_unserved_ollama_model_keeps_shared_blobs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let models = tmp.path();
+        let library = models.join("manifests/registry.ollama.ai/library");
+        std::fs::create_dir_all(library.join("ours")).unwrap();
+        std::fs::create_dir_all(library.join("other")).unwrap();
+        std::fs::create_dir_all(models.join("blobs")).unwrap();
+        let manifest = |digests: &[&str]| {
+            serde_json::json!({
+                "config": { "digest": digests[0] },
+                "layers": digests[1..].iter().map(|d| serde_json::json!({ "digest": d })).collect::<Vec<_>>(),
+            })
+            .to_string()
+        };
+        std::fs::write(library.join("ours/latest"), manifest(&["sha256:a", "sha256:shared"])).unwrap();
+        std::fs::write(library.join("other/latest"), manifest(&["sha256:b", "sha256:shared"])).unwrap();
+        for blob in ["sha256-a", "sha256-b", "sha256-shared"] {
+            std::fs::write(models.join("blobs").join(blob), blob).unwrap();
+        }
+
+        let blobs = vec!["sha256-a".to_string(), "sha256-shared".to_string()];
+        remove_unserved_ollama_model(models, &library.join("ours/latest"), &blobs).unwrap();
+
+        assert!(!library.join("ours/latest").exists());
+        assert!(!models.join("blobs/sha256-a").exists());
+        assert!(models.join("blobs/sha256-shared").exists());
+        assert!(models.join("blobs/sha256-b").exists());
+    }
+
+    #[test]
+    fn export_artifact_points_at_the_tagged_ollama_manifest() {
+        let event = serde_json::json!({ "manifest_dir": "/m/library/demo", "model_name": "demo:v2" });
+        assert_eq!(export_artifact("export", &event).as_deref(), Some("/m/library/demo/v2"));
+        let event = serde_json::json!({ "manifest_dir": "/m/library/demo", "model_name": "demo" });
+        assert_eq!(export_artifact("export", &event).as_deref(), Some("/m/library/demo/latest"));
+        let event = serde_json::json!({ "gguf_path": "/out/demo.gguf" });
+        assert_eq!(export_artifact("gguf", &event).as_deref(), Some("/out/demo.gguf"));
+    }
+
+    #[test]
+    fn export_records_without_a_present_artifact_are_stale() {
+        let tmp = tempfile::tempdir().unwrap();
+        let file = tmp.path().join("demo.gguf");
+        std::fs::write(&file, b"gguf").unwrap();
+        assert!(export_record_live(&serde_json::json!({ "artifact": file.to_string_lossy() })));
+        std::fs::remove_file(&file).unwrap();
+        assert!(!export_record_live(&serde_json::json!({ "artifact": file.to_string_lossy() })));
+        assert!(!export_record_live(&serde_json::json!({ "format": "gguf", "adapter": "a" })));
+    }
+}
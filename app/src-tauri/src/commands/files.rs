@@ -1,9 +1,10 @@
 use serde::Serialize;
+use std::collections::HashMap;
 use std::fs;
 use std::sync::OnceLock;
 use crate::fs::ProjectDirManager;
 use crate::python::PythonExecutor;
-use crate::commands::config::build_uv_env;
+use crate::commands::config::{build_uv_env, configured_http_client};
 
 /// Whether doc-parsing deps (PyPDF2, python-docx) have been checked/installed this session.
 static DOC_DEPS_OK: OnceLock<bool> = OnceLock::new();
@@ -52,6 +53,22 @@ pub struct FileInfo {
     pub size_bytes: u64,
 }
 
+#[derive(Clone, Serialize)]
+pub struct FileNode {
+    pub name: String,
+    pub path: String,
+    /// "dir" or "file"
+    pub kind: String,
+    /// File size, or the total of everything below a directory
+    pub size_bytes: u64,
+    pub children: Vec<FileNode>,
+    /// Set on directories whose contents were cut off by the depth cap
+    pub truncated: bool,
+}
+
+/// Deepest directory level `get_raw_files_tree` descends into.
+const FILE_TREE_MAX_DEPTH: usize = 8;
+
 const SUPPORTED_EXTENSIONS: &[&str] = &["txt", "json", "jsonl", "md", "docx", "pdf"];
 
 fn is_supported_file(path: &std::path::Path) -> bool {
@@ -76,11 +93,64 @@ fn collect_files_recursive(dir: &std::path::Path, out: &mut Vec<std::path::PathB
     }
 }
 
+/// Outcome of importing a single source file into `raw/`.
+#[derive(Clone, Serialize)]
+pub struct ImportFileResult {
+    pub source_path: String,
+    /// "imported", "renamed" (name collision with different content) or
+    /// "skipped" (byte-identical to a file already in raw/).
+    pub status: String,
+    pub name: String,
+    pub path: String,
+    pub size_bytes: u64,
+}
+
+/// Hash a file's contents in fixed-size chunks so large PDFs don't need to be
+/// loaded into memory at once.
+pub fn hash_file_contents(path: &std::path::Path) -> Result<u64, String> {
+    use std::hash::Hasher;
+    use std::io::Read;
+
+    let mut file = fs::File::open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        let n = file
+            .read(&mut buf)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        if n == 0 {
+            break;
+        }
+        hasher.write(&buf[..n]);
+    }
+    Ok(hasher.finish())
+}
+
+/// Pick a free name in `dir` for `file_name`, using the `doc (2).txt` convention.
+fn next_available_name(dir: &std::path::Path, file_name: &str) -> String {
+    let path = std::path::Path::new(file_name);
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+    let ext = path.extension().map(|e| e.to_string_lossy().to_string()).unwrap_or_default();
+    let mut counter = 2u32;
+    loop {
+        let candidate = if ext.is_empty() {
+            format!("{} ({})", stem, counter)
+        } else {
+            format!("{} ({}).{}", stem, counter, ext)
+        };
+        if !dir.join(&candidate).exists() {
+            return candidate;
+        }
+        counter += 1;
+    }
+}
+
 #[tauri::command]
 pub async fn import_files(
     project_id: String,
     source_paths: Vec<String>,
-) -> Result<Vec<FileInfo>, String> {
+) -> Result<Vec<ImportFileResult>, String> {
     let dir_manager = ProjectDirManager::new();
     let raw_dir = dir_manager.project_path(&project_id).join("raw");
     fs::create_dir_all(&raw_dir)
@@ -100,7 +170,23 @@ pub async fn import_files(
         }
     }
 
-    let mut imported = Vec::new();
+    // Index what is already in raw/ by (size, content hash) so re-imports of
+    // unchanged files are skipped instead of piling up copies.
+    let mut known: HashMap<(u64, u64), String> = HashMap::new();
+    if let Ok(entries) = fs::read_dir(&raw_dir) {
+        for entry in entries.flatten() {
+            let p = entry.path();
+            if !p.is_file() {
+                continue;
+            }
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            if let Ok(hash) = hash_file_contents(&p) {
+                known.insert((size, hash), entry.file_name().to_string_lossy().to_string());
+            }
+        }
+    }
+
+    let mut results = Vec::new();
 
     for src in &all_files {
         let file_name = src
@@ -108,36 +194,160 @@ pub async fn import_files(
             .ok_or_else(|| "Invalid file name".to_string())?
             .to_string_lossy()
             .to_string();
-        // Avoid overwriting: append _N if name already exists
-        let mut dest = raw_dir.join(&file_name);
-        if dest.exists() {
-            let stem = src.file_stem().unwrap_or_default().to_string_lossy().to_string();
-            let ext = src.extension().map(|e| e.to_string_lossy().to_string()).unwrap_or_default();
-            let mut counter = 1u32;
-            loop {
-                let new_name = if ext.is_empty() {
-                    format!("{}_{}", stem, counter)
-                } else {
-                    format!("{}_{}.{}", stem, counter, ext)
-                };
-                dest = raw_dir.join(&new_name);
-                if !dest.exists() { break; }
-                counter += 1;
-            }
+        let size = fs::metadata(src)
+            .map_err(|e| format!("Failed to read metadata: {}", e))?
+            .len();
+        let hash = hash_file_contents(src)?;
+
+        if let Some(existing) = known.get(&(size, hash)) {
+            results.push(ImportFileResult {
+                source_path: src.to_string_lossy().to_string(),
+                status: "skipped".to_string(),
+                name: existing.clone(),
+                path: raw_dir.join(existing).to_string_lossy().to_string(),
+                size_bytes: size,
+            });
+            continue;
         }
-        fs::copy(src, &dest).map_err(|e| format!("Failed to copy {}: {}", file_name, e))?;
 
-        let metadata = fs::metadata(&dest)
-            .map_err(|e| format!("Failed to read metadata: {}", e))?;
+        // Same name but different content: keep both, never overwrite.
+        let (dest_name, status) = if raw_dir.join(&file_name).exists() {
+            (next_available_name(&raw_dir, &file_name), "renamed")
+        } else {
+            (file_name.clone(), "imported")
+        };
+        let dest = raw_dir.join(&dest_name);
+        fs::copy(src, &dest).map_err(|e| format!("Failed to copy {}: {}", file_name, e))?;
+        known.insert((size, hash), dest_name.clone());
 
-        imported.push(FileInfo {
-            name: dest.file_name().unwrap_or_default().to_string_lossy().to_string(),
+        results.push(ImportFileResult {
+            source_path: src.to_string_lossy().to_string(),
+            status: status.to_string(),
+            name: dest_name,
             path: dest.to_string_lossy().to_string(),
-            size_bytes: metadata.len(),
+            size_bytes: size,
         });
     }
 
-    Ok(imported)
+    Ok(results)
+}
+
+/// Largest download `import_url` will store.
+const MAX_URL_IMPORT_BYTES: u64 = 200 * 1024 * 1024;
+
+/// Extension to store a download under, judged from its Content-Type.
+/// `None` means the type is not something cleaning can read.
+fn extension_for_content_type(content_type: &str) -> Option<&'static str> {
+    let mime = content_type.split(';').next().unwrap_or("").trim().to_lowercase();
+    match mime.as_str() {
+        "application/json" => Some("json"),
+        "application/jsonl" | "application/x-ndjson" | "application/x-jsonlines" => Some("jsonl"),
+        "text/markdown" | "text/x-markdown" => Some("md"),
+        "application/pdf" => Some("pdf"),
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document" => Some("docx"),
+        // Web pages are markup, not corpus text.
+        "text/html" => None,
+        m if m.starts_with("text/") => Some("txt"),
+        _ => None,
+    }
+}
+
+/// Keep only characters that are safe in a file name under `raw/`.
+fn sanitize_file_name(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || matches!(c, '.' | '-' | '_' | ' ' | '(' | ')') { c } else { '_' })
+        .collect();
+    cleaned.trim().trim_start_matches('.').to_string()
+}
+
+/// Download a text corpus or document from `url` into the project's `raw/`
+/// directory and return the stored file name.
+#[tauri::command]
+pub async fn import_url(
+    project_id: String,
+    url: String,
+    filename: Option<String>,
+) -> Result<String, String> {
+    let parsed = reqwest::Url::parse(url.trim()).map_err(|e| format!("Invalid URL: {}", e))?;
+    if parsed.scheme() != "http" && parsed.scheme() != "https" {
+        return Err("Only http and https URLs can be imported".to_string());
+    }
+
+    let client = configured_http_client()?;
+    let mut resp = client
+        .get(parsed.clone())
+        .send()
+        .await
+        .map_err(|e| format!("Download failed: {}", e))?;
+    if !resp.status().is_success() {
+        return Err(format!("Download failed: HTTP {}", resp.status()));
+    }
+    if resp.content_length().is_some_and(|len| len > MAX_URL_IMPORT_BYTES) {
+        return Err(format!(
+            "File is larger than the {} MB import limit",
+            MAX_URL_IMPORT_BYTES / (1024 * 1024)
+        ));
+    }
+
+    let requested = filename
+        .as_deref()
+        .map(sanitize_file_name)
+        .filter(|n| !n.is_empty());
+    let from_url = parsed
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .map(sanitize_file_name)
+        .filter(|n| !n.is_empty());
+    let mut file_name = requested.or(from_url).unwrap_or_else(|| "download".to_string());
+
+    let content_type = resp
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_string();
+    let name_supported = is_supported_file(std::path::Path::new(&file_name));
+    match extension_for_content_type(&content_type) {
+        Some(ext) if !name_supported => file_name = format!("{}.{}", file_name, ext),
+        Some(_) => {}
+        // Servers often label plain files generically; trust a supported extension then.
+        None if name_supported
+            && (content_type.is_empty() || content_type.starts_with("application/octet-stream")) => {}
+        None => {
+            return Err(format!(
+                "Unsupported content type '{}'. Supported: plain text, Markdown, JSON/JSONL, PDF, DOCX",
+                content_type
+            ))
+        }
+    }
+
+    let mut body: Vec<u8> = Vec::new();
+    while let Some(chunk) = resp
+        .chunk()
+        .await
+        .map_err(|e| format!("Download failed: {}", e))?
+    {
+        body.extend_from_slice(&chunk);
+        if body.len() as u64 > MAX_URL_IMPORT_BYTES {
+            return Err(format!(
+                "File is larger than the {} MB import limit",
+                MAX_URL_IMPORT_BYTES / (1024 * 1024)
+            ));
+        }
+    }
+
+    let dir_manager = ProjectDirManager::new();
+    let raw_dir = dir_manager.project_path(&project_id).join("raw");
+    fs::create_dir_all(&raw_dir)
+        .map_err(|e| format!("Failed to create raw directory: {}", e))?;
+    if raw_dir.join(&file_name).exists() {
+        file_name = next_available_name(&raw_dir, &file_name);
+    }
+    fs::write(raw_dir.join(&file_name), &body)
+        .map_err(|e| format!("Failed to write {}: {}", file_name, e))?;
+
+    Ok(file_name)
 }
 
 #[tauri::command]
@@ -171,6 +381,65 @@ pub async fn list_project_files(
     Ok(files)
 }
 
+fn build_file_node(path: &std::path::Path, depth: usize) -> Option<FileNode> {
+    // symlink_metadata so a link cycle can't send us around in circles
+    let meta = fs::symlink_metadata(path).ok()?;
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if name.starts_with('.') {
+        return None;
+    }
+    if meta.is_file() {
+        return Some(FileNode {
+            name,
+            path: path.to_string_lossy().to_string(),
+            kind: "file".to_string(),
+            size_bytes: meta.len(),
+            children: Vec::new(),
+            truncated: false,
+        });
+    }
+    if !meta.is_dir() {
+        return None;
+    }
+
+    let mut node = FileNode {
+        name,
+        path: path.to_string_lossy().to_string(),
+        kind: "dir".to_string(),
+        size_bytes: 0,
+        children: Vec::new(),
+        truncated: false,
+    };
+    if depth >= FILE_TREE_MAX_DEPTH {
+        node.truncated = true;
+        return Some(node);
+    }
+    if let Ok(entries) = fs::read_dir(path) {
+        node.children = entries
+            .filter_map(|e| e.ok())
+            .filter_map(|e| build_file_node(&e.path(), depth + 1))
+            .collect();
+    }
+    // Directories first, then by name
+    node.children
+        .sort_by(|a, b| (a.kind != "dir", &a.name).cmp(&(b.kind != "dir", &b.name)));
+    node.size_bytes = node.children.iter().map(|c| c.size_bytes).sum();
+    Some(node)
+}
+
+/// Nested view of a project's raw/ inputs for the import panel's tree.
+#[tauri::command]
+pub async fn get_raw_files_tree(project_id: String) -> Result<FileNode, String> {
+    let raw_dir = ProjectDirManager::new().project_path(&project_id).join("raw");
+    if !raw_dir.exists() {
+        fs::create_dir_all(&raw_dir).map_err(|e| format!("Failed to create raw dir: {}", e))?;
+    }
+    build_file_node(&raw_dir, 0).ok_or_else(|| "Failed to read raw directory".to_string())
+}
+
 /// Binary document extensions that require Python-based text extraction.
 const BINARY_DOC_EXTENSIONS: &[&str] = &["pdf", "docx", "doc"];
 
@@ -222,14 +491,35 @@ pub fn extract_text_via_python(file_path: &str, max_chars: usize) -> Result<Stri
     Ok(text)
 }
 
+/// Bytes inspected at the start of a file when sniffing for binary content.
+const BINARY_SNIFF_BYTES: usize = 8192;
+
+/// Null-byte heuristic: text files essentially never contain NUL.
+fn looks_binary(path: &std::path::Path) -> std::io::Result<bool> {
+    use std::io::Read;
+    let mut head = Vec::with_capacity(BINARY_SNIFF_BYTES);
+    fs::File::open(path)?
+        .take(BINARY_SNIFF_BYTES as u64)
+        .read_to_end(&mut head)?;
+    Ok(head.contains(&0))
+}
+
+/// Text of a raw file for preview. Binary files other than PDF/DOCX (images,
+/// archives) are rejected instead of being sent to the UI as garbage.
 #[tauri::command]
 pub async fn read_file_content(path: String) -> Result<String, String> {
     let p = std::path::Path::new(&path);
     if is_binary_doc(p) {
-        extract_text_via_python(&path, 0)
-    } else {
-        fs::read_to_string(&path).map_err(|e| format!("Failed to read file: {}", e))
+        return extract_text_via_python(&path, 0);
+    }
+    if looks_binary(p).map_err(|e| format!("Failed to read file: {}", e))? {
+        let size = fs::metadata(p).map(|m| m.len()).unwrap_or(0);
+        return Err(format!(
+            "Binary file cannot be previewed ({} bytes). Only text, PDF and DOCX files are supported.",
+            size
+        ));
     }
+    fs::read_to_string(&path).map_err(|e| format!("Failed to read file: {}", e))
 }
 
 #[tauri::command]
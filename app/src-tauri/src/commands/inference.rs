@@ -1,6 +1,138 @@
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use once_cell::sync::Lazy;
 use tauri::Emitter;
 use crate::python::PythonExecutor;
+use crate::commands::report::script_protocol_supported;
+use crate::python::parser::{JsonLineParser, ParsedLine};
+
+/// PIDs of running inference processes, keyed by request id.
+static INFERENCE_PROCESSES: Lazy<Mutex<HashMap<String, u32>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Inference PIDs terminated on request; their tasks report `inference:stopped`
+/// instead of an error.
+static CANCELLED_INFERENCE: Lazy<Mutex<HashSet<u32>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// How long `stop_inference` waits for a process group to exit before SIGKILL.
+const INFERENCE_STOP_GRACE: std::time::Duration = std::time::Duration::from_secs(3);
+
+fn track_inference(request_id: &str, pid: Option<u32>) {
+    if let (Some(pid), Ok(mut map)) = (pid, INFERENCE_PROCESSES.lock()) {
+        map.insert(request_id.to_string(), pid);
+    }
+}
+
+fn untrack_inference(request_id: &str, pid: Option<u32>) {
+    if let Ok(mut map) = INFERENCE_PROCESSES.lock() {
+        // A newer run may have reused the request id.
+        if pid.is_some() && map.get(request_id).copied() == pid {
+            map.remove(request_id);
+        }
+    }
+}
+
+fn tracked_inference_pids() -> Vec<u32> {
+    INFERENCE_PROCESSES
+        .lock()
+        .map(|map| map.values().copied().collect())
+        .unwrap_or_default()
+}
+
+/// Mark `pids` as cancelled and SIGTERM their process groups.
+fn cancel_inference_pids(pids: &[u32]) {
+    if let Ok(mut set) = CANCELLED_INFERENCE.lock() {
+        set.extend(pids.iter().copied());
+    }
+    for pid in pids {
+        crate::commands::processes::terminate_pid(*pid);
+    }
+}
+
+/// Mark the process running `request_id` as cancelled and return its PID.
+fn mark_request_cancelled(request_id: &str) -> Result<u32, String> {
+    let pid = INFERENCE_PROCESSES
+        .lock()
+        .map_err(|e| e.to_string())?
+        .get(request_id)
+        .copied()
+        .ok_or_else(|| format!("Inference request {} is not running (it may have already finished).", request_id))?;
+    CANCELLED_INFERENCE.lock().map_err(|e| e.to_string())?.insert(pid);
+    Ok(pid)
+}
+
+/// True (once) when `pid` was stopped through `stop_inference` / `stop_all`.
+fn take_cancelled(pid: Option<u32>) -> bool {
+    match (pid, CANCELLED_INFERENCE.lock()) {
+        (Some(pid), Ok(mut set)) => set.remove(&pid),
+        _ => false,
+    }
+}
+
+/// Bookkeeping for one inference process: tracked for `stop_inference` while
+/// it runs, and remembers whether any output arrived so a stop can tell a
+/// cancelled model load apart from a cancelled generation.
+struct InferenceRun {
+    request_id: String,
+    pid: Option<u32>,
+    produced_output: bool,
+}
+
+impl InferenceRun {
+    fn start(request_id: &str, pid: Option<u32>) -> Self {
+        track_inference(request_id, pid);
+        Self { request_id: request_id.to_string(), pid, produced_output: false }
+    }
+
+    fn note_output(&mut self) {
+        self.produced_output = true;
+    }
+
+    /// Untrack the finished process. Returns the `inference:stopped` payload
+    /// when it was cancelled, `None` when it exited on its own.
+    fn finish(&self) -> Option<serde_json::Value> {
+        untrack_inference(&self.request_id, self.pid);
+        take_cancelled(self.pid).then(|| serde_json::json!({
+            "during_load": !self.produced_output,
+            "request_id": self.request_id
+        }))
+    }
+}
+
+/// Whether any inference process is running.
+pub fn any_inference_active() -> bool {
+    !tracked_inference_pids().is_empty()
+}
+
+/// Terminate every running inference process. Returns how many were stopped.
+pub fn stop_all_inference() -> u32 {
+    let pids = tracked_inference_pids();
+    cancel_inference_pids(&pids);
+    pids.len() as u32
+}
+
+/// Stop the inference run for `request_id`, including a model that is still
+/// loading. The process group gets SIGTERM, then SIGKILL if it has not exited
+/// within a few seconds; the run's task then emits `inference:stopped`.
+#[tauri::command]
+pub async fn stop_inference(request_id: String) -> Result<(), String> {
+    let pid = mark_request_cancelled(&request_id)?;
+    crate::commands::processes::terminate_pid(pid);
+
+    // A task untracks its pid once the child has been reaped.
+    let deadline = std::time::Instant::now() + INFERENCE_STOP_GRACE;
+    while tracked_inference_pids().contains(&pid) {
+        if std::time::Instant::now() >= deadline {
+            unsafe {
+                libc::kill(-(pid as i32), libc::SIGKILL);
+                libc::kill(pid as i32, libc::SIGKILL);
+            }
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    Ok(())
+}
 
 #[derive(Deserialize, Serialize)]
 pub struct InferenceMessage {
@@ -8,6 +140,47 @@ pub struct InferenceMessage {
     pub content: String,
 }
 
+/// Limits for user-supplied stop sequences.
+const MAX_STOP_SEQUENCES: usize = 8;
+const MAX_STOP_SEQUENCE_CHARS: usize = 64;
+
+fn validate_stop_sequences(stop: Option<Vec<String>>) -> Result<Vec<String>, String> {
+    let stop = stop.unwrap_or_default();
+    if stop.len() > MAX_STOP_SEQUENCES {
+        return Err(format!("At most {} stop sequences are allowed.", MAX_STOP_SEQUENCES));
+    }
+    for s in &stop {
+        if s.is_empty() {
+            return Err("Stop sequences must not be empty.".into());
+        }
+        if s.chars().count() > MAX_STOP_SEQUENCE_CHARS {
+            return Err(format!(
+                "Stop sequence is too long (max {} characters).",
+                MAX_STOP_SEQUENCE_CHARS
+            ));
+        }
+    }
+    Ok(stop)
+}
+
+/// Byte index of the earliest stop sequence in `text`, with the matching stop.
+fn find_stop<'a>(text: &str, stops: &'a [String]) -> Option<(usize, &'a str)> {
+    stops
+        .iter()
+        .filter_map(|s| text.find(s.as_str()).map(|i| (i, s.as_str())))
+        .min_by_key(|(i, _)| *i)
+}
+
+/// Optional `start_inference` settings beyond the sampling basics.
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct InferenceOptions {
+    pub stop: Option<Vec<String>>,
+    /// "mlx" (default) or "ollama".
+    pub engine: Option<String>,
+    pub prompt_template: Option<String>,
+}
+
 #[tauri::command]
 pub async fn start_inference(
     app: tauri::AppHandle,
@@ -20,7 +193,32 @@ pub async fn start_inference(
     temperature: Option<f64>,
     lang: Option<String>,
     request_id: Option<String>,
+    options: Option<InferenceOptions>,
 ) -> Result<(), String> {
+    let InferenceOptions { stop, engine, prompt_template } = options.unwrap_or_default();
+    let prompt_template = prompt_template.filter(|t| !t.trim().is_empty());
+    if let Some(ref template) = prompt_template {
+        if !template.contains("{prompt}") {
+            return Err("Prompt template must contain a {prompt} placeholder.".into());
+        }
+    }
+    // A template wraps only the current prompt, so earlier turns would be dropped.
+    if prompt_template.is_some() && messages.as_ref().is_some_and(|m| m.len() > 1) {
+        return Err("Prompt templates apply to a single prompt and can't include conversation history. Clear the chat or remove the template.".into());
+    }
+
+    match engine.as_deref().unwrap_or("mlx") {
+        "mlx" => {}
+        "ollama" => {
+            if prompt_template.is_some() {
+                return Err("Prompt templates are only supported with the MLX engine.".into());
+            }
+            let stops = validate_stop_sequences(stop)?;
+            return start_ollama_inference(app, model, prompt, messages, stops, request_id.unwrap_or_default());
+        }
+        other => return Err(format!("Unknown inference engine: {}", other)),
+    }
+
     let executor = PythonExecutor::default();
     if !executor.is_ready() {
         return Err("Python environment is not ready.".into());
@@ -32,6 +230,13 @@ pub async fn start_inference(
         return Err(format!("Inference script not found at: {}", script.display()));
     }
 
+    let stops = validate_stop_sequences(stop)?;
+    // Older bundled scripts reject unknown args, so only pass --stop when supported.
+    let script_takes_stop = script_protocol_supported(&executor, "inference.py");
+    if prompt_template.is_some() && !script_takes_stop {
+        return Err("The bundled inference script is out of date and does not support prompt templates.".into());
+    }
+
     let resolved_adapter = adapter_path.filter(|p| !p.is_empty());
     let messages_json = messages
         .filter(|items| !items.is_empty())
@@ -41,6 +246,7 @@ pub async fn start_inference(
     let max_tok = max_tokens.unwrap_or(1024);
     let temp = temperature.unwrap_or(0.7);
     let req_id = request_id.unwrap_or_default();
+    let hf_endpoint = crate::commands::config::configured_hf_endpoint();
 
     tokio::spawn(async move {
         let mut args = vec![
@@ -63,11 +269,24 @@ pub async fn start_inference(
             args.push("--messages-json".to_string());
             args.push(messages);
         }
+        if script_takes_stop {
+            for s in &stops {
+                args.push("--stop".to_string());
+                args.push(s.clone());
+            }
+        }
+        if let Some(template) = prompt_template {
+            args.push("--prompt-template".to_string());
+            args.push(template);
+        }
         args.push("--lang".to_string());
         args.push(lang.unwrap_or_else(|| "en".to_string()));
 
         let result = tokio::process::Command::new(&python_bin)
             .args(&args)
+            .envs(hf_endpoint.as_ref().map(|e| ("HF_ENDPOINT", e)))
+            // Own process group, so a stop reaches anything spawned while loading
+            .process_group(0)
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::piped())
             .spawn();
@@ -76,6 +295,8 @@ pub async fn start_inference(
             Ok(mut child) => {
                 use tokio::io::{AsyncBufReadExt, BufReader};
 
+                let mut run = InferenceRun::start(&req_id, child.id());
+
                 // Collect stderr in background for error reporting
                 let stderr_handle = child.stderr.take().map(|stderr| {
                     tokio::spawn(async move {
@@ -92,8 +313,20 @@ pub async fn start_inference(
                 if let Some(stdout) = child.stdout.take() {
                     let reader = BufReader::new(stdout);
                     let mut lines = reader.lines();
+                    let mut parser = JsonLineParser::new();
                     while let Ok(Some(line)) = lines.next_line().await {
-                        if let Ok(mut event) = serde_json::from_str::<serde_json::Value>(&line) {
+                        for item in parser.push(&line) {
+                            let mut event = match item {
+                                ParsedLine::Event(event) => event,
+                                ParsedLine::ParseError(line) => {
+                                    let _ = app.emit("inference:parse_error", serde_json::json!({
+                                        "line": line,
+                                        "request_id": req_id
+                                    }));
+                                    continue;
+                                }
+                                ParsedLine::Text(_) => continue,
+                            };
                             if !req_id.is_empty() {
                                 if let Some(obj) = event.as_object_mut() {
                                     obj.insert(
@@ -102,13 +335,33 @@ pub async fn start_inference(
                                     );
                                 }
                             }
-                            let event_type = event["type"].as_str().unwrap_or("unknown");
+                            let event_type = event["type"].as_str().unwrap_or("unknown").to_string();
+                            if event_type == "token" || event_type == "response" {
+                                run.note_output();
+                            }
+                            // Safety net in case the script ignores --stop.
+                            if event_type == "response" && !stops.is_empty() {
+                                let text = event["text"].as_str().unwrap_or("").to_string();
+                                if let Some((idx, _)) = find_stop(&text, &stops) {
+                                    event["text"] = serde_json::Value::String(text[..idx].to_string());
+                                }
+                            }
                             let _ = app.emit(&format!("inference:{}", event_type), &event);
                         }
                     }
+                    if let Some(ParsedLine::ParseError(line)) = parser.flush() {
+                        let _ = app.emit("inference:parse_error", serde_json::json!({
+                            "line": line,
+                            "request_id": req_id
+                        }));
+                    }
                 }
-
-                match child.wait().await {
+                let wait_result = child.wait().await;
+                if let Some(stopped) = run.finish() {
+                    let _ = app.emit("inference:stopped", &stopped);
+                    return;
+                }
+                match wait_result {
                     Ok(status) => {
                         if !status.success() {
                             // Try to get stderr content for better error message
@@ -145,3 +398,352 @@ pub async fn start_inference(
 
     Ok(())
 }
+
+/// Sampling settings shared by every prompt of a `batch_inference` run.
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchInferenceParams {
+    pub max_tokens: Option<u32>,
+    pub temperature: Option<f64>,
+    pub stop: Option<Vec<String>>,
+    pub prompt_template: Option<String>,
+    pub lang: Option<String>,
+}
+
+/// Upper bound on prompts per batch run.
+const MAX_BATCH_PROMPTS: usize = 1000;
+
+/// Answer `prompts` with one model load, streaming each result as
+/// `inference:batch-item` and appending it to `projects/<id>/eval/<timestamp>.jsonl`.
+/// Returns that path right away; `inference:batch-complete` follows at the end.
+#[tauri::command]
+pub async fn batch_inference(
+    app: tauri::AppHandle,
+    project_id: String,
+    prompts: Vec<String>,
+    model: String,
+    adapter_path: Option<String>,
+    params: Option<BatchInferenceParams>,
+) -> Result<String, String> {
+    let prompts: Vec<String> = prompts
+        .into_iter()
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect();
+    if prompts.is_empty() {
+        return Err("At least one prompt is required.".into());
+    }
+    if prompts.len() > MAX_BATCH_PROMPTS {
+        return Err(format!("At most {} prompts can be run in one batch.", MAX_BATCH_PROMPTS));
+    }
+    let params = params.unwrap_or_default();
+    let prompt_template = params.prompt_template.filter(|t| !t.trim().is_empty());
+    if prompt_template.as_ref().is_some_and(|t| !t.contains("{prompt}")) {
+        return Err("Prompt template must contain a {prompt} placeholder.".into());
+    }
+    let stops = validate_stop_sequences(params.stop)?;
+
+    let executor = PythonExecutor::default();
+    if !executor.is_ready() {
+        return Err("Python environment is not ready.".into());
+    }
+    let script = PythonExecutor::scripts_dir().join("inference.py");
+    if !script.exists() {
+        return Err(format!("Inference script not found at: {}", script.display()));
+    }
+    if !script_protocol_supported(&executor, "inference.py") {
+        return Err("The bundled inference script is out of date and does not support batch mode.".into());
+    }
+
+    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S").to_string();
+    let eval_dir = crate::fs::ProjectDirManager::new().project_path(&project_id).join("eval");
+    std::fs::create_dir_all(&eval_dir)
+        .map_err(|e| format!("Failed to create eval directory: {}", e))?;
+    let results_path = eval_dir.join(format!("{}.jsonl", timestamp));
+    let batch_file = std::env::temp_dir().join(format!("courtyard_batch_{}_{}.json", project_id, timestamp));
+    std::fs::write(&batch_file, serde_json::to_string(&prompts).map_err(|e| e.to_string())?)
+        .map_err(|e| format!("Failed to write batch prompts: {}", e))?;
+
+    let mut args = vec![
+        script.to_string_lossy().to_string(),
+        "--model".to_string(),
+        model,
+        "--batch-file".to_string(),
+        batch_file.to_string_lossy().to_string(),
+        "--max-tokens".to_string(),
+        params.max_tokens.unwrap_or(1024).to_string(),
+        "--temp".to_string(),
+        format!("{:.2}", params.temperature.unwrap_or(0.7)),
+    ];
+    if let Some(adapter) = adapter_path.filter(|p| !p.is_empty()) {
+        args.push("--adapter-path".to_string());
+        args.push(adapter);
+    }
+    for s in &stops {
+        args.push("--stop".to_string());
+        args.push(s.clone());
+    }
+    if let Some(template) = prompt_template {
+        args.push("--prompt-template".to_string());
+        args.push(template);
+    }
+    args.push("--lang".to_string());
+    args.push(params.lang.unwrap_or_else(|| "en".to_string()));
+
+    let mut child = tokio::process::Command::new(executor.python_bin())
+        .args(&args)
+        .envs(crate::commands::config::configured_hf_endpoint().map(|e| ("HF_ENDPOINT", e)))
+        .process_group(0)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .map_err(|e| {
+            let _ = std::fs::remove_file(&batch_file);
+            format!("Failed to start batch inference: {}", e)
+        })?;
+
+    // Tracked like a chat request, so `stop_inference` can cancel the batch.
+    let req_id = format!("batch:{}", timestamp);
+    let run = InferenceRun::start(&req_id, child.id());
+
+    let results_str = results_path.to_string_lossy().to_string();
+    let out_path = results_path.clone();
+    tokio::spawn(async move {
+        use std::io::Write;
+        use tokio::io::{AsyncBufReadExt, BufReader};
+
+        let mut out = std::fs::File::create(&out_path).ok();
+        let mut written = 0usize;
+        let mut last_error: Option<String> = None;
+        if let Some(stdout) = child.stdout.take() {
+            let mut lines = BufReader::new(stdout).lines();
+            let mut parser = JsonLineParser::new();
+            while let Ok(Some(line)) = lines.next_line().await {
+                for item in parser.push(&line) {
+                    let ParsedLine::Event(mut event) = item else { continue };
+                    match event["type"].as_str().unwrap_or("") {
+                        "batch_item" => {
+                            let mut record = event.clone();
+                            if let Some(obj) = record.as_object_mut() {
+                                obj.remove("type");
+                                obj.remove("total");
+                            }
+                            if let Some(f) = out.as_mut() {
+                                if writeln!(f, "{}", record).is_ok() {
+                                    written += 1;
+                                }
+                            }
+                            event["request_id"] = serde_json::json!(req_id);
+                            let _ = app.emit("inference:batch-item", &event);
+                        }
+                        "error" => last_error = event["message"].as_str().map(String::from),
+                        "status" => {
+                            event["request_id"] = serde_json::json!(req_id);
+                            let _ = app.emit("inference:status", &event);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        let status = child.wait().await;
+        let _ = std::fs::remove_file(&batch_file);
+        let stopped = run.finish().is_some();
+        let error = match status {
+            _ if stopped => None,
+            Ok(s) if s.success() => None,
+            Ok(_) => Some(last_error.unwrap_or_else(|| "Batch inference failed".to_string())),
+            Err(e) => Some(e.to_string()),
+        };
+        let _ = app.emit("inference:batch-complete", serde_json::json!({
+            "request_id": req_id,
+            "path": out_path.to_string_lossy(),
+            "written": written,
+            "stopped": stopped,
+            "error": error,
+        }));
+    });
+
+    Ok(results_str)
+}
+
+/// Run `ollama run <model>` against the exported model, streaming stdout as
+/// `inference:token` and finishing with `inference:response` + `inference:complete`
+/// like the mlx script. Sampling options are left to the Modelfile.
+fn start_ollama_inference(
+    app: tauri::AppHandle,
+    model: String,
+    prompt: String,
+    messages: Option<Vec<InferenceMessage>>,
+    stops: Vec<String>,
+    req_id: String,
+) -> Result<(), String> {
+    let (ollama_bin, installed) = crate::commands::config::resolve_ollama_bin_status_from_config();
+    if !installed {
+        return Err("Ollama binary not found. Please set Ollama Binary Path in Settings.".into());
+    }
+    if model.trim().is_empty() {
+        return Err("An Ollama model name is required.".into());
+    }
+
+    // `ollama run` takes a single prompt, so flatten a conversation into a transcript.
+    let prompt = match messages.filter(|items| !items.is_empty()) {
+        Some(items) => {
+            let mut transcript: Vec<String> = items
+                .iter()
+                .map(|m| {
+                    let role = if m.role == "assistant" { "Assistant" } else { "User" };
+                    format!("{}: {}", role, m.content.trim())
+                })
+                .collect();
+            if items.last().map(|m| m.role != "assistant").unwrap_or(true) {
+                transcript.push("Assistant:".to_string());
+            }
+            transcript.join("\n")
+        }
+        None => prompt,
+    };
+    let ollama_models = crate::commands::environment::resolve_ollama_models_dir();
+
+    tokio::spawn(async move {
+        use tokio::io::AsyncReadExt;
+
+        let result = tokio::process::Command::new(&ollama_bin)
+            .args(["run", "--nowordwrap", model.trim(), &prompt])
+            .env("OLLAMA_MODELS", &ollama_models)
+            .process_group(0)
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn();
+        let mut child = match result {
+            Ok(child) => child,
+            Err(e) => {
+                let _ = app.emit("inference:error", serde_json::json!({
+                    "message": format!("Failed to run ollama: {}", e),
+                    "request_id": req_id
+                }));
+                return;
+            }
+        };
+
+        let mut run = InferenceRun::start(&req_id, child.id());
+        let stderr_handle = child.stderr.take().map(|mut stderr| {
+            tokio::spawn(async move {
+                let mut text = String::new();
+                let _ = stderr.read_to_string(&mut text).await;
+                text
+            })
+        });
+
+        let mut response = String::new();
+        let mut pending: Vec<u8> = Vec::new();
+        let mut matched_stop: Option<String> = None;
+        if let Some(mut stdout) = child.stdout.take() {
+            let mut buf = [0u8; 1024];
+            while let Ok(n) = stdout.read(&mut buf).await {
+                if n == 0 {
+                    break;
+                }
+                // Hold back bytes of a UTF-8 character split across reads.
+                pending.extend_from_slice(&buf[..n]);
+                let valid = match std::str::from_utf8(&pending) {
+                    Ok(text) => text.len(),
+                    Err(e) => e.valid_up_to(),
+                };
+                if valid == 0 {
+                    continue;
+                }
+                let chunk = String::from_utf8_lossy(&pending[..valid]).to_string();
+                pending.drain(..valid);
+                response.push_str(&chunk);
+                run.note_output();
+
+                if let Some((idx, stop)) = find_stop(&response, &stops) {
+                    response.truncate(idx);
+                    matched_stop = Some(stop.to_string());
+                    let _ = child.kill().await;
+                    break;
+                }
+                let _ = app.emit("inference:token", serde_json::json!({
+                    "text": chunk,
+                    "request_id": req_id
+                }));
+            }
+        }
+
+        let wait_result = child.wait().await;
+        if let Some(stopped) = run.finish() {
+            let _ = app.emit("inference:stopped", &stopped);
+            return;
+        }
+        let stderr_text = match stderr_handle {
+            Some(handle) => handle.await.unwrap_or_default(),
+            None => String::new(),
+        };
+        match wait_result {
+            Ok(status) if status.success() || matched_stop.is_some() => {
+                let text = response.trim().to_string();
+                let _ = app.emit("inference:response", serde_json::json!({
+                    "text": text,
+                    "request_id": req_id
+                }));
+                let _ = app.emit("inference:complete", serde_json::json!({
+                    "tokens": text.split_whitespace().count(),
+                    "stop": matched_stop,
+                    "request_id": req_id
+                }));
+            }
+            Ok(_) => {
+                let msg = stderr_text.trim();
+                let _ = app.emit("inference:error", serde_json::json!({
+                    "message": if msg.is_empty() { "ollama run failed" } else { msg },
+                    "request_id": req_id
+                }));
+            }
+            Err(e) => {
+                let _ = app.emit("inference:error", serde_json::json!({
+                    "message": e.to_string(),
+                    "request_id": req_id
+                }));
+            }
+        }
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Never signalled: the tests only mark PIDs, they don't terminate them.
+    const LOADING_PID: u32 = u32::MAX - 1;
+    const ANSWERING_PID: u32 = u32::MAX - 2;
+    const FINISHED_PID: u32 = u32::MAX - 3;
+
+    #[test]
+    fn cancel_before_output_is_reported_during_load() {
+        let run = InferenceRun::start("test-loading", Some(LOADING_PID));
+        assert_eq!(mark_request_cancelled("test-loading"), Ok(LOADING_PID));
+        let stopped = run.finish().expect("cancelled run reports inference:stopped");
+        assert_eq!(stopped["during_load"], true);
+        assert_eq!(stopped["request_id"], "test-loading");
+        assert!(mark_request_cancelled("test-loading").is_err());
+    }
+
+    #[test]
+    fn cancel_after_output_is_not_during_load() {
+        let mut run = InferenceRun::start("test-answering", Some(ANSWERING_PID));
+        run.note_output();
+        mark_request_cancelled("test-answering").unwrap();
+        assert_eq!(run.finish().unwrap()["during_load"], false);
+    }
+
+    #[test]
+    fn finished_run_is_not_reported_stopped() {
+        let run = InferenceRun::start("test-finished", Some(FINISHED_PID));
+        assert!(run.finish().is_none());
+        assert!(mark_request_cancelled("test-finished").is_err());
+    }
+}
@@ -1,3 +1,4 @@
+pub mod chat_templates;
 pub mod config;
 pub mod dataset;
 pub mod environment;
@@ -6,6 +7,8 @@ pub mod files;
 pub mod inference;
 pub mod native_notification;
 pub mod notification_config;
+pub mod processes;
 pub mod project;
+pub mod report;
 pub mod storage;
 pub mod training;
@@ -3,7 +3,7 @@ use std::sync::Mutex;
 use once_cell::sync::Lazy;
 use tauri::Emitter;
 use crate::python::PythonExecutor;
-use crate::commands::config::{load_config, hf_endpoint_for_source};
+use crate::commands::config::{load_config, configured_hf_endpoint};
 
 static DOWNLOAD_PROCESSES: Lazy<Mutex<HashMap<String, u32>>> =
     Lazy::new(|| Mutex::new(HashMap::new()));
@@ -31,7 +31,7 @@ pub async fn download_model(
 
     // Read configured HF download source for HF_ENDPOINT env var
     let app_config = load_config();
-    let hf_endpoint = hf_endpoint_for_source(&app_config.hf_source);
+    let hf_endpoint = configured_hf_endpoint();
 
     // Optionally pass custom cache dir
     let cache_dir = app_config.model_paths.huggingface.clone();
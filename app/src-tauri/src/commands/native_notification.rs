@@ -39,6 +39,25 @@ pub fn send_native_notification<R: Runtime>(
     }
 }
 
+/// OS notification for a finished long-running job, sent only when
+/// `notify_on_complete` is enabled. `outcome` is appended to the project name.
+pub async fn notify_job_finished(app: &tauri::AppHandle, project_id: &str, title: &str, outcome: &str) {
+    if !crate::commands::config::load_config().notify_on_complete {
+        return;
+    }
+    let project_name = match crate::db::store::sqlite_pool(app).await {
+        Some(pool) => crate::db::store::load_project_name(&pool, project_id).await,
+        None => None,
+    };
+    let body = format!("{}: {}", project_name.as_deref().unwrap_or(project_id), outcome);
+
+    // Best effort: a failed notification must not affect the job result.
+    #[cfg(target_os = "macos")]
+    let _ = send_macos_script_notification(title, &body);
+    #[cfg(not(target_os = "macos"))]
+    let _ = send_plugin_notification(app, title, &body, None);
+}
+
 #[cfg(not(target_os = "macos"))]
 fn send_plugin_notification<R: Runtime>(
     app: &AppHandle<R>,
@@ -0,0 +1,76 @@
+use serde::Serialize;
+use std::path::Path;
+use crate::commands::export::MlxServerState;
+
+/// How many processes `stop_all` terminated in each category.
+#[derive(Serialize, Default)]
+pub struct StopAllReport {
+    pub training: u32,
+    pub generation: u32,
+    pub cleaning: u32,
+    pub inference: u32,
+    pub export: u32,
+    pub mlx_server: u32,
+    pub ollama_pull: u32,
+}
+
+/// SIGTERM a tracked process and its process group (caffeinate + python).
+pub fn terminate_pid(pid: u32) {
+    unsafe {
+        libc::kill(-(pid as i32), libc::SIGTERM);
+        libc::kill(pid as i32, libc::SIGTERM);
+    }
+}
+
+/// Command for `program`, wrapped in `caffeinate -i` unless sleep prevention
+/// is off. A per-run `prevent_sleep` overrides the global setting.
+pub fn maybe_caffeinate(program: &Path, prevent_sleep: Option<bool>) -> tokio::process::Command {
+    let enabled = prevent_sleep
+        .or_else(|| crate::commands::config::load_config().prevent_sleep)
+        .unwrap_or(true);
+    if enabled {
+        let mut cmd = tokio::process::Command::new("caffeinate");
+        cmd.arg("-i").arg(program);
+        cmd
+    } else {
+        tokio::process::Command::new(program)
+    }
+}
+
+/// Kinds of tracked jobs currently running on the app's Python environment.
+pub fn active_python_jobs() -> Vec<&'static str> {
+    [
+        ("training", crate::commands::training::any_training_active()),
+        ("dataset generation", crate::commands::dataset::any_generation_active()),
+        ("cleaning", crate::commands::dataset::is_cleaning_active()),
+        ("inference", crate::commands::inference::any_inference_active()),
+        ("export", crate::commands::export::any_export_active()),
+    ]
+    .into_iter()
+    .filter_map(|(kind, active)| active.then_some(kind))
+    .collect()
+}
+
+/// Emergency stop: terminate every tracked background process.
+#[tauri::command]
+pub async fn stop_all(state: tauri::State<'_, MlxServerState>) -> Result<StopAllReport, String> {
+    let mlx_server = {
+        let mut guard = state.0.lock().map_err(|e| e.to_string())?;
+        match guard.take() {
+            Some((pid, _, _)) => {
+                terminate_pid(pid);
+                1
+            }
+            None => 0,
+        }
+    };
+    Ok(StopAllReport {
+        training: crate::commands::training::stop_all_training(),
+        generation: crate::commands::dataset::stop_tracked_generation(),
+        cleaning: crate::commands::dataset::stop_tracked_cleaning(),
+        inference: crate::commands::inference::stop_all_inference(),
+        export: crate::commands::export::stop_all_exports(),
+        mlx_server,
+        ollama_pull: crate::commands::environment::stop_all_ollama_pulls(),
+    })
+}
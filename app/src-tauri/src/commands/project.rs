@@ -1,6 +1,11 @@
 use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use once_cell::sync::Lazy;
 use uuid::Uuid;
 use crate::fs::ProjectDirManager;
+use crate::commands::storage::{dir_size, newest_mtime};
 
 #[derive(Clone, Serialize)]
 pub struct ProjectInfo {
@@ -11,6 +16,47 @@ pub struct ProjectInfo {
     pub model_path: Option<String>,
     pub created_at: String,
     pub updated_at: String,
+    /// Total on-disk size of the project directory.
+    pub total_bytes: u64,
+    /// Newest modification time across the project tree ("YYYY-MM-DD HH:MM:SS", UTC).
+    pub last_modified: Option<String>,
+    /// Hidden from the default project list; data stays on disk.
+    pub archived: bool,
+}
+
+/// How long a computed (size, mtime) pair stays valid. Walking large adapter and
+/// export trees is slow, and the projects screen re-lists often.
+const PROJECT_STATS_TTL: Duration = Duration::from_secs(60);
+
+/// project_id -> (computed_at, total_bytes, newest_mtime_secs)
+type ProjectStatsCache = HashMap<String, (Instant, u64, u64)>;
+
+static PROJECT_STATS_CACHE: Lazy<Mutex<ProjectStatsCache>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Return (total_bytes, newest_mtime_secs) for a project, reusing a recent result.
+fn project_stats(project_id: &str, project_path: &std::path::Path) -> (u64, u64) {
+    if let Ok(cache) = PROJECT_STATS_CACHE.lock() {
+        if let Some((at, bytes, mtime)) = cache.get(project_id) {
+            if at.elapsed() < PROJECT_STATS_TTL {
+                return (*bytes, *mtime);
+            }
+        }
+    }
+    let bytes = dir_size(project_path);
+    let mtime = newest_mtime(project_path);
+    if let Ok(mut cache) = PROJECT_STATS_CACHE.lock() {
+        cache.insert(project_id.to_string(), (Instant::now(), bytes, mtime));
+    }
+    (bytes, mtime)
+}
+
+fn format_mtime(secs: u64) -> Option<String> {
+    if secs == 0 {
+        return None;
+    }
+    chrono::DateTime::from_timestamp(secs as i64, 0)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
 }
 
 #[tauri::command]
@@ -28,20 +74,116 @@ pub async fn create_project(name: String) -> Result<ProjectInfo, String> {
         status: "created".to_string(),
         model_path: None,
         created_at: now.clone(),
-        updated_at: now,
+        updated_at: now.clone(),
+        total_bytes: 0,
+        last_modified: Some(now),
+        archived: false,
     })
 }
 
+/// List projects from the database, enriched with disk usage and last activity.
+/// `sort_by`: "size" (largest first), "recent" (most recently touched first),
+/// anything else keeps the default newest-created-first order.
+/// Archived projects are skipped unless `include_archived` is set.
+#[tauri::command]
+pub async fn list_projects(
+    app: tauri::AppHandle,
+    sort_by: Option<String>,
+    include_archived: Option<bool>,
+) -> Result<Vec<ProjectInfo>, String> {
+    let Some(pool) = crate::db::store::sqlite_pool(&app).await else {
+        // Database not loaded yet by the frontend.
+        return Ok(vec![]);
+    };
+    let rows = crate::db::store::load_projects(&pool).await?;
+    let include_archived = include_archived.unwrap_or(false);
+
+    let dir_manager = ProjectDirManager::new();
+    let mut projects: Vec<(ProjectInfo, u64)> = rows
+        .into_iter()
+        .filter(|row| include_archived || !row.archived)
+        .map(|row| {
+            let (total_bytes, mtime) = project_stats(&row.id, &dir_manager.project_path(&row.id));
+            let info = ProjectInfo {
+                id: row.id,
+                name: row.name,
+                path: row.path,
+                status: row.status,
+                model_path: row.model_path,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+                total_bytes,
+                last_modified: format_mtime(mtime),
+                archived: row.archived,
+            };
+            (info, mtime)
+        })
+        .collect();
+
+    match sort_by.as_deref() {
+        Some("size") => projects.sort_by_key(|p| std::cmp::Reverse(p.0.total_bytes)),
+        Some("recent") => projects.sort_by_key(|p| std::cmp::Reverse(p.1)),
+        _ => {}
+    }
+
+    Ok(projects.into_iter().map(|(info, _)| info).collect())
+}
+
+async fn set_archived(app: &tauri::AppHandle, id: &str, archived: bool) -> Result<(), String> {
+    let pool = crate::db::store::sqlite_pool(app)
+        .await
+        .ok_or_else(|| "Database is not loaded yet".to_string())?;
+    crate::db::store::set_project_archived(&pool, id, archived).await
+}
+
 #[tauri::command]
-pub async fn list_projects() -> Result<Vec<ProjectInfo>, String> {
-    // Frontend reads projects from SQLite directly via tauri-plugin-sql.
-    // This command is kept for API completeness but not used by the UI.
-    Ok(vec![])
+pub async fn archive_project(app: tauri::AppHandle, id: String) -> Result<(), String> {
+    set_archived(&app, &id, true).await
+}
+
+#[tauri::command]
+pub async fn unarchive_project(app: tauri::AppHandle, id: String) -> Result<(), String> {
+    set_archived(&app, &id, false).await
+}
+
+/// What `delete_project` would remove.
+#[derive(Serialize)]
+pub struct DeleteImpact {
+    pub total_bytes: u64,
+    pub adapter_count: u32,
+    pub dataset_version_count: u32,
+    /// Job ids of training runs still writing into this project.
+    pub active_training_jobs: Vec<String>,
+}
+
+/// Preview of a project deletion, so the UI can state the cost and refuse
+/// while training is running.
+#[tauri::command]
+pub fn get_project_delete_impact(project_id: String) -> Result<DeleteImpact, String> {
+    let project_path = ProjectDirManager::new().project_path(&project_id);
+    if !project_path.is_dir() {
+        return Err(format!("Project not found: {}", project_id));
+    }
+    let adapter_count = std::fs::read_dir(project_path.join("adapters"))
+        .map(|rd| rd.filter_map(|e| e.ok()).filter(|e| e.path().is_dir()).count() as u32)
+        .unwrap_or(0);
+    let dataset_version_count =
+        crate::commands::dataset::list_dataset_versions(project_id)?.len() as u32;
+
+    Ok(DeleteImpact {
+        total_bytes: dir_size(&project_path),
+        adapter_count,
+        dataset_version_count,
+        active_training_jobs: crate::commands::training::active_training_jobs_in(&project_path),
+    })
 }
 
 #[tauri::command]
 pub async fn delete_project(id: String) -> Result<(), String> {
     let dir_manager = ProjectDirManager::new();
     dir_manager.delete_project_dir(&id)?;
+    if let Ok(mut cache) = PROJECT_STATS_CACHE.lock() {
+        cache.remove(&id);
+    }
     Ok(())
 }
@@ -0,0 +1,324 @@
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+use once_cell::sync::Lazy;
+use crate::commands::config::{hf_endpoint_for_source, load_config, resolve_model_paths, resolve_ollama_bin_status};
+use crate::commands::environment::{
+    detect_mlx_lm_version, detect_python_version, get_chip_name, get_os_version, get_system_memory_gb,
+    is_ollama_version_supported, is_python_version_supported, parse_ollama_version,
+    resolve_ollama_models_dir, MAX_PYTHON_VERSION, MIN_MLX_LM_VERSION, MIN_OLLAMA_VERSION,
+    MIN_PYTHON_VERSION,
+};
+use crate::fs::ProjectDirManager;
+use crate::python::PythonExecutor;
+
+/// Scripts the app shells out to, with the protocol version this build
+/// expects each to report via `--version`. A script bumps its version when
+/// its arguments or events change; a missing one usually means a broken bundle.
+const EXPECTED_SCRIPTS: &[(&str, &str)] = &[
+    ("clean_data.py", "2"),
+    ("extract_text.py", "1"),
+    ("generate_dataset.py", "1"),
+    ("generate_dataset_builtin.py", "1"),
+    ("generate_dataset_lmstudio.py", "1"),
+    ("generate_dataset_ollama.py", "1"),
+    ("inference.py", "2"),
+    ("export_ollama.py", "2"),
+    ("export_gguf.py", "2"),
+    ("export_mlx.py", "1"),
+];
+
+/// Protocol version `script` reports via `--version`, if it runs.
+fn script_protocol_version(python: &Path, script: &Path) -> Option<String> {
+    std::process::Command::new(python)
+        .arg(script)
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// `--version` results per (python, script), valid while the script's mtime is unchanged.
+type ScriptVersionCache = HashMap<(PathBuf, PathBuf), (SystemTime, Option<String>)>;
+static SCRIPT_VERSION_CACHE: Lazy<Mutex<ScriptVersionCache>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// `script_protocol_version`, re-running the script only after it changed on disk.
+fn cached_script_protocol_version(python: &Path, script: &Path) -> Option<String> {
+    let mtime = std::fs::metadata(script).and_then(|m| m.modified()).ok()?;
+    let key = (python.to_path_buf(), script.to_path_buf());
+    if let Some((cached_mtime, version)) = SCRIPT_VERSION_CACHE.lock().ok()?.get(&key) {
+        if *cached_mtime == mtime {
+            return version.clone();
+        }
+    }
+    let version = script_protocol_version(python, script);
+    if let Ok(mut cache) = SCRIPT_VERSION_CACHE.lock() {
+        cache.insert(key, (mtime, version.clone()));
+    }
+    version
+}
+
+/// Whether `installed` is at least protocol `expected`; both are integers.
+fn protocol_at_least(installed: &str, expected: &str) -> bool {
+    match (installed.parse::<u32>(), expected.parse::<u32>()) {
+        (Ok(installed), Ok(expected)) => installed >= expected,
+        _ => false,
+    }
+}
+
+/// Whether the installed `script_name` speaks at least the protocol this
+/// build expects, i.e. accepts every argument the app passes to it.
+pub fn script_protocol_supported(executor: &PythonExecutor, script_name: &str) -> bool {
+    let Some((_, expected)) = EXPECTED_SCRIPTS.iter().find(|(name, _)| *name == script_name) else {
+        return false;
+    };
+    let script = PythonExecutor::scripts_dir().join(script_name);
+    cached_script_protocol_version(executor.python_bin(), &script)
+        .is_some_and(|installed| protocol_at_least(&installed, expected))
+}
+
+/// Protocol version per expected script, or "missing"/"unknown" when it
+/// can't be read. Scripts are queried in parallel.
+fn collect_script_versions(executor: &PythonExecutor) -> HashMap<String, String> {
+    let scripts_dir = PythonExecutor::scripts_dir();
+    let python = executor.python_bin();
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = EXPECTED_SCRIPTS
+            .iter()
+            .map(|(name, _)| {
+                let script = scripts_dir.join(name);
+                scope.spawn(move || {
+                    let version = if !script.exists() {
+                        "missing".to_string()
+                    } else {
+                        script_protocol_version(python, &script)
+                            .unwrap_or_else(|| "unknown".to_string())
+                    };
+                    (name.to_string(), version)
+                })
+            })
+            .collect();
+        handles.into_iter().filter_map(|h| h.join().ok()).collect()
+    })
+}
+
+/// Protocol version reported by each bundled script.
+#[tauri::command]
+pub fn get_script_versions() -> Result<HashMap<String, String>, String> {
+    let executor = PythonExecutor::default();
+    if !executor.is_ready() {
+        return Err("Python environment is not set up".to_string());
+    }
+    Ok(collect_script_versions(&executor))
+}
+
+/// First line of `<bin> --version`, if it runs.
+fn binary_version(bin: &Path) -> Option<String> {
+    std::process::Command::new(bin)
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).lines().next().unwrap_or("").trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Free space (bytes) on the volume holding `path`, via `df -k`.
+pub(crate) fn disk_free_bytes(path: &Path) -> Option<u64> {
+    let out = std::process::Command::new("df")
+        .args(["-k", &path.to_string_lossy()])
+        .output()
+        .ok()?;
+    if !out.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let line = stdout.lines().nth(1)?;
+    let available_kb: u64 = line.split_whitespace().nth(3)?.parse().ok()?;
+    Some(available_kb * 1024)
+}
+
+fn format_gb(bytes: u64) -> String {
+    format!("{:.1} GB", bytes as f64 / 1_073_741_824.0)
+}
+
+/// Strip `user:password@` from proxy URLs.
+fn redact_url_credentials(url: &str) -> String {
+    match (url.find("://"), url.rfind('@')) {
+        (Some(scheme_end), Some(at)) if at > scheme_end => {
+            format!("{}://***@{}", &url[..scheme_end], &url[at + 1..])
+        }
+        _ => url.to_string(),
+    }
+}
+
+/// Assemble a pasteable Markdown report of the environment for bug reports.
+/// With `redact` (default true) the home directory is replaced by `~` and proxy
+/// credentials are masked.
+#[tauri::command]
+pub fn get_system_report(redact: Option<bool>) -> Result<String, String> {
+    let redact = redact.unwrap_or(true);
+    let home = dirs::home_dir()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let show = |p: &Path| -> String {
+        let s = p.to_string_lossy().to_string();
+        if redact && !home.is_empty() && s.starts_with(&home) {
+            format!("~{}", &s[home.len()..])
+        } else {
+            s
+        }
+    };
+
+    let config = load_config();
+    let executor = PythonExecutor::default();
+    let dir_manager = ProjectDirManager::new();
+    let base_dir = dir_manager.base_dir().clone();
+    let resolved = resolve_model_paths();
+    let (ollama_bin, ollama_installed) = resolve_ollama_bin_status(&config);
+    let uv_path = PythonExecutor::find_uv();
+
+    let mut r = String::new();
+    let _ = writeln!(r, "## M-Courtyard system report\n");
+    let _ = writeln!(r, "### System");
+    let _ = writeln!(r, "- App version: {}", env!("CARGO_PKG_VERSION"));
+    let _ = writeln!(r, "- OS: {}", get_os_version());
+    let _ = writeln!(r, "- Chip: {}", get_chip_name());
+    let _ = writeln!(r, "- Memory: {:.1} GB", get_system_memory_gb());
+    let _ = writeln!(
+        r,
+        "- Disk free (base dir volume): {}",
+        disk_free_bytes(&base_dir).map(format_gb).unwrap_or_else(|| "unknown".to_string())
+    );
+
+    let _ = writeln!(r, "\n### Tooling");
+    let _ = writeln!(r, "- Python venv ready: {}", executor.is_ready());
+    if let Some(version) = detect_python_version(&executor) {
+        if is_python_version_supported(&version) {
+            let _ = writeln!(r, "- Python: {}", version);
+        } else {
+            let _ = writeln!(
+                r,
+                "- Python: {} — WARNING: outside supported range {}–{}; run Repair environment",
+                version, MIN_PYTHON_VERSION, MAX_PYTHON_VERSION
+            );
+        }
+    }
+    let _ = writeln!(
+        r,
+        "- mlx-lm: {} (min {})",
+        detect_mlx_lm_version(&executor).unwrap_or_else(|| "not installed".to_string()),
+        MIN_MLX_LM_VERSION
+    );
+    let _ = writeln!(
+        r,
+        "- uv: {}",
+        match &uv_path {
+            Some(p) => format!("{} ({})", binary_version(p).unwrap_or_else(|| "unknown version".to_string()), show(p)),
+            None => "not found".to_string(),
+        }
+    );
+    let ollama_version = if ollama_installed {
+        binary_version(Path::new(&ollama_bin))
+    } else {
+        None
+    };
+    let _ = writeln!(
+        r,
+        "- Ollama: {}",
+        if ollama_installed {
+            format!(
+                "{} ({})",
+                ollama_version.clone().unwrap_or_else(|| "unknown version".to_string()),
+                show(Path::new(&ollama_bin))
+            )
+        } else {
+            "not installed".to_string()
+        }
+    );
+    if let Some(version) = ollama_version.as_deref().and_then(parse_ollama_version) {
+        if !is_ollama_version_supported(&version) {
+            let _ = writeln!(
+                r,
+                "  - WARNING: Ollama {} is older than {}; exported models may fail to load. Update Ollama.",
+                version, MIN_OLLAMA_VERSION
+            );
+        }
+    }
+
+    let _ = writeln!(r, "\n### Paths");
+    let _ = writeln!(r, "- Base dir: {}", show(&base_dir));
+    let _ = writeln!(r, "- HuggingFace cache: {}", show(&resolved.huggingface));
+    let _ = writeln!(r, "- ModelScope cache: {}", show(&resolved.modelscope));
+    let _ = writeln!(r, "- LM Studio models: {}", show(&resolved.lmstudio));
+    let _ = writeln!(r, "- Ollama models (effective): {}", show(&resolve_ollama_models_dir()));
+    let _ = writeln!(
+        r,
+        "- Export path: {}",
+        config.export_path.as_deref().map(|p| show(Path::new(p))).unwrap_or_else(|| "(default)".to_string())
+    );
+
+    let _ = writeln!(r, "\n### Network");
+    let _ = writeln!(r, "- HF source: {}", config.hf_source);
+    if let Some(endpoint) = hf_endpoint_for_source(&config.hf_source, config.hf_custom_endpoint.as_deref()) {
+        let _ = writeln!(r, "- HF endpoint: {}", endpoint);
+    }
+    let proxy = |v: &Option<String>| -> String {
+        match v {
+            Some(url) if redact => redact_url_credentials(url),
+            Some(url) => url.clone(),
+            None => "-".to_string(),
+        }
+    };
+    let _ = writeln!(r, "- HTTP proxy: {}", proxy(&config.network.http_proxy));
+    let _ = writeln!(r, "- HTTPS proxy: {}", proxy(&config.network.https_proxy));
+    let _ = writeln!(r, "- Custom CA bundle: {}", config.network.ssl_cert_file.is_some());
+
+    let _ = writeln!(r, "\n### Storage");
+    match crate::commands::storage::scan_storage(&std::collections::HashMap::new(), &std::collections::HashSet::new()) {
+        Ok(usage) => {
+            let _ = writeln!(r, "- Projects: {}", usage.projects.len());
+            let _ = writeln!(r, "- Total: {}", format_gb(usage.total_bytes));
+            let _ = writeln!(r, "- Cleanable: {}", format_gb(usage.cleanable_bytes));
+        }
+        Err(e) => {
+            let _ = writeln!(r, "- Scan failed: {}", e);
+        }
+    }
+
+    let _ = writeln!(r, "\n### Scripts");
+    let scripts_dir = PythonExecutor::scripts_dir();
+    let _ = writeln!(r, "- Scripts dir: {}", show(&scripts_dir));
+    let missing: Vec<&str> = EXPECTED_SCRIPTS
+        .iter()
+        .map(|(name, _)| *name)
+        .filter(|name| !scripts_dir.join(name).exists())
+        .collect();
+    if missing.is_empty() {
+        let _ = writeln!(r, "- All {} scripts present", EXPECTED_SCRIPTS.len());
+    } else {
+        let _ = writeln!(r, "- Missing: {}", missing.join(", "));
+    }
+    if executor.is_ready() {
+        let versions = collect_script_versions(&executor);
+        let mismatched: Vec<String> = EXPECTED_SCRIPTS
+            .iter()
+            .filter_map(|(name, expected)| {
+                let got = versions.get(*name).map(String::as_str).unwrap_or("unknown");
+                (got != "missing" && !protocol_at_least(got, expected))
+                    .then(|| format!("{} (got {}, expected at least {})", name, got, expected))
+            })
+            .collect();
+        if mismatched.is_empty() {
+            let _ = writeln!(r, "- Protocol versions match");
+        } else {
+            let _ = writeln!(r, "- WARNING: protocol mismatch: {}", mismatched.join(", "));
+        }
+    }
+
+    Ok(r)
+}
@@ -1,4 +1,5 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use crate::fs::ProjectDirManager;
 
@@ -11,6 +12,7 @@ pub struct ProjectStorageInfo {
     pub export_fused_bytes: u64,
     pub empty_adapter_count: u32,
     pub checkpoint_bytes: u64,
+    pub archived: bool,
 }
 
 /// Overall storage usage summary
@@ -22,9 +24,28 @@ pub struct StorageUsage {
     pub empty_adapter_count: u32,
     pub tmp_bytes: u64,
     pub checkpoint_bytes: u64,
+    /// `total_bytes` minus archived projects.
+    pub active_bytes: u64,
     pub projects: Vec<ProjectStorageInfo>,
 }
 
+/// One line of `storage_history.jsonl`.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct StorageSnapshot {
+    /// Unix seconds.
+    pub timestamp: u64,
+    pub total_bytes: u64,
+    pub cleanable_bytes: u64,
+    pub export_fused_bytes: u64,
+    pub checkpoint_bytes: u64,
+}
+
+const STORAGE_HISTORY_FILE: &str = "storage_history.jsonl";
+/// Snapshots closer together than this are not recorded.
+const STORAGE_HISTORY_INTERVAL_SECS: u64 = 3600;
+/// Older lines are dropped once the file grows past this.
+const STORAGE_HISTORY_MAX_ENTRIES: usize = 2000;
+
 /// Cleanup result
 #[derive(Serialize)]
 pub struct CleanupResult {
@@ -34,7 +55,7 @@ pub struct CleanupResult {
     pub removed_tmp: bool,
 }
 
-fn dir_size(path: &Path) -> u64 {
+pub fn dir_size(path: &Path) -> u64 {
     if !path.exists() {
         return 0;
     }
@@ -52,6 +73,35 @@ fn dir_size(path: &Path) -> u64 {
     total
 }
 
+/// Newest modification time (unix seconds) of any file or directory under `path`.
+pub fn newest_mtime(path: &Path) -> u64 {
+    let own = std::fs::metadata(path)
+        .ok()
+        .and_then(|m| m.modified().ok())
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let mut newest = own;
+    if let Ok(entries) = std::fs::read_dir(path) {
+        for entry in entries.flatten() {
+            let p = entry.path();
+            if p.is_dir() {
+                newest = newest.max(newest_mtime(&p));
+            } else {
+                let ts = entry
+                    .metadata()
+                    .ok()
+                    .and_then(|m| m.modified().ok())
+                    .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0);
+                newest = newest.max(ts);
+            }
+        }
+    }
+    newest
+}
+
 fn scan_project(project_path: &Path, project_id: &str) -> ProjectStorageInfo {
     let total_bytes = dir_size(project_path);
 
@@ -120,16 +170,109 @@ fn scan_project(project_path: &Path, project_id: &str) -> ProjectStorageInfo {
         export_fused_bytes,
         empty_adapter_count,
         checkpoint_bytes,
+        archived: false,
     }
 }
 
+/// Scan storage usage, naming projects from the DB.
+/// Directories with no matching project row keep `project_name: None`.
 #[tauri::command]
-pub fn scan_storage_usage() -> Result<StorageUsage, String> {
+pub async fn scan_storage_usage(app: tauri::AppHandle) -> Result<StorageUsage, String> {
+    let mut project_names = HashMap::new();
+    let mut archived = HashSet::new();
+    if let Some(pool) = crate::db::store::sqlite_pool(&app).await {
+        if let Ok(rows) = crate::db::store::load_projects(&pool).await {
+            for row in rows {
+                if row.archived {
+                    archived.insert(row.id.clone());
+                }
+                project_names.insert(row.id, row.name);
+            }
+        }
+    }
+    let usage = scan_storage(&project_names, &archived)?;
+    record_storage_snapshot(&usage);
+    Ok(usage)
+}
+
+fn read_storage_history(path: &Path) -> Vec<StorageSnapshot> {
+    std::fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// Append a snapshot to the history file, at most once per hour.
+fn record_storage_snapshot(usage: &StorageUsage) {
+    let path = ProjectDirManager::new().base_dir().join(STORAGE_HISTORY_FILE);
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let mut history = read_storage_history(&path);
+    if history
+        .last()
+        .is_some_and(|last| now.saturating_sub(last.timestamp) < STORAGE_HISTORY_INTERVAL_SECS)
+    {
+        return;
+    }
+    let snapshot = StorageSnapshot {
+        timestamp: now,
+        total_bytes: usage.total_bytes,
+        cleanable_bytes: usage.cleanable_bytes,
+        export_fused_bytes: usage.export_fused_bytes,
+        checkpoint_bytes: usage.checkpoint_bytes,
+    };
+    if history.len() < STORAGE_HISTORY_MAX_ENTRIES {
+        use std::io::Write;
+        if let (Ok(mut file), Ok(line)) = (
+            std::fs::OpenOptions::new().create(true).append(true).open(&path),
+            serde_json::to_string(&snapshot),
+        ) {
+            let _ = writeln!(file, "{}", line);
+        }
+        return;
+    }
+    // Full: rewrite keeping the newest entries.
+    history.push(snapshot);
+    let skip = history.len() - STORAGE_HISTORY_MAX_ENTRIES;
+    let body: String = history[skip..]
+        .iter()
+        .filter_map(|s| serde_json::to_string(s).ok())
+        .map(|line| line + "\n")
+        .collect();
+    let _ = std::fs::write(&path, body);
+}
+
+/// Recorded storage snapshots, oldest first; `limit` keeps only the newest.
+#[tauri::command]
+pub fn get_storage_history(limit: Option<usize>) -> Result<Vec<StorageSnapshot>, String> {
+    let path = ProjectDirManager::new().base_dir().join(STORAGE_HISTORY_FILE);
+    let mut history = read_storage_history(&path);
+    if let Some(limit) = limit {
+        let skip = history.len().saturating_sub(limit);
+        history.drain(..skip);
+    }
+    Ok(history)
+}
+
+/// Filesystem half of `scan_storage_usage`; `project_names` maps id → name,
+/// `archived` holds ids left out of `active_bytes`.
+pub fn scan_storage(
+    project_names: &HashMap<String, String>,
+    archived: &HashSet<String>,
+) -> Result<StorageUsage, String> {
     let dm = ProjectDirManager::new();
-    let home = std::env::var_os("HOME")
-        .map(std::path::PathBuf::from)
-        .unwrap_or_else(|| std::path::PathBuf::from("."));
-    let base_dir = home.join("Courtyard");
+    scan_storage_in(dm.base_dir(), project_names, archived)
+}
+
+/// `scan_storage` over an explicit Courtyard base directory.
+fn scan_storage_in(
+    base_dir: &Path,
+    project_names: &HashMap<String, String>,
+    archived: &HashSet<String>,
+) -> Result<StorageUsage, String> {
     let projects_dir = base_dir.join("projects");
     let tmp_dir = base_dir.join("tmp");
 
@@ -140,6 +283,7 @@ pub fn scan_storage_usage() -> Result<StorageUsage, String> {
     let mut export_fused_bytes: u64 = 0;
     let mut empty_adapter_count: u32 = 0;
     let mut checkpoint_bytes: u64 = 0;
+    let mut archived_bytes: u64 = 0;
 
     if projects_dir.is_dir() {
         if let Ok(entries) = std::fs::read_dir(&projects_dir) {
@@ -149,7 +293,12 @@ pub fn scan_storage_usage() -> Result<StorageUsage, String> {
                     continue;
                 }
                 let project_id = entry.file_name().to_string_lossy().to_string();
-                let info = scan_project(&p, &project_id);
+                let mut info = scan_project(&p, &project_id);
+                info.project_name = project_names.get(&project_id).cloned();
+                info.archived = archived.contains(&project_id);
+                if info.archived {
+                    archived_bytes += info.total_bytes;
+                }
                 total_bytes += info.total_bytes;
                 export_fused_bytes += info.export_fused_bytes;
                 empty_adapter_count += info.empty_adapter_count;
@@ -169,6 +318,7 @@ pub fn scan_storage_usage() -> Result<StorageUsage, String> {
         empty_adapter_count,
         tmp_bytes,
         checkpoint_bytes,
+        active_bytes: total_bytes - archived_bytes,
         projects,
     })
 }
@@ -258,20 +408,7 @@ pub fn cleanup_project_cache() -> Result<CleanupResult, String> {
                             if !final_adapter.exists() {
                                 continue;
                             }
-                            if let Ok(files) = std::fs::read_dir(&ap) {
-                                for file in files.flatten() {
-                                    let name = file.file_name().to_string_lossy().to_string();
-                                    if name.ends_with("_adapters.safetensors")
-                                        && name != "adapters.safetensors"
-                                        && name.chars().take_while(|c| c.is_ascii_digit()).count() >= 3
-                                    {
-                                        let size = file.metadata().map(|m| m.len()).unwrap_or(0);
-                                        if std::fs::remove_file(file.path()).is_ok() {
-                                            freed_bytes += size;
-                                        }
-                                    }
-                                }
-                            }
+                            freed_bytes += remove_checkpoints(&ap);
                         }
                     }
                 }
@@ -286,3 +423,119 @@ pub fn cleanup_project_cache() -> Result<CleanupResult, String> {
         removed_tmp: tmp_size > 0,
     })
 }
+
+/// Delete the numbered `NNNNNNN_adapters.safetensors` checkpoints in one
+/// adapter dir, returning the bytes freed.
+fn remove_checkpoints(adapter_dir: &Path) -> u64 {
+    let mut freed: u64 = 0;
+    if let Ok(files) = std::fs::read_dir(adapter_dir) {
+        for file in files.flatten() {
+            let name = file.file_name().to_string_lossy().to_string();
+            if name.ends_with("_adapters.safetensors")
+                && name != "adapters.safetensors"
+                && name.chars().take_while(|c| c.is_ascii_digit()).count() >= 3
+            {
+                let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+                if std::fs::remove_file(file.path()).is_ok() {
+                    freed += size;
+                }
+            }
+        }
+    }
+    freed
+}
+
+/// Remove the intermediate checkpoints of a single adapter the user keeps.
+/// Only runs when the final adapters.safetensors exists. Returns freed bytes.
+#[tauri::command]
+pub fn compact_adapter(adapter_path: String) -> Result<u64, String> {
+    let path = Path::new(&adapter_path);
+    if !path.is_dir() {
+        return Err(format!("Adapter not found: {}", adapter_path));
+    }
+    // Same safety check as delete_adapter
+    let path = ProjectDirManager::new().resolve_adapter_dir(path)?;
+    if crate::commands::training::is_adapter_training(&path) {
+        return Err("This adapter is still training".to_string());
+    }
+    if !path.join("adapters.safetensors").exists() {
+        return Err("No final adapters.safetensors yet; checkpoints are kept".to_string());
+    }
+    Ok(remove_checkpoints(&path))
+}
+
+/// Remove a project's adapter folders that hold no weights at all — neither a
+/// final adapters.safetensors nor any checkpoint (interrupted runs).
+/// Adapters of a still-running job are left alone. Returns the count removed.
+#[tauri::command]
+pub fn purge_incomplete_adapters(project_id: String) -> Result<u32, String> {
+    let adapters_dir = ProjectDirManager::new().project_path(&project_id).join("adapters");
+    if !adapters_dir.is_dir() {
+        return Ok(0);
+    }
+    let mut removed: u32 = 0;
+    let entries = std::fs::read_dir(&adapters_dir).map_err(|e| e.to_string())?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() || !path.to_string_lossy().contains("/adapters/") {
+            continue;
+        }
+        if crate::commands::training::is_adapter_training(&path) {
+            continue;
+        }
+        let has_weights = std::fs::read_dir(&path)
+            .map(|rd| {
+                rd.flatten()
+                    .any(|f| f.file_name().to_string_lossy().ends_with("adapters.safetensors"))
+            })
+            .unwrap_or(true);
+        if !has_weights && std::fs::remove_dir_all(&path).is_ok() {
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_bytes(path: &Path, len: usize) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, vec![0u8; len]).unwrap();
+    }
+
+    #[test]
+    fn scan_storage_totals_and_names_projects() {
+        let tmp = tempfile::tempdir().unwrap();
+        let base = tmp.path();
+        let alpha = base.join("projects").join("alpha");
+        write_bytes(&alpha.join("adapters/run1/adapters.safetensors"), 100);
+        write_bytes(&alpha.join("adapters/run1/0000200_adapters.safetensors"), 40);
+        std::fs::create_dir_all(alpha.join("adapters/empty")).unwrap();
+        write_bytes(&alpha.join("export/fused/model.safetensors"), 500);
+        write_bytes(&alpha.join("export/ollama/fused/model.safetensors"), 60);
+        let beta = base.join("projects").join("beta");
+        write_bytes(&beta.join("export/gguf/model.gguf"), 7);
+        write_bytes(&base.join("tmp/scratch.bin"), 3);
+
+        let names = HashMap::from([("alpha".to_string(), "Alpha Project".to_string())]);
+        let archived = HashSet::from(["beta".to_string()]);
+        let usage = scan_storage_in(base, &names, &archived).unwrap();
+
+        assert_eq!(usage.total_bytes, 100 + 40 + 500 + 60 + 7 + 3);
+        assert_eq!(usage.export_fused_bytes, 560);
+        assert_eq!(usage.checkpoint_bytes, 40);
+        assert_eq!(usage.tmp_bytes, 3);
+        assert_eq!(usage.cleanable_bytes, 560 + 40 + 3);
+        assert_eq!(usage.empty_adapter_count, 1);
+        assert_eq!(usage.active_bytes, usage.total_bytes - 7);
+
+        let project = |id: &str| usage.projects.iter().find(|p| p.project_id == id).unwrap();
+        assert_eq!(project("alpha").project_name.as_deref(), Some("Alpha Project"));
+        assert_eq!(project("alpha").total_bytes, 700);
+        assert!(!project("alpha").archived);
+        assert_eq!(project("beta").project_name, None);
+        assert!(project("beta").archived);
+    }
+}
@@ -5,12 +5,68 @@ use uuid::Uuid;
 use tauri::Emitter;
 use crate::fs::ProjectDirManager;
 use crate::python::PythonExecutor;
-use crate::commands::config::{load_config, hf_endpoint_for_source};
+use crate::commands::native_notification::notify_job_finished;
+use crate::commands::config::{configured_hf_endpoint, hf_transfer_env, resolve_model_paths};
 use crate::commands::environment::ensure_mlx_lm_minimum_version;
+use crate::commands::processes::maybe_caffeinate;
 
 static TRAINING_PROCESSES: Lazy<Mutex<HashMap<String, u32>>> =
     Lazy::new(|| Mutex::new(HashMap::new()));
 
+/// Live state of each in-flight training job, kept until its task ends.
+static TRAINING_PROGRESS: Lazy<Mutex<HashMap<String, TrainingProgress>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(serde::Serialize, Clone)]
+pub struct TrainingProgress {
+    pub job_id: String,
+    pub iter: u64,
+    pub total_iters: u64,
+    pub train_loss: Option<f64>,
+    pub val_loss: Option<f64>,
+    /// Unix seconds when the job was started.
+    pub started_at: u64,
+    /// iter / total_iters, 0-100.
+    pub percent: f64,
+    /// Canonical adapter directory the job writes to.
+    #[serde(skip)]
+    adapter_dir: std::path::PathBuf,
+}
+
+/// Fold a parsed progress line into the job's `TrainingProgress`.
+fn record_training_progress(metric: &TrainingMetric) {
+    let Ok(mut map) = TRAINING_PROGRESS.lock() else { return };
+    let Some(progress) = map.get_mut(&metric.job_id) else { return };
+    progress.iter = progress.iter.max(metric.iter);
+    if metric.train_loss.is_some() {
+        progress.train_loss = metric.train_loss;
+    }
+    if metric.val_loss.is_some() {
+        progress.val_loss = metric.val_loss;
+    }
+    if progress.total_iters > 0 {
+        progress.percent = (progress.iter as f64 / progress.total_iters as f64 * 100.0).min(100.0);
+    }
+}
+
+fn forget_training_progress(job_id: &str) {
+    if let Ok(mut map) = TRAINING_PROGRESS.lock() {
+        map.remove(job_id);
+    }
+}
+
+/// Current state of a running training job. Errors once the job has finished
+/// (or never existed), so callers can tell "done" apart from "running".
+#[tauri::command]
+pub fn get_training_progress(job_id: String) -> Result<TrainingProgress, String> {
+    TRAINING_PROGRESS
+        .lock()
+        .map_err(|e| e.to_string())?
+        .get(&job_id)
+        .cloned()
+        .ok_or_else(|| format!("No running training job with id {}", job_id))
+}
+
 /// Returns true when the model identifier indicates a quantized model.
 /// Checks common naming conventions used by mlx-community and other sources.
 fn is_quantized_model(model: &str) -> bool {
@@ -20,6 +76,151 @@ fn is_quantized_model(model: &str) -> bool {
     patterns.iter().any(|p| lower.contains(p))
 }
 
+/// Whether any training job, including its base-model download, is running.
+pub fn any_training_active() -> bool {
+    TRAINING_PROGRESS.lock().map(|map| !map.is_empty()).unwrap_or(false)
+}
+
+/// Terminate every running training job. Returns how many were stopped.
+pub fn stop_all_training() -> u32 {
+    let pids: Vec<u32> = TRAINING_PROCESSES
+        .lock()
+        .map(|mut map| map.drain().map(|(_, pid)| pid).collect())
+        .unwrap_or_default();
+    for pid in &pids {
+        crate::commands::processes::terminate_pid(*pid);
+    }
+    pids.len() as u32
+}
+
+/// Whether a running training job writes to `adapter_dir`.
+pub fn is_adapter_training(adapter_dir: &std::path::Path) -> bool {
+    let Ok(dir) = adapter_dir.canonicalize() else { return false };
+    TRAINING_PROGRESS
+        .lock()
+        .map(|map| map.values().any(|p| p.adapter_dir == dir))
+        .unwrap_or(false)
+}
+
+/// Running training job ids whose adapter dir lives under `project_path`.
+pub fn active_training_jobs_in(project_path: &std::path::Path) -> Vec<String> {
+    let Ok(adapters_dir) = project_path.join("adapters").canonicalize() else {
+        return Vec::new();
+    };
+    TRAINING_PROGRESS
+        .lock()
+        .map(|map| {
+            map.values()
+                .filter(|p| p.adapter_dir.starts_with(&adapters_dir))
+                .map(|p| p.job_id.clone())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Attempts for the base-model download preflight before training gives up.
+const MODEL_DOWNLOAD_ATTEMPTS: u32 = 3;
+
+/// `--fine-tune-type` values mlx_lm.lora accepts.
+const FINE_TUNE_TYPES: &[&str] = &["lora", "dora", "full"];
+
+/// True when `model` is a local directory or already has a snapshot in the HF cache.
+fn base_model_is_local(model: &str) -> bool {
+    if model.starts_with('/') || model.starts_with('~') || model.starts_with('.') {
+        return true;
+    }
+    let snapshots = resolve_model_paths()
+        .huggingface
+        .join(format!("models--{}", model.replace('/', "--")))
+        .join("snapshots");
+    std::fs::read_dir(snapshots)
+        .map(|mut rd| rd.next().is_some())
+        .unwrap_or(false)
+}
+
+/// Download the base model before training, retrying transient failures with
+/// exponential backoff (2s, 4s, ...) and noting each retry in `training-log`.
+/// Returns Err("stopped") if stop_training killed the download.
+async fn ensure_base_model_downloaded(
+    app: &tauri::AppHandle,
+    job_id: &str,
+    python_bin: &std::path::Path,
+    model: &str,
+    hf_endpoint: Option<&str>,
+) -> Result<(), String> {
+    if base_model_is_local(model) {
+        return Ok(());
+    }
+    let log = |line: String| {
+        let _ = app.emit("training-log", serde_json::json!({
+            "job_id": job_id,
+            "line": line,
+        }));
+    };
+    log(format!("[courtyard] Downloading base model {} before training...", model));
+
+    let mut last_error = String::new();
+    for attempt in 1..=MODEL_DOWNLOAD_ATTEMPTS {
+        let child = tokio::process::Command::new(python_bin)
+            .args([
+                "-c",
+                // Only what mlx_lm.load reads, not duplicate PyTorch/GGUF weights.
+                "import sys; from huggingface_hub import snapshot_download; \
+                 snapshot_download(sys.argv[1], allow_patterns=[\"*.json\", \"*.safetensors\", \"*.py\", \
+                 \"tokenizer.model\", \"*.tiktoken\", \"*.txt\", \"*.jsonl\"])",
+                model,
+            ])
+            .envs(hf_endpoint.map(|e| ("HF_ENDPOINT", e)))
+            .envs(hf_transfer_env())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Failed to start model download: {}", e))?;
+        let pid = child.id();
+        if let (Some(pid), Ok(mut map)) = (pid, TRAINING_PROCESSES.lock()) {
+            map.insert(job_id.to_string(), pid);
+        }
+        let output = child.wait_with_output().await.map_err(|e| e.to_string())?;
+
+        // stop_training removes our entry; don't retry a user-cancelled download.
+        let still_ours = TRAINING_PROCESSES
+            .lock()
+            .map(|mut map| {
+                let ours = pid.is_some() && map.get(job_id).copied() == pid;
+                if ours { map.remove(job_id); }
+                ours
+            })
+            .unwrap_or(false);
+        if output.status.success() {
+            return Ok(());
+        }
+        if !still_ours {
+            return Err("stopped".into());
+        }
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        last_error = stderr
+            .lines()
+            .rev()
+            .find(|l| !l.trim().is_empty())
+            .unwrap_or("unknown error")
+            .trim()
+            .to_string();
+        if attempt < MODEL_DOWNLOAD_ATTEMPTS {
+            let delay = 2u64.pow(attempt);
+            log(format!(
+                "[courtyard] Model download attempt {}/{} failed ({}); retrying in {}s...",
+                attempt, MODEL_DOWNLOAD_ATTEMPTS, last_error, delay
+            ));
+            tokio::time::sleep(std::time::Duration::from_secs(delay)).await;
+        }
+    }
+    Err(format!(
+        "Failed to download base model {} after {} attempts: {}",
+        model, MODEL_DOWNLOAD_ATTEMPTS, last_error
+    ))
+}
+
 #[derive(serde::Serialize)]
 pub struct StartTrainingResult {
     pub job_id: String,
@@ -32,8 +233,9 @@ pub async fn start_training(
     project_id: String,
     params: String,
     dataset_path: Option<String>,
+    prevent_sleep: Option<bool>,
+    data_fraction: Option<f64>,
 ) -> Result<StartTrainingResult, String> {
-    let job_id = Uuid::new_v4().to_string();
     let executor = PythonExecutor::default();
 
     if !executor.is_ready() {
@@ -47,6 +249,17 @@ pub async fn start_training(
     let training_params: serde_json::Value =
         serde_json::from_str(&params).map_err(|e| format!("Invalid params: {}", e))?;
 
+    let job_id = Uuid::new_v4().to_string();
+    // A named adapter gets a readable directory; the job id stays a UUID.
+    let adapter_name = training_params["adapter_name"]
+        .as_str()
+        .map(str::trim)
+        .filter(|s| !s.is_empty());
+    let adapter_dir = match adapter_name {
+        Some(name) => adapter_dir_name(name)?,
+        None => None,
+    };
+
     let model = training_params["model"]
         .as_str()
         .ok_or("Missing model parameter")?
@@ -61,8 +274,14 @@ pub async fn start_training(
             )
         }
     };
-    let adapter_path = project_path.join("adapters").join(&job_id);
     let fine_tune_type = training_params["fine_tune_type"].as_str().unwrap_or("lora").to_string();
+    if !FINE_TUNE_TYPES.contains(&fine_tune_type.as_str()) {
+        return Err(format!(
+            "Unknown fine-tune type '{}'. Supported types: {}.",
+            fine_tune_type,
+            FINE_TUNE_TYPES.join(", ")
+        ));
+    }
 
     // Intercept: quantized model + full fine-tuning is unsupported by MLX
     // (MLX raises [QuantizedMatmul::vjp] no gradient wrt the quantized weights)
@@ -93,6 +312,14 @@ pub async fn start_training(
     let steps_per_report = training_params["steps_per_report"].as_u64().unwrap_or(10);
     let val_batches = training_params["val_batches"].as_u64().unwrap_or(25);
     let seed = training_params["seed"].as_u64().unwrap_or(0);
+    let resume_adapter_file = match training_params["resume_adapter_file"].as_str().filter(|s| !s.trim().is_empty()) {
+        Some(file) => {
+            let path = resolve_resume_adapter_file(&project_path, file)?;
+            check_resume_adapter_matches(&path, &model, &fine_tune_type, lora_rank)?;
+            Some(path)
+        }
+        None => None,
+    };
 
     // Verify dataset exists
     let train_path = data_dir.join("train.jsonl");
@@ -111,6 +338,32 @@ pub async fn start_training(
         })?;
     }
 
+    // Quick-test runs train on a seeded sample written to tmp/, leaving the
+    // dataset version itself untouched.
+    let data_fraction = match data_fraction {
+        Some(f) if !(f > 0.0 && f <= 1.0) => {
+            return Err(format!("data_fraction must be in (0, 1], got {}", f));
+        }
+        Some(f) if f < 1.0 => Some(f),
+        _ => None,
+    };
+    let sample_dir = match data_fraction {
+        Some(fraction) => {
+            let path = dir_manager.base_dir().join("tmp").join(format!("train-sample-{}", job_id));
+            std::fs::create_dir_all(&path)
+                .map_err(|e| format!("Failed to create sample dir: {}", e))?;
+            let dir = SampleDir(Some(path.clone()));
+            sample_jsonl(&train_path, &path.join("train.jsonl"), fraction, seed)?;
+            sample_jsonl(&valid_path, &path.join("valid.jsonl"), fraction, seed)?;
+            dir
+        }
+        None => SampleDir(None),
+    };
+    let dataset_dir = data_dir.clone();
+    let data_dir = sample_dir.path().map(std::path::Path::to_path_buf).unwrap_or(data_dir);
+    let train_path = data_dir.join("train.jsonl");
+    let valid_path = data_dir.join("valid.jsonl");
+
     // Auto-clamp batch_size so it never exceeds the smallest dataset split
     let count_lines = |path: &std::path::Path| -> usize {
         std::fs::read_to_string(path)
@@ -126,7 +379,34 @@ pub async fn start_training(
         batch_size
     };
 
-    std::fs::create_dir_all(&adapter_path)
+    // Same idea for step intervals: anything larger than iters never fires,
+    // which for save_every means no checkpoint before the final save.
+    let mut clamp_notes: Vec<String> = Vec::new();
+    let mut clamp_to = |name: &str, value: u64, limit: u64| -> u64 {
+        let limit = limit.max(1);
+        if value > limit {
+            clamp_notes.push(format!(
+                "[courtyard] {} reduced from {} to {} (limited by {})",
+                name,
+                value,
+                limit,
+                if name == "val_batches" { "validation set size" } else { "iters" },
+            ));
+            limit
+        } else {
+            value
+        }
+    };
+    let save_every = clamp_to("save_every", save_every, iters);
+    let steps_per_eval = clamp_to("steps_per_eval", steps_per_eval, iters);
+    let steps_per_report = clamp_to("steps_per_report", steps_per_report, iters);
+    let val_batches = clamp_to(
+        "val_batches",
+        val_batches,
+        (valid_count as u64).div_ceil(batch_size.max(1)),
+    );
+
+    let adapter_path = claim_adapter_dir(&project_path.join("adapters"), adapter_dir.as_deref(), &job_id)
         .map_err(|e| format!("Failed to create adapter directory: {}", e))?;
 
     // Save training metadata for export page to read base model
@@ -152,10 +432,14 @@ pub async fn start_training(
         "steps_per_report": steps_per_report,
         "val_batches": val_batches,
         "seed": seed,
-        "dataset_path": data_dir.to_string_lossy(),
+        "dataset_path": dataset_dir.to_string_lossy(),
+        "dataset_version": dataset_version_name(&dataset_dir),
+        "data_fraction": data_fraction.unwrap_or(1.0),
         "train_samples": train_count,
         "valid_samples": valid_count,
         "created_at": chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+        "resumed_from": resume_adapter_file.as_ref().map(|p| p.to_string_lossy().to_string()),
+        "adapter_name": adapter_name,
     });
     let _ = std::fs::write(
         adapter_path.join("training_meta.json"),
@@ -164,22 +448,26 @@ pub async fn start_training(
 
     // Generate a YAML config for lora/dora parameters (--lora-rank is NOT a valid CLI arg)
     let config_path = adapter_path.join("lora_config.yaml");
-    let config_content = if fine_tune_type == "full" {
+    let config_content = match fine_tune_type.as_str() {
         // Full fine-tuning does not use lora_parameters
-        String::new()
-    } else {
-        let base = format!(
-            "lora_parameters:\n  rank: {}\n  alpha: {}\n  dropout: {}\n  scale: {}\n",
-            lora_rank,
-            lora_rank * 2,
-            lora_dropout,
-            lora_scale,
-        );
-        if use_rslora {
-            format!("{}  use_rslora: true\n", base)
-        } else {
-            base
+        "full" => String::new(),
+        // DoRA layers are built from the same lora_parameters; mlx-lm picks
+        // the layer type from --fine-tune-type.
+        "lora" | "dora" => {
+            let base = format!(
+                "lora_parameters:\n  rank: {}\n  alpha: {}\n  dropout: {}\n  scale: {}\n",
+                lora_rank,
+                lora_rank * 2,
+                lora_dropout,
+                lora_scale,
+            );
+            if use_rslora {
+                format!("{}  use_rslora: true\n", base)
+            } else {
+                base
+            }
         }
+        other => return Err(format!("Unknown fine-tune type: {}", other)),
     };
     std::fs::write(&config_path, &config_content)
         .map_err(|e| format!("Failed to write lora config: {}", e))?;
@@ -190,10 +478,40 @@ pub async fn start_training(
     let adapter_path_str_spawn = adapter_path_str.clone();
 
     // Read configured HF download source for HF_ENDPOINT env var
-    let app_config = load_config();
-    let hf_endpoint = hf_endpoint_for_source(&app_config.hf_source);
+    let hf_endpoint = configured_hf_endpoint();
+
+    if let Ok(mut map) = TRAINING_PROGRESS.lock() {
+        map.insert(job_id.clone(), TrainingProgress {
+            job_id: job_id.clone(),
+            iter: 0,
+            total_iters: iters,
+            train_loss: None,
+            val_loss: None,
+            started_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            percent: 0.0,
+            adapter_dir: adapter_path.canonicalize().unwrap_or_else(|_| adapter_path.clone()),
+        });
+    }
 
     tokio::spawn(async move {
+        // Dropped whenever the task ends, removing the sample.
+        let _sample_dir = sample_dir;
+        if let Err(e) = ensure_base_model_downloaded(
+            &app, &job_id_clone, &python_bin, &model, hf_endpoint.as_deref(),
+        ).await {
+            if e != "stopped" {
+                let _ = app.emit("training-error", serde_json::json!({
+                    "job_id": job_id_clone,
+                    "error": e,
+                }));
+            }
+            forget_training_progress(&job_id_clone);
+            return;
+        }
+
         // Build args: python -m mlx_lm lora --train ...
         let mut py_args = vec![
             "-m".to_string(),
@@ -246,22 +564,21 @@ pub async fn start_training(
             py_args.push("--grad-accumulation-steps".to_string());
             py_args.push(grad_accumulation_steps.to_string());
         }
+        if let Some(ref file) = resume_adapter_file {
+            py_args.push("--resume-adapter-file".to_string());
+            py_args.push(file.to_string_lossy().to_string());
+        }
 
         // Wrap with caffeinate -i to prevent idle sleep during training
-        let mut caffeinate_args: Vec<String> = vec![
-            "-i".to_string(),
-            python_bin.to_string_lossy().to_string(),
-        ];
-        caffeinate_args.extend(py_args);
-
-        let mut cmd = tokio::process::Command::new("caffeinate");
-        cmd.args(&caffeinate_args)
+        let mut cmd = maybe_caffeinate(&python_bin, prevent_sleep);
+        cmd.args(&py_args)
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::piped());
         cmd.env("AGX_RELAX_CDM_CTXSTORE_TIMEOUT", "1");
         if let Some(ref endpoint) = hf_endpoint {
             cmd.env("HF_ENDPOINT", endpoint);
         }
+        cmd.envs(hf_transfer_env());
         let result = cmd.spawn();
 
         match result {
@@ -271,6 +588,12 @@ pub async fn start_training(
                         map.insert(job_id_clone.clone(), pid);
                     }
                 }
+                for note in &clamp_notes {
+                    let _ = app.emit("training-log", serde_json::json!({
+                        "job_id": job_id_clone,
+                        "line": note,
+                    }));
+                }
 
                 use tokio::io::{AsyncBufReadExt, BufReader};
 
@@ -285,9 +608,12 @@ pub async fn start_training(
                 let collected: std::sync::Arc<std::sync::Mutex<Vec<String>>> =
                     std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
 
+                let overfit = std::sync::Arc::new(std::sync::Mutex::new(OverfitTracker::new(save_every)));
+
                 let app_out = app.clone();
                 let jid_out = job_id_clone.clone();
                 let col_out = std::sync::Arc::clone(&collected);
+                let overfit_out = std::sync::Arc::clone(&overfit);
                 let stdout_task = tokio::spawn(async move {
                     if let Some(out) = stdout {
                         let mut lines = BufReader::new(out).lines();
@@ -296,6 +622,25 @@ pub async fn start_training(
                                 "job_id": jid_out,
                                 "line": &line,
                             }));
+                            let metric = parse_training_metric(&line, &jid_out);
+                            if let Some(ref metric) = metric {
+                                record_training_progress(metric);
+                                let _ = app_out.emit("training-metric", metric);
+                            }
+                            if let Some(gb) = parse_peak_mem_gb(&line) {
+                                let _ = app_out.emit("training-metrics", serde_json::json!({
+                                    "job_id": jid_out,
+                                    "iter": metric.as_ref().map(|m| m.iter),
+                                    "peak_memory_gb": gb,
+                                }));
+                            }
+                            let warning = metric
+                                .as_ref()
+                                .and_then(|m| overfit_out.lock().ok().and_then(|mut t| t.observe(m)));
+                            if let Some(mut warning) = warning {
+                                warning["job_id"] = serde_json::json!(jid_out);
+                                let _ = app_out.emit("training-overfit-warning", warning);
+                            }
                             if let Ok(mut v) = col_out.lock() { v.push(line); }
                         }
                     }
@@ -304,6 +649,7 @@ pub async fn start_training(
                 let app_err = app.clone();
                 let jid_err = job_id_clone.clone();
                 let col_err = std::sync::Arc::clone(&collected);
+                let overfit_err = std::sync::Arc::clone(&overfit);
                 let stderr_task = tokio::spawn(async move {
                     if let Some(err) = stderr {
                         let mut lines = BufReader::new(err).lines();
@@ -312,12 +658,40 @@ pub async fn start_training(
                                 "job_id": jid_err,
                                 "line": &line,
                             }));
+                            let metric = parse_training_metric(&line, &jid_err);
+                            if let Some(ref metric) = metric {
+                                record_training_progress(metric);
+                                let _ = app_err.emit("training-metric", metric);
+                            }
+                            if let Some(gb) = parse_peak_mem_gb(&line) {
+                                let _ = app_err.emit("training-metrics", serde_json::json!({
+                                    "job_id": jid_err,
+                                    "iter": metric.as_ref().map(|m| m.iter),
+                                    "peak_memory_gb": gb,
+                                }));
+                            }
+                            let warning = metric
+                                .as_ref()
+                                .and_then(|m| overfit_err.lock().ok().and_then(|mut t| t.observe(m)));
+                            if let Some(mut warning) = warning {
+                                warning["job_id"] = serde_json::json!(jid_err);
+                                let _ = app_err.emit("training-overfit-warning", warning);
+                            }
                             if let Ok(mut v) = col_err.lock() { v.push(line); }
                         }
                     }
                 });
 
+                let sampling = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true));
+                let memory_task = tokio::spawn(sample_training_memory(
+                    app.clone(),
+                    job_id_clone.clone(),
+                    std::sync::Arc::clone(&sampling),
+                ));
+
                 let _ = tokio::join!(stdout_task, stderr_task);
+                sampling.store(false, std::sync::atomic::Ordering::SeqCst);
+                memory_task.abort();
 
                 let completed_at_ms: f64 = std::time::SystemTime::now()
                     .duration_since(std::time::UNIX_EPOCH)
@@ -328,26 +702,20 @@ pub async fn start_training(
                 let mut train_series: Vec<serde_json::Value> = Vec::new();
                 let mut val_series: Vec<serde_json::Value> = Vec::new();
                 let mut last_iter: u64 = 0;
+                let mut peak_memory_gb: Option<f64> = None;
                 if let Ok(lines) = collected.lock() {
                     for line in lines.iter() {
-                        if !line.starts_with("Iter ") { continue; }
-                        let after_iter = &line[5..];
-                        let iter_end = after_iter.find(|c: char| !c.is_ascii_digit()).unwrap_or(after_iter.len());
-                        let iter: u64 = match after_iter[..iter_end].parse() { Ok(n) => n, Err(_) => continue };
+                        let Some(metric) = parse_training_metric(line, &job_id_clone) else { continue };
+                        let iter = metric.iter;
                         last_iter = last_iter.max(iter);
-                        if let Some(rest) = line.split("Train loss ").nth(1) {
-                            let s = rest.split(',').next().unwrap_or("").trim();
-                            if let Ok(loss) = s.parse::<f64>() {
-                                train_series.push(serde_json::json!([iter as f64, loss]));
-                            }
+                        if let Some(gb) = parse_peak_mem_gb(line) {
+                            peak_memory_gb = Some(peak_memory_gb.map_or(gb, |p: f64| p.max(gb)));
                         }
-                        if let Some(rest) = line.split("Val loss ").nth(1) {
-                            let s = rest.split(',').next()
-                                .and_then(|p| p.split_whitespace().next())
-                                .unwrap_or("");
-                            if let Ok(loss) = s.parse::<f64>() {
-                                val_series.push(serde_json::json!([iter as f64, loss]));
-                            }
+                        if let Some(loss) = metric.train_loss {
+                            train_series.push(serde_json::json!([iter as f64, loss]));
+                        }
+                        if let Some(loss) = metric.val_loss {
+                            val_series.push(serde_json::json!([iter as f64, loss]));
                         }
                     }
                 }
@@ -375,15 +743,32 @@ pub async fn start_training(
                             "total_iters_completed": last_iter,
                             "train_loss_series": train_series,
                             "val_loss_series": val_series,
+                            "peak_memory_gb": peak_memory_gb,
                         });
                         let _ = std::fs::write(
                             std::path::Path::new(&adapter_path_str_spawn).join("training_result.json"),
                             serde_json::to_string(&result_json).unwrap_or_default(),
                         );
+                        if let Some(gb) = peak_memory_gb {
+                            let meta_path = std::path::Path::new(&adapter_path_str_spawn).join("training_meta.json");
+                            if let Some(mut meta) = std::fs::read_to_string(&meta_path).ok()
+                                .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok())
+                            {
+                                meta["peak_memory_gb"] = serde_json::json!(gb);
+                                let _ = std::fs::write(&meta_path, serde_json::to_string_pretty(&meta).unwrap_or_default());
+                            }
+                        }
                         let _ = app.emit("training-complete", serde_json::json!({
                             "job_id": job_id_clone,
                             "success": success,
+                            "peak_memory_gb": peak_memory_gb,
                         }));
+                        let outcome = match (success, final_train) {
+                            (true, Some(loss)) => format!("training completed (final loss {:.3})", loss),
+                            (true, None) => "training completed".to_string(),
+                            (false, _) => "training stopped before completion".to_string(),
+                        };
+                        notify_job_finished(&app, &project_id, "Training finished", &outcome).await;
                     }
                     Err(e) => {
                         let _ = app.emit("training-error", serde_json::json!({
@@ -404,6 +789,7 @@ pub async fn start_training(
                 }));
             }
         }
+        forget_training_progress(&job_id_clone);
     });
 
     Ok(StartTrainingResult {
@@ -412,6 +798,385 @@ pub async fn start_training(
     })
 }
 
+/// Version id of a project dataset dir: the timestamp dir name under
+/// `dataset/`, or "legacy" for the flat layout. None for outside folders.
+fn dataset_version_name(dataset_dir: &std::path::Path) -> Option<String> {
+    let name = dataset_dir.file_name()?.to_string_lossy().to_string();
+    if name == "dataset" {
+        return Some("legacy".to_string());
+    }
+    let parent = dataset_dir.parent()?.file_name()?;
+    (parent == "dataset").then_some(name)
+}
+
+/// Quick-test sample dir under tmp/, removed when dropped: on any error
+/// return from start_training, or when the training task ends.
+struct SampleDir(Option<std::path::PathBuf>);
+
+impl SampleDir {
+    fn path(&self) -> Option<&std::path::Path> {
+        self.0.as_deref()
+    }
+}
+
+impl Drop for SampleDir {
+    fn drop(&mut self) {
+        if let Some(dir) = self.0.take() {
+            let _ = std::fs::remove_dir_all(dir);
+        }
+    }
+}
+
+/// Write a seeded sample of `fraction` of the non-empty lines of `src` to
+/// `dst`, keeping their original order. At least one line is kept.
+fn sample_jsonl(
+    src: &std::path::Path,
+    dst: &std::path::Path,
+    fraction: f64,
+    seed: u64,
+) -> Result<usize, String> {
+    use std::hash::{Hash, Hasher};
+
+    let content = std::fs::read_to_string(src)
+        .map_err(|e| format!("Failed to read {}: {}", src.display(), e))?;
+    let lines: Vec<&str> = content.lines().filter(|l| !l.trim().is_empty()).collect();
+    let keep = ((lines.len() as f64 * fraction).ceil() as usize).clamp(1, lines.len().max(1));
+    // Rank lines by a seeded hash of their index; the lowest `keep` are sampled.
+    let mut ranked: Vec<(u64, usize)> = (0..lines.len())
+        .map(|i| {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            (seed, i).hash(&mut hasher);
+            (hasher.finish(), i)
+        })
+        .collect();
+    ranked.sort_unstable();
+    let mut picked: Vec<usize> = ranked.into_iter().take(keep).map(|(_, i)| i).collect();
+    picked.sort_unstable();
+    let mut out = picked.iter().map(|&i| lines[i]).collect::<Vec<_>>().join("\n");
+    out.push('\n');
+    std::fs::write(dst, out).map_err(|e| format!("Failed to write {}: {}", dst.display(), e))?;
+    Ok(picked.len())
+}
+
+/// Seconds between `training-memory` samples.
+const MEMORY_SAMPLE_INTERVAL_SECS: u64 = 5;
+
+/// Emit `training-memory` events (used/available GB and pressure level) until
+/// `running` is cleared.
+async fn sample_training_memory(
+    app: tauri::AppHandle,
+    job_id: String,
+    running: std::sync::Arc<std::sync::atomic::AtomicBool>,
+) {
+    let total_gb = crate::commands::environment::get_system_memory_gb();
+    if total_gb <= 0.0 {
+        return;
+    }
+    while running.load(std::sync::atomic::Ordering::SeqCst) {
+        if let Some(available_gb) = available_memory_gb().await {
+            let pressure = match memory_pressure_level().await {
+                Some(level) => level,
+                // No kernel level: judge by the free fraction instead.
+                None if available_gb / total_gb < 0.1 => "critical",
+                None if available_gb / total_gb < 0.2 => "warn",
+                None => "normal",
+            };
+            let _ = app.emit("training-memory", serde_json::json!({
+                "job_id": job_id,
+                "total_gb": total_gb,
+                "used_gb": (total_gb - available_gb).max(0.0),
+                "available_gb": available_gb,
+                "pressure": pressure,
+            }));
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(MEMORY_SAMPLE_INTERVAL_SECS)).await;
+    }
+}
+
+/// Free + inactive + speculative + purgeable pages from `vm_stat`, in GB.
+async fn available_memory_gb() -> Option<f64> {
+    let output = tokio::process::Command::new("vm_stat").output().await.ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    let page_size: f64 = text
+        .lines()
+        .next()?
+        .split("page size of ")
+        .nth(1)?
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()?;
+    let pages: f64 = text
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            let counted = matches!(
+                key.trim(),
+                "Pages free" | "Pages inactive" | "Pages speculative" | "Pages purgeable"
+            );
+            if !counted {
+                return None;
+            }
+            value.trim().trim_end_matches('.').parse::<f64>().ok()
+        })
+        .sum();
+    Some(pages * page_size / 1_073_741_824.0)
+}
+
+/// macOS kernel memory pressure (1 normal, 2 warn, 4 critical).
+async fn memory_pressure_level() -> Option<&'static str> {
+    let output = tokio::process::Command::new("sysctl")
+        .args(["-n", "kern.memorystatus_vm_pressure_level"])
+        .output()
+        .await
+        .ok()?;
+    match String::from_utf8_lossy(&output.stdout).trim() {
+        "1" => Some("normal"),
+        "2" => Some("warn"),
+        "4" => Some("critical"),
+        _ => None,
+    }
+}
+
+/// Longest adapter directory name derived from a user-supplied name.
+const MAX_ADAPTER_NAME_CHARS: usize = 64;
+
+/// Create a fresh adapter directory under `adapters_dir`: `name` if given and
+/// still free, otherwise `job_id`. `create_dir` fails on an existing entry, so
+/// two jobs never end up sharing a directory.
+fn claim_adapter_dir(
+    adapters_dir: &std::path::Path,
+    name: Option<&str>,
+    job_id: &str,
+) -> std::io::Result<std::path::PathBuf> {
+    std::fs::create_dir_all(adapters_dir)?;
+    if let Some(name) = name {
+        let path = adapters_dir.join(name);
+        match std::fs::create_dir(&path) {
+            Ok(()) => return Ok(path),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+            Err(e) => return Err(e),
+        }
+    }
+    let path = adapters_dir.join(job_id);
+    std::fs::create_dir(&path)?;
+    Ok(path)
+}
+
+/// Directory name for a friendly adapter name: letters, digits, '-' and '_'
+/// only, with whitespace turned into '-'. Errors on path-like names; None when
+/// nothing usable is left.
+fn adapter_dir_name(name: &str) -> Result<Option<String>, String> {
+    if name.contains(['/', '\\']) || name.contains("..") {
+        return Err(format!("Adapter name '{}' must not contain path separators or '..'.", name));
+    }
+    let dir: String = name
+        .chars()
+        .filter_map(|c| match c {
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            c if c.is_whitespace() => Some('-'),
+            _ => None,
+        })
+        .take(MAX_ADAPTER_NAME_CHARS)
+        .collect();
+    let dir = dir.trim_matches('-').to_string();
+    Ok((!dir.is_empty()).then_some(dir))
+}
+
+/// Check that `file` is an `*adapters.safetensors` inside this project's
+/// adapters/ directory and return its canonical path.
+fn resolve_resume_adapter_file(project_path: &std::path::Path, file: &str) -> Result<std::path::PathBuf, String> {
+    let path = std::path::Path::new(file)
+        .canonicalize()
+        .map_err(|_| format!("Resume adapter file not found: {}", file))?;
+    let is_adapter_weights = path.is_file()
+        && path
+            .file_name()
+            .map(|n| n.to_string_lossy().ends_with("adapters.safetensors"))
+            .unwrap_or(false);
+    if !is_adapter_weights {
+        return Err(format!("Not an adapter weights file (*_adapters.safetensors): {}", file));
+    }
+    let adapters_dir = project_path
+        .join("adapters")
+        .canonicalize()
+        .map_err(|_| "This project has no adapters to resume from.".to_string())?;
+    if !path.starts_with(&adapters_dir) {
+        return Err("The resume adapter file must be inside this project's adapters folder.".into());
+    }
+    Ok(path)
+}
+
+/// Reject resuming from an adapter whose recorded base model, fine-tune type
+/// or LoRA rank differs from this run's; mlx-lm would fail to load the weights
+/// or silently train on a different model. Values the adapter doesn't record
+/// are not checked.
+fn check_resume_adapter_matches(
+    adapter_file: &std::path::Path,
+    model: &str,
+    fine_tune_type: &str,
+    lora_rank: u64,
+) -> Result<(), String> {
+    let Some(dir) = adapter_file.parent() else { return Ok(()) };
+    let meta = read_json_file(&dir.join("training_meta.json")).unwrap_or_default();
+    let config = read_json_file(&dir.join("adapter_config.json")).unwrap_or_default();
+    if let Some(base) = read_adapter_base_model(dir) {
+        if normalize_model_identity(&base) != normalize_model_identity(model) {
+            return Err(format!("Cannot resume: the adapter was trained on {}, but {} is selected.", base, model));
+        }
+    }
+    let resumed_type = meta["fine_tune_type"].as_str().or_else(|| config["fine_tune_type"].as_str());
+    if let Some(resumed_type) = resumed_type {
+        if resumed_type != fine_tune_type {
+            return Err(format!(
+                "Cannot resume: the adapter was trained with {} fine-tuning, but {} is selected.",
+                resumed_type, fine_tune_type
+            ));
+        }
+    }
+    if fine_tune_type != "full" {
+        let rank = meta["lora_rank"].as_u64().or_else(|| config["lora_parameters"]["rank"].as_u64());
+        if let Some(rank) = rank.filter(|&r| r != lora_rank) {
+            return Err(format!("Cannot resume: the adapter has LoRA rank {}, but rank {} is selected.", rank, lora_rank));
+        }
+    }
+    Ok(())
+}
+
+/// Typed fields of one mlx_lm progress line, sent as `training-metric`.
+#[derive(serde::Serialize, Clone, Debug)]
+struct TrainingMetric {
+    job_id: String,
+    iter: u64,
+    train_loss: Option<f64>,
+    val_loss: Option<f64>,
+    it_per_sec: Option<f64>,
+    tokens_per_sec: Option<f64>,
+    trained_tokens: Option<u64>,
+}
+
+/// First number after any of `labels` in an already lowercased line. Tolerates
+/// ':' / '=' separators and extra spaces, which vary across mlx_lm versions.
+fn labeled_number(lower: &str, labels: &[&str]) -> Option<f64> {
+    labels.iter().find_map(|label| {
+        lower.match_indices(label).find_map(|(i, _)| {
+            let rest = lower[i + label.len()..].trim_start_matches([' ', ':', '=']);
+            let end = rest
+                .find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | 'e' | '-' | '+')))
+                .unwrap_or(rest.len());
+            rest[..end].trim_end_matches('.').parse().ok()
+        })
+    })
+}
+
+/// Parse an mlx_lm progress line ("Iter 100: Train loss 1.234, It/sec 2.1,
+/// Tokens/sec 540, Trained Tokens 12800, ..." or "Iter 200: Val loss 1.4, ...").
+/// None for lines without an iteration number or any known metric.
+fn parse_training_metric(line: &str, job_id: &str) -> Option<TrainingMetric> {
+    let lower = line.to_ascii_lowercase();
+    let iter = labeled_number(&lower, &["iter"])?;
+    let metric = TrainingMetric {
+        job_id: job_id.to_string(),
+        iter: iter as u64,
+        train_loss: labeled_number(&lower, &["train loss", "train_loss"]),
+        val_loss: labeled_number(&lower, &["val loss", "val_loss"]),
+        it_per_sec: labeled_number(&lower, &["it/sec", "it/s", "iters/sec"]),
+        tokens_per_sec: labeled_number(&lower, &["tokens/sec", "tok/sec", "tokens/s"]),
+        trained_tokens: labeled_number(&lower, &["trained tokens", "trained_tokens"]).map(|n| n as u64),
+    };
+    let has_metric = metric.train_loss.is_some()
+        || metric.val_loss.is_some()
+        || metric.it_per_sec.is_some()
+        || metric.tokens_per_sec.is_some()
+        || metric.trained_tokens.is_some();
+    (iter >= 0.0 && has_metric).then_some(metric)
+}
+
+/// Consecutive evals with rising val loss (and falling train loss) that
+/// count as overfitting.
+const OVERFIT_PATIENCE_EVALS: u32 = 3;
+
+/// Watches the live loss lines for val loss climbing while train loss keeps
+/// falling, and reports it once per run.
+struct OverfitTracker {
+    save_every: u64,
+    latest_train: Option<f64>,
+    /// (val loss, train loss at that eval) of the previous eval.
+    prev_eval: Option<(f64, Option<f64>)>,
+    best_val: Option<(u64, f64)>,
+    rising_evals: u32,
+    warned: bool,
+}
+
+impl OverfitTracker {
+    fn new(save_every: u64) -> Self {
+        Self {
+            save_every,
+            latest_train: None,
+            prev_eval: None,
+            best_val: None,
+            rising_evals: 0,
+            warned: false,
+        }
+    }
+
+    /// Feed one parsed progress line; returns the warning payload the first
+    /// time the overfitting pattern is seen.
+    fn observe(&mut self, metric: &TrainingMetric) -> Option<serde_json::Value> {
+        if let Some(loss) = metric.train_loss {
+            self.latest_train = Some(loss);
+        }
+        let val = metric.val_loss?;
+        let iter = metric.iter;
+
+        if self.best_val.is_none_or(|(_, best)| val < best) {
+            self.best_val = Some((iter, val));
+        }
+        let train_falling = |prev_train: Option<f64>| match (prev_train, self.latest_train) {
+            (Some(prev), Some(now)) => now < prev,
+            _ => false,
+        };
+        self.rising_evals = match self.prev_eval {
+            Some((prev_val, prev_train)) if val > prev_val && train_falling(prev_train) => self.rising_evals + 1,
+            _ => 0,
+        };
+        self.prev_eval = Some((val, self.latest_train));
+
+        if self.warned || self.rising_evals < OVERFIT_PATIENCE_EVALS {
+            return None;
+        }
+        self.warned = true;
+        let (best_iter, best_loss) = self.best_val?;
+        // The newest checkpoint saved at or before the best eval.
+        let checkpoint_iter = (self.save_every > 0)
+            .then(|| best_iter / self.save_every * self.save_every)
+            .filter(|&i| i > 0);
+        Some(serde_json::json!({
+            "iter": iter,
+            "val_loss": val,
+            "best_val_iter": best_iter,
+            "best_val_loss": best_loss,
+            "suggested_checkpoint_iter": checkpoint_iter,
+            "suggested_checkpoint": checkpoint_iter.map(|i| format!("{:07}_adapters.safetensors", i)),
+            "message": format!(
+                "Validation loss has risen for {} evals while train loss keeps falling — the model may be overfitting. Consider stopping and using the checkpoint nearest iteration {}.",
+                self.rising_evals, best_iter
+            ),
+        }))
+    }
+}
+
+/// Peak memory in GB from an mlx_lm report line ("..., Peak mem 5.432 GB").
+fn parse_peak_mem_gb(line: &str) -> Option<f64> {
+    let rest = line.split("Peak mem ").nth(1)?;
+    let mut parts = rest.split_whitespace();
+    let value: f64 = parts.next()?.trim_end_matches(',').parse().ok()?;
+    match parts.next().map(|u| u.trim_end_matches(',')) {
+        Some("MB") => Some(value / 1024.0),
+        _ => Some(value),
+    }
+}
+
 #[tauri::command]
 pub async fn stop_training(job_id: String) -> Result<(), String> {
     let pid = {
@@ -440,11 +1205,7 @@ pub fn open_project_folder(project_id: String) -> Result<(), String> {
     if !project_path.exists() {
         return Err("Project directory does not exist".into());
     }
-    std::process::Command::new("open")
-        .arg(&project_path)
-        .spawn()
-        .map_err(|e| format!("Failed to open folder: {}", e))?;
-    Ok(())
+    crate::fs::open_in_file_manager(&project_path)
 }
 
 #[derive(serde::Serialize)]
@@ -454,15 +1215,28 @@ pub struct AdapterInfo {
     pub created: String,
     pub has_weights: bool,
     pub base_model: String,
+    pub lora_rank: Option<u64>,
+    pub lora_layers: Option<u64>,
+    pub fine_tune_type: Option<String>,
+    pub iters: Option<u64>,
+    /// Formats this adapter has been exported to ("ollama", "gguf", "mlx").
+    pub exported: Vec<String>,
+    /// Adapter weights file this run continued from, if it was resumed.
+    pub resumed_from: Option<String>,
+    /// Name the user gave the run, as typed.
+    pub display_name: Option<String>,
 }
 
+/// `only_complete` drops adapters without weights (e.g. interrupted runs);
+/// by default everything is returned so incomplete ones can be cleaned up.
 #[tauri::command]
-pub fn list_adapters(project_id: String) -> Result<Vec<AdapterInfo>, String> {
+pub fn list_adapters(project_id: String, only_complete: Option<bool>) -> Result<Vec<AdapterInfo>, String> {
     let dir_manager = ProjectDirManager::new();
     let adapters_dir = dir_manager.project_path(&project_id).join("adapters");
     if !adapters_dir.exists() {
         return Ok(vec![]);
     }
+    let mut exported = crate::commands::export::exported_formats(&project_id);
     let mut adapters: Vec<AdapterInfo> = std::fs::read_dir(&adapters_dir)
         .map_err(|e| e.to_string())?
         .filter_map(|entry| {
@@ -486,60 +1260,286 @@ pub fn list_adapters(project_id: String) -> Result<Vec<AdapterInfo>, String> {
                     local.format("%Y-%m-%d %H:%M").to_string()
                 })
                 .unwrap_or_default();
-            // Read base_model from training_meta.json, fallback to adapter_config.json
-            let base_model = std::fs::read_to_string(path.join("training_meta.json"))
-                .ok()
-                .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok())
-                .and_then(|v| v["base_model"].as_str().map(|s| s.to_string()))
-                .or_else(|| {
-                    // Fallback: read "model" field from adapter_config.json (created by mlx-lm)
-                    std::fs::read_to_string(path.join("adapter_config.json"))
-                        .ok()
-                        .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok())
-                        .and_then(|v| v["model"].as_str().map(|s| s.to_string()))
-                })
-                .unwrap_or_default();
+            let base_model = read_adapter_base_model(&path).unwrap_or_default();
+            // training_meta.json is ours; adapter_config.json is written by mlx-lm
+            // and covers adapters trained outside the app.
+            let meta = read_json_file(&path.join("training_meta.json")).unwrap_or_default();
+            let config = read_json_file(&path.join("adapter_config.json")).unwrap_or_default();
+            let fine_tune_type = meta["fine_tune_type"]
+                .as_str()
+                .or_else(|| config["fine_tune_type"].as_str())
+                .map(|s| s.to_string());
+            let is_full = fine_tune_type.as_deref() == Some("full");
+            let name = entry.file_name().to_string_lossy().to_string();
             Some(AdapterInfo {
-                name: entry.file_name().to_string_lossy().to_string(),
+                exported: exported.remove(&name).unwrap_or_default(),
+                name,
                 path: path.to_string_lossy().to_string(),
                 created,
                 has_weights,
                 base_model,
+                lora_rank: if is_full {
+                    None
+                } else {
+                    meta["lora_rank"]
+                        .as_u64()
+                        .or_else(|| config["lora_parameters"]["rank"].as_u64())
+                },
+                lora_layers: meta["lora_layers"]
+                    .as_u64()
+                    .or_else(|| config["num_layers"].as_u64()),
+                fine_tune_type,
+                iters: meta["iters"].as_u64().or_else(|| config["iters"].as_u64()),
+                resumed_from: meta["resumed_from"].as_str().map(|s| s.to_string()),
+                display_name: meta["adapter_name"].as_str().map(|s| s.to_string()),
             })
         })
+        .filter(|a: &AdapterInfo| !only_complete.unwrap_or(false) || a.has_weights)
         .collect();
     adapters.sort_by(|a, b| b.created.cmp(&a.created));
     Ok(adapters)
 }
 
+/// Saved training_meta.json for an adapter, so the live-training view can
+/// restore its hyperparameters after a reload.
+#[tauri::command]
+pub fn get_training_meta(adapter_path: String) -> Result<serde_json::Value, String> {
+    let meta_path = std::path::Path::new(&adapter_path).join("training_meta.json");
+    let content = std::fs::read_to_string(&meta_path)
+        .map_err(|e| format!("Failed to read {}: {}", meta_path.display(), e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Invalid training_meta.json: {}", e))
+}
+
+/// Read the base model an adapter was trained on.
+/// Prefers training_meta.json, falls back to the "model" field mlx-lm writes
+/// into adapter_config.json.
+pub(crate) fn read_adapter_base_model(adapter_dir: &std::path::Path) -> Option<String> {
+    std::fs::read_to_string(adapter_dir.join("training_meta.json"))
+        .ok()
+        .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok())
+        .and_then(|v| v["base_model"].as_str().map(|s| s.to_string()))
+        .or_else(|| {
+            std::fs::read_to_string(adapter_dir.join("adapter_config.json"))
+                .ok()
+                .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok())
+                .and_then(|v| v["model"].as_str().map(|s| s.to_string()))
+        })
+        .filter(|s| !s.trim().is_empty())
+}
+
+/// Base model recorded for an adapter, so export and inference can pre-fill
+/// `model` instead of relying on the user to pick the matching base.
+#[tauri::command]
+pub fn resolve_base_model(adapter_path: String) -> Result<Option<String>, String> {
+    let path = std::path::Path::new(&adapter_path);
+    if !path.is_dir() {
+        return Err(format!("Adapter not found: {}", adapter_path));
+    }
+    Ok(read_adapter_base_model(path))
+}
+
+/// Reduce a model id or local path to a comparable identity.
+/// "mlx-community/Qwen2-7B-Instruct-4bit", ".../models--Qwen--Qwen2-7B-Instruct/snapshots/abc"
+/// and "Qwen/Qwen2-7B-Instruct" all normalize to "qwen2-7b-instruct", so quantized
+/// and full-precision variants of the same base are treated as compatible.
+fn normalize_model_identity(model: &str) -> String {
+    let trimmed = model.trim().trim_end_matches('/');
+    let name = trimmed
+        .split('/')
+        .find_map(|part| part.strip_prefix("models--"))
+        .map(|repo| repo.rsplit("--").next().unwrap_or(repo).to_string())
+        .unwrap_or_else(|| trimmed.rsplit('/').next().unwrap_or(trimmed).to_string());
+    let mut lower = name.to_lowercase();
+    let suffixes = ["-mlx", "-4bit", "-8bit", "-6bit", "-3bit", "-bf16", "-fp16", "-f16", "-quantized"];
+    loop {
+        let before = lower.len();
+        for suffix in suffixes {
+            if let Some(stripped) = lower.strip_suffix(suffix) {
+                lower = stripped.to_string();
+            }
+        }
+        if lower.len() == before {
+            break;
+        }
+    }
+    lower
+}
+
 #[derive(serde::Serialize)]
+pub struct CompatibilityResult {
+    pub compatible: bool,
+    pub adapter_base_model: Option<String>,
+    pub selected_model: String,
+    pub reason: Option<String>,
+}
+
+/// Compare the base model recorded for an adapter with the model the user selected
+/// for export/inference, so a mismatch can be flagged before launching a job.
+#[tauri::command]
+pub fn check_adapter_compatibility(adapter_path: String, model: String) -> Result<CompatibilityResult, String> {
+    let path = std::path::Path::new(&adapter_path);
+    if !path.is_dir() {
+        return Err(format!("Adapter not found: {}", adapter_path));
+    }
+
+    let Some(base_model) = read_adapter_base_model(path) else {
+        // Nothing recorded (e.g. adapter copied in by hand) — don't block, just say so.
+        return Ok(CompatibilityResult {
+            compatible: true,
+            adapter_base_model: None,
+            selected_model: model,
+            reason: Some("Adapter has no recorded base model; compatibility cannot be verified.".to_string()),
+        });
+    };
+
+    let compatible = normalize_model_identity(&base_model) == normalize_model_identity(&model);
+    let reason = if compatible {
+        None
+    } else {
+        Some(format!(
+            "Adapter was trained on {}, but {} is selected.",
+            base_model, model
+        ))
+    };
+
+    Ok(CompatibilityResult {
+        compatible,
+        adapter_base_model: Some(base_model),
+        selected_model: model,
+        reason,
+    })
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
 pub struct LocalModelInfo {
     pub name: String,
     pub path: String,
     pub size_mb: u64,
     pub is_mlx: bool,
     pub source: String,
+    /// GGUF split across `-0000N-of-0000M` files; `path` is the first shard
+    #[serde(default)]
+    pub sharded: bool,
+    /// User-set display name from `set_model_alias`
+    #[serde(default)]
+    pub alias: Option<String>,
 }
 
-#[tauri::command]
-pub fn scan_local_models() -> Result<Vec<LocalModelInfo>, String> {
+/// One cache root's scan result, reused while the root's mtime is unchanged.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct IndexedCache {
+    mtime: u64,
+    models: Vec<LocalModelInfo>,
+}
+
+/// `~/Courtyard/model_index.json`, keyed by "<source>:<cache root>".
+#[derive(serde::Serialize, serde::Deserialize, Default)]
+struct ModelIndex {
+    caches: HashMap<String, IndexedCache>,
+}
+
+fn model_index_path() -> std::path::PathBuf {
+    ProjectDirManager::new().base_dir().join("model_index.json")
+}
+
+/// Newest top-level mtime across `dirs` (0 when none exist). Adding or removing
+/// a model changes its cache root's mtime.
+fn top_level_mtime(dirs: &[std::path::PathBuf]) -> u64 {
+    dirs.iter()
+        .filter_map(|d| std::fs::metadata(d).ok()?.modified().ok())
+        .filter_map(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .max()
+        .unwrap_or(0)
+}
+
+/// Scan every model cache, re-walking only roots whose mtime changed since the
+/// index was written (all of them when `force`).
+fn collect_local_models(force: bool) -> Vec<LocalModelInfo> {
     let resolved = crate::commands::config::resolve_model_paths();
+    let index_path = model_index_path();
+    let mut index: ModelIndex = if force {
+        ModelIndex::default()
+    } else {
+        std::fs::read_to_string(&index_path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    };
+    let mut dirty = false;
     let mut models = Vec::new();
 
+    let mut cached = |key: String, roots: Vec<std::path::PathBuf>, scan: &dyn Fn(&mut Vec<LocalModelInfo>)| {
+        let mtime = top_level_mtime(&roots);
+        if let Some(entry) = index.caches.get(&key).filter(|c| c.mtime == mtime) {
+            models.extend(entry.models.iter().cloned());
+            return;
+        }
+        let mut found = Vec::new();
+        scan(&mut found);
+        models.extend(found.iter().cloned());
+        index.caches.insert(key, IndexedCache { mtime, models: found });
+        dirty = true;
+    };
+
     // 1. Scan HuggingFace cache
-    scan_hf_style_cache(&resolved.huggingface, "huggingface", &mut models);
+    let hf = resolved.huggingface.clone();
+    cached(
+        format!("huggingface:{}", hf.display()),
+        vec![hf.clone()],
+        &|m| scan_hf_style_cache(&hf, "huggingface", m),
+    );
 
     // 2. Scan ModelScope cache
-    scan_hf_style_cache(&resolved.modelscope, "modelscope", &mut models);
+    let ms = resolved.modelscope.clone();
+    cached(
+        format!("modelscope:{}", ms.display()),
+        vec![ms.clone()],
+        &|m| scan_hf_style_cache(&ms, "modelscope", m),
+    );
 
     // 3. Scan the single effective Ollama path (daemon-aware: uses actual running path)
     let ollama_dir = crate::commands::environment::resolve_ollama_models_dir();
     let ollama_lib = ollama_dir
         .join("manifests").join("registry.ollama.ai").join("library");
-    scan_ollama_models(&ollama_lib, &ollama_dir, "ollama", &mut models);
+    cached(
+        format!("ollama:{}", ollama_dir.display()),
+        vec![ollama_lib.clone()],
+        &|m| scan_ollama_models(&ollama_lib, &ollama_dir, "ollama", m),
+    );
 
-    // 4. Scan LM Studio models directory
-    scan_lmstudio_models(&resolved.lmstudio, "lmstudio", &mut models);
+    // 4. Scan LM Studio models directory (plus the hub roots it may use)
+    let lms = resolved.lmstudio.clone();
+    let mut lms_roots = vec![lms.clone(), lms.join("hub").join("models")];
+    if let Some(parent) = lms.parent() {
+        lms_roots.push(parent.join("hub").join("models"));
+    }
+    cached(
+        format!("lmstudio:{}", lms.display()),
+        lms_roots,
+        &|m| scan_lmstudio_models(&lms, "lmstudio", m),
+    );
+
+    // 5. Standalone GGUF files: the app's own exports plus user-added dirs
+    let gguf_dirs = gguf_scan_dirs();
+    cached(
+        "gguf".to_string(),
+        gguf_dirs.clone(),
+        &|m| scan_gguf_files(&gguf_dirs, m),
+    );
+
+    if dirty {
+        if let Ok(json) = serde_json::to_string(&index) {
+            let _ = std::fs::create_dir_all(ProjectDirManager::new().base_dir());
+            let _ = std::fs::write(&index_path, json);
+        }
+    }
+
+    // Aliases live in config, not the index, so renames show up without a rescan
+    let aliases = crate::commands::config::load_config().aliases;
+    for model in &mut models {
+        model.alias = aliases.get(&model.name).cloned();
+    }
 
     // MLX models first, then by source, then by name
     models.sort_by(|a, b| {
@@ -547,7 +1547,96 @@ pub fn scan_local_models() -> Result<Vec<LocalModelInfo>, String> {
             .then(a.source.cmp(&b.source))
             .then(a.name.cmp(&b.name))
     });
-    Ok(models)
+    models
+}
+
+#[tauri::command]
+pub fn scan_local_models() -> Result<Vec<LocalModelInfo>, String> {
+    Ok(collect_local_models(false))
+}
+
+/// Drop the model index and rescan every cache from scratch.
+#[tauri::command]
+pub fn reindex_models() -> Result<Vec<LocalModelInfo>, String> {
+    Ok(collect_local_models(true))
+}
+
+/// Directories that may hold GGUF files: every project's `export/gguf`, the
+/// configured export path's per-project `gguf` dirs, and `gguf_dirs`.
+fn gguf_scan_dirs() -> Vec<std::path::PathBuf> {
+    let config = crate::commands::config::load_config();
+    let subdirs = |root: std::path::PathBuf, tail: &[&str]| -> Vec<std::path::PathBuf> {
+        std::fs::read_dir(&root)
+            .map(|rd| {
+                rd.filter_map(|e| e.ok())
+                    .map(|e| tail.iter().fold(e.path(), |p, t| p.join(t)))
+                    .filter(|p| p.is_dir())
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let mut dirs = subdirs(
+        ProjectDirManager::new().base_dir().join("projects"),
+        &["export", "gguf"],
+    );
+    if let Some(ref export_path) = config.export_path {
+        dirs.extend(subdirs(std::path::PathBuf::from(export_path), &["gguf"]));
+    }
+    dirs.extend(config.gguf_dirs.iter().map(std::path::PathBuf::from).filter(|p| p.is_dir()));
+    dirs.sort();
+    dirs.dedup();
+    dirs
+}
+
+/// Split a GGUF shard stem ("model-00001-of-00003") into its base name and
+/// shard index.
+fn gguf_shard_parts(stem: &str) -> Option<(&str, u32)> {
+    let (rest, total) = stem.rsplit_once("-of-")?;
+    let (base, index) = rest.rsplit_once('-')?;
+    let numeric = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    if !numeric(total) || !numeric(index) {
+        return None;
+    }
+    Some((base, index.parse().ok()?))
+}
+
+/// Report each .gguf file (or shard set) in `dirs` as a model.
+fn scan_gguf_files(dirs: &[std::path::PathBuf], models: &mut Vec<LocalModelInfo>) {
+    for dir in dirs {
+        let Ok(entries) = std::fs::read_dir(dir) else { continue; };
+        // base name -> (first shard path, lowest shard index, total bytes, sharded)
+        let mut found: HashMap<String, (std::path::PathBuf, u32, u64, bool)> = HashMap::new();
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let is_gguf = path.extension().map(|e| e.eq_ignore_ascii_case("gguf")).unwrap_or(false);
+            if !is_gguf || !path.is_file() {
+                continue;
+            }
+            let stem = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            let (base, index, sharded) = match gguf_shard_parts(&stem) {
+                Some((base, index)) => (base.to_string(), index, true),
+                None => (stem.clone(), 0, false),
+            };
+            let slot = found.entry(base).or_insert_with(|| (path.clone(), index, 0, sharded));
+            if index < slot.1 {
+                slot.0 = path.clone();
+                slot.1 = index;
+            }
+            slot.2 += size;
+        }
+        for (name, (path, _, bytes, sharded)) in found {
+            models.push(LocalModelInfo {
+                name,
+                path: path.to_string_lossy().to_string(),
+                size_mb: bytes / (1024 * 1024),
+                is_mlx: false,
+                source: "gguf".to_string(),
+                sharded,
+                alias: None,
+            });
+        }
+    }
 }
 
 fn scan_hf_style_cache(cache_dir: &std::path::Path, source: &str, models: &mut Vec<LocalModelInfo>) {
@@ -616,6 +1705,8 @@ fn scan_hf_style_cache(cache_dir: &std::path::Path, source: &str, models: &mut V
                 size_mb,
                 is_mlx,
                 source: source.to_string(),
+                sharded: false,
+                alias: None,
             });
         }
     }
@@ -650,15 +1741,62 @@ fn scan_ollama_models(
                 models.push(LocalModelInfo {
                     name: display,
                     path: ollama_models_dir.to_string_lossy().to_string(),
-                    size_mb: 0, // Ollama blob sizes require manifest parsing
+                    size_mb: ollama_manifest_size_bytes(&tag.path(), &ollama_models_dir) / (1024 * 1024),
                     is_mlx: false,
                     source: source.to_string(),
+                    sharded: false,
+                    alias: None,
                 });
             }
         }
     }
 }
 
+/// Total size of the config and layers an Ollama tag manifest references.
+/// Uses each descriptor's `size`, or the blob file (`blobs/sha256-<hex>`) when
+/// the size is missing. A manifest that isn't valid JSON falls back to summing
+/// the blobs for every digest found in its text; 0 when it can't be read.
+fn ollama_manifest_size_bytes(manifest_path: &std::path::Path, ollama_base: &std::path::Path) -> u64 {
+    let Ok(text) = std::fs::read_to_string(manifest_path) else { return 0 };
+    let blob_size = |digest: &str| {
+        std::fs::metadata(ollama_base.join("blobs").join(digest.replace(':', "-")))
+            .map(|m| m.len())
+            .unwrap_or(0)
+    };
+    let Ok(manifest) = serde_json::from_str::<serde_json::Value>(&text) else {
+        return manifest_digests(&text).iter().map(|d| blob_size(d)).sum();
+    };
+    manifest["layers"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .chain(std::iter::once(&manifest["config"]))
+        .map(|desc| {
+            desc["size"].as_u64().unwrap_or_else(|| {
+                desc["digest"].as_str().map(blob_size).unwrap_or(0)
+            })
+        })
+        .sum()
+}
+
+/// Distinct `sha256:<64 hex>` digests mentioned anywhere in `text`.
+fn manifest_digests(text: &str) -> Vec<String> {
+    let mut digests = Vec::new();
+    for (start, _) in text.match_indices("sha256:") {
+        let hex: String = text[start + "sha256:".len()..]
+            .chars()
+            .take_while(|c| c.is_ascii_hexdigit())
+            .collect();
+        if hex.len() == 64 {
+            let digest = format!("sha256:{}", hex);
+            if !digests.contains(&digest) {
+                digests.push(digest);
+            }
+        }
+    }
+    digests
+}
+
 /// Scan LM Studio models directory.
 /// LM Studio 2.x stores models under <root>/hub/models/{publisher}/{model}/
 /// with manifest.json + model.yaml (hub format, no direct .gguf in model dir).
@@ -738,6 +1876,8 @@ fn scan_lmstudio_models(
                     size_mb,
                     is_mlx,
                     source: source.to_string(),
+                    sharded: false,
+                    alias: None,
                 });
             }
         }
@@ -780,6 +1920,76 @@ pub fn validate_model_path(path: String) -> Result<bool, String> {
     Ok(has_config && (has_safetensors || has_tokenizer))
 }
 
+#[derive(serde::Serialize)]
+pub struct ModelInspection {
+    pub valid: bool,
+    pub model_type: Option<String>,
+    pub architecture: Option<String>,
+    pub hidden_size: Option<u64>,
+    pub num_layers: Option<u64>,
+    /// Quantization bits from MLX `quantization` or HF `quantization_config`.
+    pub quantization_bits: Option<u64>,
+    pub quantization_group_size: Option<u64>,
+    pub size_bytes: u64,
+    /// `tokenizer_class` when declared, else inferred from the files present.
+    pub tokenizer_type: Option<String>,
+}
+
+fn dir_size_bytes(path: &std::path::Path) -> u64 {
+    std::fs::read_dir(path)
+        .map(|rd| {
+            rd.filter_map(|e| e.ok())
+                .map(|e| match std::fs::metadata(e.path()) {
+                    Ok(meta) if meta.is_dir() => dir_size_bytes(&e.path()),
+                    Ok(meta) => meta.len(),
+                    Err(_) => 0,
+                })
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+fn read_json_file(path: &std::path::Path) -> Option<serde_json::Value> {
+    std::fs::read_to_string(path).ok().and_then(|s| serde_json::from_str(&s).ok())
+}
+
+/// Like `validate_model_path`, but also reports what the directory contains.
+#[tauri::command]
+pub fn inspect_model_path(path: String) -> Result<ModelInspection, String> {
+    let valid = validate_model_path(path.clone())?;
+    let p = std::path::Path::new(&path);
+    let config = read_json_file(&p.join("config.json")).unwrap_or(serde_json::Value::Null);
+    // Multimodal configs nest the language model under `text_config`.
+    let field = |key: &str| -> Option<&serde_json::Value> {
+        config.get(key).or_else(|| config.get("text_config").and_then(|t| t.get(key)))
+    };
+    let quant = config.get("quantization").or_else(|| config.get("quantization_config"));
+
+    let tokenizer_type = read_json_file(&p.join("tokenizer_config.json"))
+        .and_then(|t| t["tokenizer_class"].as_str().map(|s| s.to_string()))
+        .or_else(|| {
+            if p.join("tokenizer.json").exists() {
+                Some("fast".to_string())
+            } else if p.join("tokenizer.model").exists() {
+                Some("sentencepiece".to_string())
+            } else {
+                None
+            }
+        });
+
+    Ok(ModelInspection {
+        valid,
+        model_type: field("model_type").and_then(|v| v.as_str()).map(|s| s.to_string()),
+        architecture: config["architectures"][0].as_str().map(|s| s.to_string()),
+        hidden_size: field("hidden_size").or_else(|| field("n_embd")).and_then(|v| v.as_u64()),
+        num_layers: field("num_hidden_layers").or_else(|| field("n_layer")).and_then(|v| v.as_u64()),
+        quantization_bits: quant.and_then(|q| q["bits"].as_u64()),
+        quantization_group_size: quant.and_then(|q| q["group_size"].as_u64()),
+        size_bytes: if p.is_dir() { dir_size_bytes(p) } else { 0 },
+        tokenizer_type,
+    })
+}
+
 #[tauri::command]
 pub fn open_model_cache(source: Option<String>) -> Result<(), String> {
     let resolved = crate::commands::config::resolve_model_paths();
@@ -792,11 +2002,7 @@ pub fn open_model_cache(source: Option<String>) -> Result<(), String> {
     if !target.exists() {
         std::fs::create_dir_all(&target).map_err(|e| e.to_string())?;
     }
-    std::process::Command::new("open")
-        .arg(&target)
-        .spawn()
-        .map_err(|e| format!("Failed to open folder: {}", e))?;
-    Ok(())
+    crate::fs::open_in_file_manager(&target)
 }
 
 #[tauri::command]
@@ -808,18 +2014,46 @@ pub fn delete_adapter(adapter_path: String) -> Result<(), String> {
     if !path.is_dir() {
         return Err("Adapter path must be a directory".to_string());
     }
-    // Safety: must contain "adapters" somewhere in the path to avoid accidental deletion
-    if !adapter_path.contains("/adapters/") {
-        return Err("Path does not look like an adapter directory".to_string());
-    }
-    std::fs::remove_dir_all(path)
+    // Safety: only ever delete <projects>/<id>/adapters/<name>, after resolving symlinks
+    let path = ProjectDirManager::new().resolve_adapter_dir(path)?;
+    std::fs::remove_dir_all(&path)
         .map_err(|e| format!("Failed to delete adapter: {}", e))?;
     Ok(())
 }
 
+/// Move an adapter directory (with its training_meta.json) into another
+/// project's adapters/. Returns the new adapter path.
+#[tauri::command]
+pub fn move_adapter(adapter_path: String, target_project_id: String) -> Result<String, String> {
+    let src = std::path::Path::new(&adapter_path);
+    if !src.is_dir() {
+        return Err(format!("Adapter not found: {}", adapter_path));
+    }
+    // Same safety check as delete_adapter
+    let src = ProjectDirManager::new().resolve_adapter_dir(src)?;
+    let target_project = ProjectDirManager::new().project_path(&target_project_id);
+    if !target_project.is_dir() {
+        return Err(format!("Target project not found: {}", target_project_id));
+    }
+    let name = src
+        .file_name()
+        .ok_or_else(|| "Invalid adapter path".to_string())?;
+    let dest = target_project.join("adapters").join(name);
+    if dest.canonicalize().ok().as_ref() == Some(&src) {
+        return Ok(adapter_path);
+    }
+    crate::fs::move_dir(&src, &dest)?;
+    Ok(dest.to_string_lossy().to_string())
+}
+
+/// With `reveal`, the file manager opens with `adapter_path` itself selected
+/// (e.g. a specific checkpoint) instead of just showing the folder.
 #[tauri::command]
-pub fn open_adapter_folder(adapter_path: String) -> Result<(), String> {
+pub fn open_adapter_folder(adapter_path: String, reveal: Option<bool>) -> Result<(), String> {
     let path = std::path::Path::new(&adapter_path);
+    if reveal.unwrap_or(false) && path.exists() {
+        return crate::fs::reveal_in_file_manager(path);
+    }
     // If the path is a file, open its parent directory; otherwise open the directory itself
     let dir = if path.is_file() {
         path.parent().ok_or("Cannot resolve parent directory")?
@@ -831,11 +2065,7 @@ pub fn open_adapter_folder(adapter_path: String) -> Result<(), String> {
             .filter(|p| p.exists())
             .ok_or_else(|| format!("Path not found: {}", adapter_path))?
     };
-    std::process::Command::new("open")
-        .arg(dir)
-        .spawn()
-        .map_err(|e| format!("Failed to open folder: {}", e))?;
-    Ok(())
+    crate::fs::open_in_file_manager(dir)
 }
 
 /// Open the LM Studio application on macOS.
@@ -1098,3 +2328,117 @@ pub fn update_training_note(adapter_path: String, note: String) -> Result<(), St
         .map_err(|e| format!("Failed to update note: {}", e))?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG_DIGEST: &str = "sha256:1111111111111111111111111111111111111111111111111111111111111111";
+    const LAYER_DIGEST: &str = "sha256:2222222222222222222222222222222222222222222222222222222222222222";
+
+    /// An Ollama models dir holding one manifest with `body` and blobs of 10 and 300 bytes.
+    fn fixture(body: &str) -> (tempfile::TempDir, std::path::PathBuf) {
+        let tmp = tempfile::tempdir().unwrap();
+        let blobs = tmp.path().join("blobs");
+        std::fs::create_dir_all(&blobs).unwrap();
+        std::fs::write(blobs.join(CONFIG_DIGEST.replace(':', "-")), vec![0u8; 10]).unwrap();
+        std::fs::write(blobs.join(LAYER_DIGEST.replace(':', "-")), vec![0u8; 300]).unwrap();
+        let manifest = tmp.path().join("manifests/registry.ollama.ai/library/demo/latest");
+        std::fs::create_dir_all(manifest.parent().unwrap()).unwrap();
+        std::fs::write(&manifest, body).unwrap();
+        (tmp, manifest)
+    }
+
+    #[test]
+    fn manifest_size_uses_descriptor_sizes() {
+        let body = serde_json::json!({
+            "config": { "digest": CONFIG_DIGEST, "size": 1000 },
+            "layers": [{ "digest": LAYER_DIGEST, "size": 5000 }],
+        });
+        let (tmp, manifest) = fixture(&body.to_string());
+        assert_eq!(ollama_manifest_size_bytes(&manifest, tmp.path()), 6000);
+    }
+
+    #[test]
+    fn manifest_size_falls_back_to_blobs_when_sizes_missing() {
+        let body = serde_json::json!({
+            "config": { "digest": CONFIG_DIGEST },
+            "layers": [{ "digest": LAYER_DIGEST }],
+        });
+        let (tmp, manifest) = fixture(&body.to_string());
+        assert_eq!(ollama_manifest_size_bytes(&manifest, tmp.path()), 310);
+    }
+
+    #[test]
+    fn manifest_size_sums_blobs_for_unparseable_manifest() {
+        let body = format!("{{\"config\":{{\"digest\":\"{}\"}},\"layers\":[{{\"digest\":\"{}\"", CONFIG_DIGEST, LAYER_DIGEST);
+        let (tmp, manifest) = fixture(&body);
+        assert_eq!(ollama_manifest_size_bytes(&manifest, tmp.path()), 310);
+    }
+
+    #[test]
+    fn claim_adapter_dir_falls_back_to_job_id_when_name_taken() {
+        let tmp = tempfile::tempdir().unwrap();
+        let adapters = tmp.path().join("adapters");
+        let first = claim_adapter_dir(&adapters, Some("my-adapter"), "job-1").unwrap();
+        assert_eq!(first, adapters.join("my-adapter"));
+        let second = claim_adapter_dir(&adapters, Some("my-adapter"), "job-2").unwrap();
+        assert_eq!(second, adapters.join("job-2"));
+        assert!(claim_adapter_dir(&adapters, None, "job-2").is_err());
+    }
+
+    #[test]
+    fn parse_training_metric_reads_mlx_lm_report_formats() {
+        type Expected = (u64, Option<f64>, Option<f64>, Option<f64>, Option<f64>, Option<u64>);
+        let cases: &[(&str, Expected)] = &[
+            // mlx-lm report with learning rate and peak memory.
+            (
+                "Iter 10: Train loss 1.234, Learning Rate 1.000e-05, It/sec 0.512, Tokens/sec 402.3, Trained Tokens 7856, Peak mem 5.432 GB",
+                (10, Some(1.234), None, Some(0.512), Some(402.3), Some(7856)),
+            ),
+            // Older mlx-examples style report.
+            (
+                "Iter 20: Train loss 0.980, It/sec 1.25, Tokens/sec 250.0",
+                (20, Some(0.98), None, Some(1.25), Some(250.0), None),
+            ),
+            ("Iter 200: Val loss 1.456, Val took 3.210s", (200, None, Some(1.456), None, None, None)),
+            ("Iter 1: Val loss 2.345, Val took 12.5s", (1, None, Some(2.345), None, None, None)),
+            ("iter=30 train_loss=0.75 it/s=2.0", (30, Some(0.75), None, Some(2.0), None, None)),
+        ];
+        for (line, expected) in cases {
+            let m = parse_training_metric(line, "job").unwrap_or_else(|| panic!("no metric in {:?}", line));
+            let got = (m.iter, m.train_loss, m.val_loss, m.it_per_sec, m.tokens_per_sec, m.trained_tokens);
+            assert_eq!(got, *expected, "{}", line);
+        }
+    }
+
+    #[test]
+    fn parse_training_metric_ignores_non_report_lines() {
+        for line in [
+            "Loading pretrained model",
+            "Trainable parameters: 0.085% (6.816M/8030.261M)",
+            "Starting training..., iters: 1000",
+            "Iter 100: Saved adapter weights to adapters/0000100_adapters.safetensors.",
+            "",
+        ] {
+            assert!(parse_training_metric(line, "job").is_none(), "{}", line);
+        }
+    }
+
+    #[test]
+    fn resume_adapter_must_match_base_type_and_rank() {
+        let tmp = tempfile::tempdir().unwrap();
+        let meta = serde_json::json!({
+            "base_model": "mlx-community/Qwen2-7B-Instruct-4bit",
+            "fine_tune_type": "lora",
+            "lora_rank": 8,
+        });
+        std::fs::write(tmp.path().join("training_meta.json"), meta.to_string()).unwrap();
+        let file = tmp.path().join("adapters.safetensors");
+
+        assert!(check_resume_adapter_matches(&file, "Qwen/Qwen2-7B-Instruct", "lora", 8).is_ok());
+        assert!(check_resume_adapter_matches(&file, "Qwen/Qwen2-1.5B-Instruct", "lora", 8).is_err());
+        assert!(check_resume_adapter_matches(&file, "Qwen/Qwen2-7B-Instruct", "dora", 8).is_err());
+        assert!(check_resume_adapter_matches(&file, "Qwen/Qwen2-7B-Instruct", "lora", 16).is_err());
+    }
+}
@@ -73,5 +73,13 @@ pub fn run_migrations() -> Vec<Migration> {
             "#,
             kind: MigrationKind::Up,
         },
+        Migration {
+            version: 3,
+            description: "add projects.archived",
+            sql: r#"
+                ALTER TABLE projects ADD COLUMN archived INTEGER NOT NULL DEFAULT 0;
+            "#,
+            kind: MigrationKind::Up,
+        },
     ]
 }
@@ -1,3 +1,4 @@
 pub mod migrations;
+pub mod store;
 
 pub use migrations::run_migrations;
@@ -0,0 +1,78 @@
+use sqlx::Row;
+use tauri::Manager;
+use tauri_plugin_sql::{DbInstances, DbPool};
+
+/// Connection string shared with the frontend's `Database.load(...)` call.
+pub const DB_URL: &str = "sqlite:courtyard.db";
+
+/// A row of the `projects` table.
+pub struct ProjectRow {
+    pub id: String,
+    pub name: String,
+    pub path: String,
+    pub status: String,
+    pub model_path: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    pub archived: bool,
+}
+
+/// Borrow the SQLite pool opened by tauri-plugin-sql.
+/// Returns None until the frontend has loaded the database.
+pub async fn sqlite_pool(app: &tauri::AppHandle) -> Option<sqlx::SqlitePool> {
+    let instances = app.try_state::<DbInstances>()?;
+    let guard = instances.0.read().await;
+    match guard.get(DB_URL)? {
+        DbPool::Sqlite(pool) => Some(pool.clone()),
+    }
+}
+
+pub async fn load_projects(pool: &sqlx::SqlitePool) -> Result<Vec<ProjectRow>, String> {
+    let rows = sqlx::query(
+        "SELECT id, name, path, status, model_path, created_at, updated_at, archived FROM projects ORDER BY created_at DESC",
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to query projects: {}", e))?;
+
+    rows.iter()
+        .map(|row| {
+            Ok(ProjectRow {
+                id: row.try_get("id").map_err(|e| e.to_string())?,
+                name: row.try_get("name").map_err(|e| e.to_string())?,
+                path: row.try_get("path").map_err(|e| e.to_string())?,
+                status: row.try_get("status").map_err(|e| e.to_string())?,
+                model_path: row.try_get("model_path").map_err(|e| e.to_string())?,
+                created_at: row.try_get("created_at").map_err(|e| e.to_string())?,
+                updated_at: row.try_get("updated_at").map_err(|e| e.to_string())?,
+                archived: row.try_get("archived").map_err(|e| e.to_string())?,
+            })
+        })
+        .collect()
+}
+
+pub async fn load_project_name(pool: &sqlx::SqlitePool, id: &str) -> Option<String> {
+    sqlx::query_scalar("SELECT name FROM projects WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .ok()
+        .flatten()
+}
+
+pub async fn set_project_archived(
+    pool: &sqlx::SqlitePool,
+    id: &str,
+    archived: bool,
+) -> Result<(), String> {
+    let result = sqlx::query("UPDATE projects SET archived = ?, updated_at = datetime('now') WHERE id = ?")
+        .bind(archived)
+        .bind(id)
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Failed to update project: {}", e))?;
+    if result.rows_affected() == 0 {
+        return Err(format!("Project not found: {}", id));
+    }
+    Ok(())
+}
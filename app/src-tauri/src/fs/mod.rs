@@ -1,3 +1,7 @@
 pub mod project_dir;
+pub mod reveal;
+pub mod transfer;
 
 pub use project_dir::ProjectDirManager;
+pub use reveal::{open_in_file_manager, reveal_in_file_manager};
+pub use transfer::{move_dir, move_file};
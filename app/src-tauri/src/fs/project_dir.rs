@@ -1,5 +1,5 @@
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
 
 pub struct ProjectDirManager {
     base_dir: PathBuf,
@@ -45,8 +45,92 @@ impl ProjectDirManager {
         self.base_dir.join("projects").join(project_id)
     }
 
+    pub fn base_dir(&self) -> &PathBuf {
+        &self.base_dir
+    }
+
+    /// Canonicalize `path` and require it to be exactly
+    /// `<base>/projects/<project>/adapters/<name>`, so symlinks or `..`
+    /// cannot point destructive adapter commands anywhere else.
+    pub fn resolve_adapter_dir(&self, path: &Path) -> Result<PathBuf, String> {
+        let resolved = path
+            .canonicalize()
+            .map_err(|e| format!("Adapter not found: {} ({})", path.display(), e))?;
+        let projects_root = self
+            .base_dir
+            .join("projects")
+            .canonicalize()
+            .map_err(|e| format!("Projects directory not found: {}", e))?;
+        let parts: Vec<Component> = resolved
+            .strip_prefix(&projects_root)
+            .map_err(|_| "Adapter path is outside the Courtyard projects directory".to_string())?
+            .components()
+            .collect();
+        match parts.as_slice() {
+            [Component::Normal(_), Component::Normal(dir), Component::Normal(_)] if *dir == "adapters" => {
+                Ok(resolved)
+            }
+            _ => Err("Path does not look like an adapter directory".to_string()),
+        }
+    }
 }
 
 fn dirs_next() -> Option<PathBuf> {
     std::env::var_os("HOME").map(PathBuf::from)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A manager rooted in a temp dir with one project holding adapter `good`.
+    fn setup() -> (tempfile::TempDir, ProjectDirManager) {
+        let tmp = tempfile::tempdir().unwrap();
+        let manager = ProjectDirManager { base_dir: tmp.path().join("Courtyard") };
+        fs::create_dir_all(manager.project_path("p").join("adapters").join("good")).unwrap();
+        (tmp, manager)
+    }
+
+    #[test]
+    fn accepts_adapter_dir() {
+        let (_tmp, manager) = setup();
+        let adapter = manager.project_path("p").join("adapters").join("good");
+        assert_eq!(
+            manager.resolve_adapter_dir(&adapter).unwrap(),
+            adapter.canonicalize().unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_path_outside_projects() {
+        let (tmp, manager) = setup();
+        let outside = tmp.path().join("x").join("adapters").join("y");
+        fs::create_dir_all(&outside).unwrap();
+        assert!(manager.resolve_adapter_dir(&outside).is_err());
+    }
+
+    #[test]
+    fn rejects_parent_traversal() {
+        let (_tmp, manager) = setup();
+        let escaped = manager.project_path("p").join("adapters").join("..").join("..");
+        assert!(manager.resolve_adapter_dir(&escaped).is_err());
+    }
+
+    #[test]
+    fn rejects_adapters_root() {
+        let (_tmp, manager) = setup();
+        let root = manager.project_path("p").join("adapters");
+        assert!(manager.resolve_adapter_dir(&root).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_symlink_pointing_outside() {
+        let (tmp, manager) = setup();
+        let target = tmp.path().join("elsewhere");
+        fs::create_dir_all(&target).unwrap();
+        let link = manager.project_path("p").join("adapters").join("link");
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+        assert!(manager.resolve_adapter_dir(&link).is_err());
+    }
+}
@@ -0,0 +1,51 @@
+use std::path::Path;
+use std::process::Command;
+
+/// Open the directory `path` in the platform file manager. Goes through the
+/// opener plugin, falling back to `open` / `explorer` / `xdg-open`.
+pub fn open_in_file_manager(path: &Path) -> Result<(), String> {
+    if !path.exists() {
+        return Err(format!("Path not found: {}", path.display()));
+    }
+    if tauri_plugin_opener::open_path(path, None::<&str>).is_ok() {
+        return Ok(());
+    }
+    let program = if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(target_os = "windows") {
+        "explorer"
+    } else {
+        "xdg-open"
+    };
+    Command::new(program)
+        .arg(path)
+        .spawn()
+        .map_err(|e| format!("Failed to open folder: {}", e))?;
+    Ok(())
+}
+
+/// Show `path` in the platform file manager with the item itself selected,
+/// rather than just opening its parent directory.
+pub fn reveal_in_file_manager(path: &Path) -> Result<(), String> {
+    if !path.exists() {
+        return Err(format!("Path not found: {}", path.display()));
+    }
+    let mut cmd = if cfg!(target_os = "macos") {
+        let mut c = Command::new("open");
+        c.arg("-R").arg(path);
+        c
+    } else if cfg!(target_os = "windows") {
+        // explorer wants "/select,<path>" as a single argument.
+        let mut c = Command::new("explorer");
+        c.arg(format!("/select,{}", path.display()));
+        c
+    } else {
+        // No portable select on Linux; open the containing directory.
+        let mut c = Command::new("xdg-open");
+        c.arg(path.parent().unwrap_or(path));
+        c
+    };
+    cmd.spawn()
+        .map_err(|e| format!("Failed to reveal {}: {}", path.display(), e))?;
+    Ok(())
+}
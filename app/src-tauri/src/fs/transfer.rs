@@ -0,0 +1,57 @@
+use std::fs;
+use std::path::Path;
+
+/// Move a directory, renaming when possible and falling back to copy + delete
+/// when `src` and `dst` live on different volumes.
+pub fn move_dir(src: &Path, dst: &Path) -> Result<(), String> {
+    if dst.exists() {
+        return Err(format!("Destination already exists: {}", dst.display()));
+    }
+    if let Some(parent) = dst.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    if fs::rename(src, dst).is_ok() {
+        return Ok(());
+    }
+    if let Err(e) = copy_dir_recursive(src, dst) {
+        let _ = fs::remove_dir_all(dst);
+        return Err(e);
+    }
+    fs::remove_dir_all(src)
+        .map_err(|e| format!("Copied to {} but failed to remove {}: {}", dst.display(), src.display(), e))
+}
+
+/// Move a single file, falling back to copy + delete across volumes.
+pub fn move_file(src: &Path, dst: &Path) -> Result<(), String> {
+    if dst.exists() {
+        return Err(format!("Destination already exists: {}", dst.display()));
+    }
+    if fs::rename(src, dst).is_ok() {
+        return Ok(());
+    }
+    if let Err(e) = fs::copy(src, dst) {
+        let _ = fs::remove_file(dst);
+        return Err(format!("Failed to copy {}: {}", src.display(), e));
+    }
+    fs::remove_file(src)
+        .map_err(|e| format!("Copied to {} but failed to remove {}: {}", dst.display(), src.display(), e))
+}
+
+/// Recursively copy `src` into a new directory at `dst`.
+pub fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), String> {
+    fs::create_dir_all(dst).map_err(|e| format!("Failed to create {}: {}", dst.display(), e))?;
+    let entries = fs::read_dir(src).map_err(|e| format!("Failed to read {}: {}", src.display(), e))?;
+    for entry in entries.flatten() {
+        let from = entry.path();
+        let to = dst.join(entry.file_name());
+        let file_type = entry.file_type().map_err(|e| e.to_string())?;
+        if file_type.is_dir() {
+            copy_dir_recursive(&from, &to)?;
+        } else {
+            fs::copy(&from, &to)
+                .map_err(|e| format!("Failed to copy {}: {}", from.display(), e))?;
+        }
+    }
+    Ok(())
+}
@@ -3,16 +3,18 @@ mod db;
 mod fs;
 mod python;
 
-use commands::config::{get_app_config, set_model_source_path, set_export_path, set_hf_source, set_ollama_bin_path, set_lmstudio_api_url, check_lmstudio_api, get_network_config, save_network_config};
-use commands::environment::{check_environment, setup_environment, install_uv, check_ollama_status, list_ollama_models, get_ollama_path_info, fix_ollama_models_path, reset_ollama_models_path};
-use commands::project::{create_project, delete_project, list_projects};
-use commands::training::{start_training, stop_training, open_project_folder, list_adapters, delete_adapter, open_adapter_folder, scan_local_models, open_model_cache, validate_model_path, open_lmstudio_app, check_lmstudio_server, save_training_result, list_training_history, update_training_note};
-use commands::files::{import_files, list_project_files, read_file_content, delete_file, clear_project_data};
-use commands::dataset::{start_cleaning, generate_dataset, get_dataset_preview, stop_generation, list_dataset_versions, open_dataset_folder, sample_raw_files, preview_clean_segments, import_custom_dataset};
-use commands::inference::start_inference;
-use commands::export::{export_to_ollama, export_to_gguf, export_to_mlx, verify_export_model, start_mlx_server, stop_mlx_server, get_mlx_server_status, MlxServerState};
+use commands::config::{get_app_config, set_model_source_path, set_export_path, set_hf_source, test_hf_source, set_ollama_bin_path, set_lmstudio_api_url, check_lmstudio_api, get_network_config, save_network_config, set_prevent_sleep, set_notification_preferences, set_gguf_dirs, set_default_export_format, set_model_alias, clear_model_alias, set_autoclean_policy};
+use commands::environment::{check_environment, setup_environment, repair_venv, update_mlx_lm, install_uv, check_ollama_status, list_ollama_models, get_ollama_path_info, fix_ollama_models_path, reset_ollama_models_path, health_check_ollama_models_dir, pull_ollama_model, cancel_pull_ollama_model};
+use commands::project::{create_project, delete_project, get_project_delete_impact, list_projects, archive_project, unarchive_project};
+use commands::training::{start_training, stop_training, get_training_progress, open_project_folder, list_adapters, get_training_meta, check_adapter_compatibility, resolve_base_model, delete_adapter, move_adapter, open_adapter_folder, scan_local_models, reindex_models, open_model_cache, validate_model_path, inspect_model_path, open_lmstudio_app, check_lmstudio_server, save_training_result, list_training_history, update_training_note};
+use commands::files::{import_files, import_url, list_project_files, get_raw_files_tree, read_file_content, delete_file, clear_project_data};
+use commands::dataset::{start_cleaning, generate_dataset, retry_generation, get_last_generation, estimate_generation_cost, get_dataset_preview, stop_generation, stop_cleaning, list_dataset_versions, prune_dataset_versions_by_age, shuffle_dataset_version, open_dataset_folder, sample_raw_files, recommend_generation_mode, preview_clean_segments, get_segment_distribution, get_cleaning_manifest, import_custom_dataset, export_cleaned_corpus};
+use commands::inference::{start_inference, stop_inference, batch_inference};
+use commands::export::{export_to_ollama, export_to_gguf, export_to_mlx, export_with_defaults, move_export, fuse_adapter, repair_ollama_export, verify_export_model, verify_gguf_model, cancel_export, get_export_log, start_mlx_server, stop_mlx_server, get_mlx_server_status, MlxServerState};
 use commands::native_notification::{get_native_notification_permission, request_native_notification_permission, send_native_notification};
-use commands::storage::{scan_storage_usage, cleanup_project_cache};
+use commands::storage::{scan_storage_usage, cleanup_project_cache, purge_incomplete_adapters, compact_adapter, get_storage_history};
+use commands::report::{get_system_report, get_script_versions};
+use commands::processes::stop_all;
 use commands::notification_config::{get_notification_config, save_notification_config};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -27,49 +29,85 @@ pub fn run() {
         .plugin(tauri_plugin_notification::init())
         .plugin(
             tauri_plugin_sql::Builder::default()
-                .add_migrations("sqlite:courtyard.db", migrations)
+                .add_migrations(db::store::DB_URL, migrations)
                 .build(),
         )
         .invoke_handler(tauri::generate_handler![
             check_environment,
             setup_environment,
+            repair_venv,
+            update_mlx_lm,
             install_uv,
             check_ollama_status,
             list_ollama_models,
             get_ollama_path_info,
             fix_ollama_models_path,
+            health_check_ollama_models_dir,
             reset_ollama_models_path,
+            pull_ollama_model,
+            cancel_pull_ollama_model,
             create_project,
             list_projects,
+            get_project_delete_impact,
+            archive_project,
+            unarchive_project,
             delete_project,
             start_training,
             stop_training,
+            get_training_progress,
             import_files,
+            import_url,
             list_project_files,
+            get_raw_files_tree,
             read_file_content,
             delete_file,
             clear_project_data,
             start_cleaning,
             generate_dataset,
+            retry_generation,
+            get_last_generation,
+            estimate_generation_cost,
             get_dataset_preview,
             stop_generation,
+            stop_cleaning,
             list_dataset_versions,
+            prune_dataset_versions_by_age,
+            shuffle_dataset_version,
             open_dataset_folder,
             sample_raw_files,
+            recommend_generation_mode,
             preview_clean_segments,
+            get_segment_distribution,
+            get_cleaning_manifest,
             import_custom_dataset,
+            export_cleaned_corpus,
             open_project_folder,
             list_adapters,
+            get_training_meta,
+            check_adapter_compatibility,
+            resolve_base_model,
             delete_adapter,
+            move_adapter,
             open_adapter_folder,
             scan_local_models,
+            reindex_models,
             open_model_cache,
             validate_model_path,
+            inspect_model_path,
             start_inference,
+            stop_inference,
+            batch_inference,
             export_to_ollama,
             export_to_gguf,
             export_to_mlx,
+            export_with_defaults,
+            move_export,
+            fuse_adapter,
+            repair_ollama_export,
             verify_export_model,
+            verify_gguf_model,
+            cancel_export,
+            get_export_log,
             start_mlx_server,
             stop_mlx_server,
             get_mlx_server_status,
@@ -77,6 +115,7 @@ pub fn run() {
             set_model_source_path,
             set_export_path,
             set_hf_source,
+            test_hf_source,
             set_ollama_bin_path,
             set_lmstudio_api_url,
             check_lmstudio_api,
@@ -87,6 +126,9 @@ pub fn run() {
             send_native_notification,
             scan_storage_usage,
             cleanup_project_cache,
+            purge_incomplete_adapters,
+            compact_adapter,
+            get_storage_history,
             get_notification_config,
             save_notification_config,
             save_training_result,
@@ -94,6 +136,16 @@ pub fn run() {
             update_training_note,
             get_network_config,
             save_network_config,
+            set_prevent_sleep,
+            set_notification_preferences,
+            set_autoclean_policy,
+            set_gguf_dirs,
+            set_default_export_format,
+            set_model_alias,
+            clear_model_alias,
+            get_system_report,
+            get_script_versions,
+            stop_all,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -102,6 +102,24 @@ impl PythonExecutor {
         find_binary("ollama", &candidates)
     }
 
+    /// Check if llama.cpp's CLI (`llama-cli`) is available on the system
+    pub fn find_llama_cli() -> Option<PathBuf> {
+        let candidates = vec![
+            PathBuf::from("/opt/homebrew/bin/llama-cli"),
+            PathBuf::from("/usr/local/bin/llama-cli"),
+        ];
+        find_binary("llama-cli", &candidates)
+    }
+
+    /// Find another llama.cpp tool (e.g. `llama-quantize`, `llama-imatrix`)
+    pub fn find_llama_tool(name: &str) -> Option<PathBuf> {
+        let candidates = vec![
+            PathBuf::from(format!("/opt/homebrew/bin/{}", name)),
+            PathBuf::from(format!("/usr/local/bin/{}", name)),
+        ];
+        find_binary(name, &candidates)
+    }
+
     /// Returns the path to bundled scripts directory.
     /// In dev builds, the source-tree scripts directory is checked first so
     /// newly-added scripts don't require a separate copy/symlink step.
@@ -1,3 +1,4 @@
 pub mod executor;
+pub mod parser;
 
 pub use executor::PythonExecutor;
@@ -1,2 +1,75 @@
-// Reserved for future Rust-side log parsing utilities.
-// Currently training log parsing is done on the frontend.
+use serde_json::Value;
+
+/// A JSON object split across more lines than this is reported, not rebuilt.
+const MAX_PENDING_LINES: usize = 4;
+
+/// What a stdout line from a Python script turned out to be.
+pub enum ParsedLine {
+    /// A complete JSON event.
+    Event(Value),
+    /// Plain text output.
+    Text(String),
+    /// JSON that could not be recovered (corrupted by interleaved writes).
+    ParseError(String),
+}
+
+/// Line-oriented JSON event parser for script stdout.
+///
+/// Scripts print one JSON object per line, but writes from several worker
+/// threads can split an object across lines. A line that looks like the start
+/// of an object but fails to parse is held back and joined with what follows;
+/// if that still fails it comes out as `ParseError` instead of being dropped.
+#[derive(Default)]
+pub struct JsonLineParser {
+    pending: Vec<String>,
+}
+
+impl JsonLineParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, line: &str) -> Vec<ParsedLine> {
+        let mut out = Vec::new();
+
+        if let Ok(event) = serde_json::from_str::<Value>(line) {
+            out.extend(self.flush());
+            out.push(ParsedLine::Event(event));
+            return out;
+        }
+
+        let starts_object = line.trim_start().starts_with('{');
+        if self.pending.is_empty() {
+            if starts_object {
+                self.pending.push(line.to_string());
+            } else {
+                out.push(ParsedLine::Text(line.to_string()));
+            }
+            return out;
+        }
+
+        if starts_object {
+            // A new object began before the held one completed.
+            out.extend(self.flush());
+            self.pending.push(line.to_string());
+            return out;
+        }
+
+        self.pending.push(line.to_string());
+        if let Ok(event) = serde_json::from_str::<Value>(&self.pending.concat()) {
+            self.pending.clear();
+            out.push(ParsedLine::Event(event));
+        } else if self.pending.len() >= MAX_PENDING_LINES {
+            out.extend(self.flush());
+        }
+        out
+    }
+
+    /// Report whatever is still held back; call once the stream ends.
+    pub fn flush(&mut self) -> Option<ParsedLine> {
+        if self.pending.is_empty() {
+            return None;
+        }
+        Some(ParsedLine::ParseError(std::mem::take(&mut self.pending).join("\n")))
+    }
+}
@@ -14,6 +14,7 @@ export interface LocalModelInfo {
   size_mb: number;
   is_mlx: boolean;
   source: string;
+  alias?: string | null;
 }
 
 interface OnlineModelOption {
@@ -258,7 +259,7 @@ const HF_DOWNLOAD_LINKS = [
 
 function isSourceUsableInMode(source: string, mode: ModelSelectorMode, dataprepSource?: string): boolean {
   if (mode === "dataprep") return source === (dataprepSource || "ollama");
-  if (mode === "training") return source !== "ollama" && source !== "lmstudio" && source !== "trained";
+  if (mode === "training") return source !== "ollama" && source !== "lmstudio" && source !== "gguf" && source !== "trained";
   if (mode === "export") return source === "trained";
   return true;
 }
@@ -270,7 +271,7 @@ function isModelUsable(
   daemonModels: OllamaModelInfo[],
   dataprepSource?: string,
 ): boolean {
-  if (mode === "training") return source !== "ollama" && source !== "lmstudio" && source !== "trained";
+  if (mode === "training") return source !== "ollama" && source !== "lmstudio" && source !== "gguf" && source !== "trained";
   if (mode === "dataprep") {
     const target = dataprepSource || "ollama";
     if (source !== target) return false;
@@ -283,7 +284,7 @@ function isModelUsable(
 
 function getDisabledReasonKey(source: string, mode: ModelSelectorMode, daemonVisible: boolean, dataprepSource?: string): string {
   if (mode === "training") {
-    if (source === "ollama" || source === "lmstudio") return "modelSelector.disabledReason.ollamaNoLora";
+    if (source === "ollama" || source === "lmstudio" || source === "gguf") return "modelSelector.disabledReason.ollamaNoLora";
     if (source === "trained") return "modelSelector.disabledReason.trainedNotBase";
   }
   if (mode === "dataprep") {
@@ -353,8 +354,8 @@ export function ModelSelector({ mode, selectedModel, onSelect, disabled, project
   const loadAdapters = useCallback(async () => {
     if (!projectId) { setAdapters([]); return; }
     try {
-      const list = await invoke<AdapterInfo[]>("list_adapters", { projectId });
-      setAdapters(list.filter((a) => a.has_weights));
+      const list = await invoke<AdapterInfo[]>("list_adapters", { projectId, onlyComplete: true });
+      setAdapters(list);
     } catch {
       setAdapters([]);
     }
@@ -621,7 +622,7 @@ export function ModelSelector({ mode, selectedModel, onSelect, disabled, project
                     ) : (
                       <span className={`h-4 w-4 shrink-0 rounded-full border-2 ${usable ? "border-muted-foreground/30" : "border-muted-foreground/15"}`} />
                     )}
-                    <span className={`flex-1 min-w-0 truncate font-medium ${usable ? "text-foreground" : "text-muted-foreground/40"}`}>{m.name}</span>
+                    <span className={`flex-1 min-w-0 truncate font-medium ${usable ? "text-foreground" : "text-muted-foreground/40"}`} title={m.name}>{m.alias || m.name}</span>
                     <span className="ml-2 shrink-0 text-muted-foreground/70">
                       {m.size_mb > 1024 ? `${(m.size_mb / 1024).toFixed(1)} GB` : m.size_mb > 0 ? `${m.size_mb} MB` : ""}
                     </span>
@@ -865,7 +866,7 @@ export function ModelSelector({ mode, selectedModel, onSelect, disabled, project
                                           </>
                                         );
                                       })() : (
-                                        <span className={`font-medium ${usable ? "text-foreground" : "text-muted-foreground/40"}`}>{m.name}</span>
+                                        <span className={`font-medium ${usable ? "text-foreground" : "text-muted-foreground/40"}`}>{m.alias || m.name}</span>
                                       )}
                                       {m.is_mlx && m.source !== "trained" && (
                                         <span className="ml-1.5 rounded bg-tag-mlx/15 px-1 py-0.5 text-[10px] text-tag-mlx">MLX</span>
@@ -37,13 +37,15 @@ interface DatasetVersionInfo {
   quality_score?: number | null;
   quality_grade?: string;
   quality_scoring_enabled?: boolean;
+  trained_adapters?: string[];
+  incomplete?: boolean;
 }
 
-interface RawFileSample {
-  name: string;
-  ext: string;
-  size: number;
-  snippet: string;
+interface ModeRecommendation {
+  recommended: "qa" | "style" | "chat" | "instruct";
+  status: ModeStatusMap;
+  hint_key: string;
+  rationale: string;
 }
 
 interface SegmentPreviewItem {
@@ -63,6 +65,8 @@ interface SegmentPreviewSummary {
   short_segments: number;
   long_segments: number;
   primary_strategy: string;
+  quality: "good" | "short-heavy" | "long-heavy" | "mixed";
+  advice: string;
 }
 
 interface SegmentPreviewResponse {
@@ -70,6 +74,13 @@ interface SegmentPreviewResponse {
   items: SegmentPreviewItem[];
 }
 
+interface GenerationEstimate {
+  segments: number;
+  input_tokens: number;
+  output_tokens: number;
+  estimated_seconds: number | null;
+}
+
 type ModeStatus = "recommended" | "available" | "cautious";
 type ModeStatusMap = Record<"qa" | "style" | "chat" | "instruct", ModeStatus>;
 
@@ -80,106 +91,6 @@ const SEGMENT_STRATEGY_KEY_MAP: Record<string, string> = {
   paragraph_balanced: "segment.strategy.paragraph_balanced",
 };
 
-function analyzeContentForModes(samples: RawFileSample[]): { status: ModeStatusMap; hintKey: string } {
-  if (samples.length === 0) {
-    return {
-      status: { qa: "available", style: "available", chat: "available", instruct: "available" },
-      hintKey: "generate.modeCheckNoFiles",
-    };
-  }
-
-  const structuredExts = new Set(["json", "jsonl", "csv", "tsv", "xml", "yaml", "yml"]);
-  const proseExts = new Set(["txt", "md", "markdown", "doc", "docx", "pdf", "rtf"]);
-
-  let proseScore = 0;
-  let structuredScore = 0;
-  let dialogueScore = 0;
-  let headingScore = 0;
-  let totalSnippetLen = 0;
-
-  for (const s of samples) {
-    // Extension-based signals
-    if (structuredExts.has(s.ext)) structuredScore += 2;
-    if (proseExts.has(s.ext)) proseScore += 2;
-
-    const text = s.snippet;
-    if (!text) continue;
-    totalSnippetLen += text.length;
-
-    // Content heuristics
-    const lines = text.split("\n").filter((l) => l.trim());
-    const avgLineLen = lines.length > 0 ? text.length / lines.length : 0;
-
-    // Long paragraphs → prose/narrative
-    if (avgLineLen > 80) proseScore += 2;
-    // Short lines with many line breaks → structured/list
-    if (avgLineLen < 30 && lines.length > 5) structuredScore += 1;
-
-    // JSON-like content
-    if (/^\s*[\[{]/.test(text) && /[\]}]\s*$/.test(text.trim())) structuredScore += 3;
-    // Key-value patterns
-    if ((text.match(/[""]\s*:\s*/g) || []).length > 3) structuredScore += 2;
-
-    // Dialogue markers → good for chat mode
-    const dialogueMarkers = (text.match(/["「『"]/g) || []).length;
-    if (dialogueMarkers > 2) dialogueScore += 2;
-    // Quotation patterns like "xxx说" or "xxx道"
-    if ((text.match(/[说道问答叫喊笑哭]：/g) || []).length > 0) dialogueScore += 2;
-    // English dialogue: "said", "asked"
-    if ((text.match(/\b(said|asked|replied|exclaimed)\b/gi) || []).length > 0) dialogueScore += 2;
-
-    // Headings → good for QA extraction
-    if ((text.match(/^#{1,6}\s+/gm) || []).length > 0) headingScore += 2;
-    if ((text.match(/^第[一二三四五六七八九十\d]+[章节部分]/gm) || []).length > 0) headingScore += 2;
-
-    // Narrative continuity (paragraph connectors)
-    const narrativeWords = (text.match(/[然而但是因此所以接着随后于是不过]/g) || []).length;
-    if (narrativeWords > 2) proseScore += 1;
-  }
-
-  // Normalize: is the content primarily structured or prose?
-  const isMainlyStructured = structuredScore > proseScore * 2 && proseScore < 3;
-  const isMainlyProse = proseScore > structuredScore * 2;
-  const hasDialogue = dialogueScore >= 2;
-  const hasHeadings = headingScore >= 2;
-
-  const status: ModeStatusMap = {
-    qa: "recommended",
-    style: "available",
-    chat: "available",
-    instruct: "recommended",
-  };
-
-  // QA: best when there are headings or structured knowledge
-  if (hasHeadings) status.qa = "recommended";
-  if (isMainlyStructured && !hasHeadings) status.qa = "available";
-
-  // Style: best for rich narrative prose
-  if (isMainlyProse) {
-    status.style = "recommended";
-  } else if (isMainlyStructured) {
-    status.style = "cautious";
-  }
-
-  // Chat: best with dialogue-like content
-  if (hasDialogue) {
-    status.chat = "recommended";
-  } else if (isMainlyStructured) {
-    status.chat = "cautious";
-  }
-
-  // Instruct: versatile, works with most content
-  status.instruct = "recommended";
-  if (totalSnippetLen < 100) status.instruct = "available";
-
-  let hintKey = "generate.modeCheckGeneralHint";
-  if (isMainlyStructured) hintKey = "generate.modeCheckStructuredHint";
-  else if (isMainlyProse && hasDialogue) hintKey = "generate.modeCheckDialogueHint";
-  else if (isMainlyProse) hintKey = "generate.modeCheckProseHint";
-
-  return { status, hintKey };
-}
-
 export function DataPrepPage() {
   const { t } = useTranslation("dataPrep");
   const { t: tc } = useTranslation("common");
@@ -234,6 +145,7 @@ export function DataPrepPage() {
   const validationTimerRef = useRef<ReturnType<typeof setTimeout> | null>(null);
   const [segmentPreview, setSegmentPreview] = useState<SegmentPreviewResponse | null>(null);
   const [segmentPreviewLoading, setSegmentPreviewLoading] = useState(false);
+  const [genEstimate, setGenEstimate] = useState<GenerationEstimate | null>(null);
   const [previewTab, setPreviewTab] = useState<"data" | "segment">("data");
   const [autoSegment, setAutoSegment] = useState(true);
   const datasetSectionRef = useRef<HTMLDivElement>(null);
@@ -339,9 +251,32 @@ export function DataPrepPage() {
       autoGenAfterClean.current = false;
     }).then((u) => unsubs.push(u));
 
+    listen("cleaning:stopped", () => {
+      setCleaning(false);
+      setPipelineStage("idle");
+      setCleanProgress("");
+      autoGenAfterClean.current = false;
+      reloadFiles();
+    }).then((u) => unsubs.push(u));
+
     return () => { unsubs.forEach((u) => u()); };
   }, []);
 
+  // Refresh the token/time estimate when the mode, source or cleaned data changes
+  useEffect(() => {
+    if (!currentProject || !genMode) {
+      setGenEstimate(null);
+      return;
+    }
+    invoke<GenerationEstimate>("estimate_generation_cost", {
+      projectId: currentProject.id,
+      mode: genMode,
+      source: genSource,
+    })
+      .then(setGenEstimate)
+      .catch(() => setGenEstimate(null));
+  }, [currentProject?.id, genMode, genSource, _cleanedFiles]);
+
   // Initialize global generation event listeners (idempotent - only runs once)
   useEffect(() => {
     initListeners();
@@ -427,11 +362,11 @@ export function DataPrepPage() {
       // Sample file content for mode detection
       if (raw.length > 0) {
         try {
-          const samples: RawFileSample[] = await invoke("sample_raw_files", {
+          const rec: ModeRecommendation = await invoke("recommend_generation_mode", {
             projectId,
           });
           if (useProjectStore.getState().currentProject?.id !== projectId) return;
-          const result = analyzeContentForModes(samples);
+          const result = { status: rec.status, hintKey: rec.hint_key };
           setModeCapability(result);
           // Smart default: auto-select first recommended mode if user hasn't chosen one yet
           const { formGenMode } = useGenerationStore.getState();
@@ -625,7 +560,7 @@ export function DataPrepPage() {
     setDatasetPage(0);
     setExpandedDataset(null);
     const store = useGenerationStore.getState();
-    store.startGeneration();
+    store.startGeneration(currentProject.id);
     try {
       await invoke("generate_dataset", {
         projectId: currentProject.id,
@@ -649,7 +584,7 @@ export function DataPrepPage() {
 
   const handleStop = async () => {
     try {
-      await invoke("stop_generation");
+      await invoke("stop_generation", { projectId: currentProject?.id });
     } catch (e) {
       console.error("Stop failed:", e);
     }
@@ -834,7 +769,7 @@ export function DataPrepPage() {
     setPreviewTab("data"); // Ensure preview tab is active during generation to avoid panel collapse
     const store = useGenerationStore.getState();
     store.clearLogs();
-    store.startGeneration();
+    store.startGeneration(currentProject.id);
 
     try {
       await invoke("generate_dataset", {
@@ -861,6 +796,36 @@ export function DataPrepPage() {
     }
   };
 
+  const handleResumeVersion = async (version: string) => {
+    if (!currentProject || retryingVersion || generating || cleaning) return;
+    if (!acquireTask(currentProject.id, currentProject.name, "generating")) return;
+
+    setRetryingVersion(version);
+    setPipelineStage("generating");
+    setPreviewTab("data");
+    const store = useGenerationStore.getState();
+    store.clearLogs();
+    store.startGeneration(currentProject.id);
+
+    try {
+      await invoke("retry_generation", {
+        projectId: currentProject.id,
+        version,
+        lang: i18nGlobal.language,
+      });
+    } catch (e) {
+      useGenerationStore.setState({
+        generating: false,
+        genProgress: "",
+        genError: String(e),
+      });
+      setPipelineStage("idle");
+      useTaskStore.getState().releaseTask();
+    } finally {
+      setRetryingVersion(null);
+    }
+  };
+
   if (!currentProject) {
     return (
       <div className="space-y-6">
@@ -1454,6 +1419,18 @@ export function DataPrepPage() {
                     )}
                   </div>
 
+                  {genEstimate && genEstimate.segments > 0 && genSource !== "builtin" && !generating && (
+                    <p className="text-[0.6875rem] text-muted-foreground">
+                      {t("generate.estimate", {
+                        segments: genEstimate.segments,
+                        input: genEstimate.input_tokens.toLocaleString(),
+                        output: genEstimate.output_tokens.toLocaleString(),
+                      })}
+                      {genEstimate.estimated_seconds != null &&
+                        ` ${t("generate.estimateTime", { minutes: Math.max(1, Math.round(genEstimate.estimated_seconds / 60)) })}`}
+                    </p>
+                  )}
+
                   {/* Generate / Stop Buttons */}
                   {generating || cleaning ? (
                     <button
@@ -1776,6 +1753,9 @@ export function DataPrepPage() {
                                 {v.quality_scoring_enabled && v.quality_grade && (
                                   <span className={`rounded-sm border px-1.5 py-0.5 text-[9px] font-bold leading-none ${getQualityGradeTone(v.quality_grade)}`}>{`Q-${v.quality_grade.toUpperCase()}`}</span>
                                 )}
+                                {v.incomplete && (
+                                  <span className="rounded-sm border border-warning/40 bg-warning/10 px-1.5 py-0.5 text-[9px] font-medium leading-none text-warning">{t("dataset.incomplete")}</span>
+                                )}
                                 {v.failed_count > 0 && (
                                   <span className="rounded-sm border border-warning/40 bg-warning/10 px-1.5 py-0.5 text-[9px] font-medium leading-none text-warning">{t("dataset.failedCount", { count: v.failed_count })}</span>
                                 )}
@@ -1800,6 +1780,17 @@ export function DataPrepPage() {
                                       {typeof v.quality_score === "number" && <span className="text-foreground">{t("dataset.qualityScore", { score: v.quality_score.toFixed(1) })}</span>}
                                     </div>
                                   )}
+                                  {v.incomplete && (
+                                    <div className="pt-1">
+                                      <button
+                                        onClick={(e) => { e.stopPropagation(); handleResumeVersion(v.version); }}
+                                        disabled={generating || cleaning || !!retryingVersion}
+                                        className="rounded-md border border-warning/40 bg-warning/10 px-2 py-1 text-[0.6875rem] font-medium text-warning transition-colors hover:bg-warning/15 disabled:opacity-50"
+                                      >
+                                        {retryingVersion === v.version ? t("dataset.resuming") : t("dataset.resume")}
+                                      </button>
+                                    </div>
+                                  )}
                                   {v.failed_count > 0 && (
                                     <div className="pt-1">
                                       <button
@@ -18,6 +18,9 @@ interface AdapterInfo {
   created: string;
   has_weights: boolean;
   base_model: string;
+  exported?: string[];
+  resumed_from?: string | null;
+  display_name?: string | null;
 }
 
 interface OllamaPathInfo {
@@ -532,7 +535,7 @@ export function ExportPage() {
                     {selectedAdapterInfo ? (
                       <>
                         <span className="font-medium text-foreground">{selectedAdapterInfo.created}</span>
-                        <span className="ml-1.5 text-muted-foreground/50 text-sm">{selectedAdapterInfo.name.slice(0, 8)}</span>
+                        <span className="ml-1.5 text-muted-foreground/50 text-sm">{selectedAdapterInfo.display_name || selectedAdapterInfo.name.slice(0, 8)}</span>
                         {selectedAdapterInfo.base_model && (
                           <span className="ml-1.5 text-muted-foreground/40 text-sm">· {selectedAdapterInfo.base_model}</span>
                         )}
@@ -568,7 +571,7 @@ export function ExportPage() {
                             : <span className={`h-4 w-4 shrink-0 rounded-full border-2 ${canSelect ? "border-muted-foreground/70" : "border-muted-foreground/40"}`} />}
                           <div className="min-w-0 flex-1">
                             <span className={`font-medium ${canSelect ? "text-foreground" : "text-muted-foreground"}`}>{a.created}</span>
-                            <span className="ml-1.5 text-muted-foreground text-sm">{a.name.slice(0, 8)}</span>
+                            <span className="ml-1.5 text-muted-foreground text-sm">{a.display_name || a.name.slice(0, 8)}</span>
                             {a.base_model && <span className="ml-1.5 text-muted-foreground text-sm">· {a.base_model}</span>}
                             {!a.has_weights && (
                               <span className="ml-1.5 rounded bg-warning/20 px-1 py-0.5 text-[0.625rem] text-warning">{t("noWeights")}</span>
@@ -26,8 +26,17 @@ interface AppConfigResponse {
   lmstudio_installed: boolean;
   lmstudio_api_url: string;
   hf_source: string;
+  hf_endpoint: string | null;
   ollama_bin_path: string;
   ollama_bin_custom: boolean;
+  prevent_sleep: boolean;
+  base_dir: string;
+  venv_ready: boolean;
+  notify_on_complete: boolean;
+  gguf_dirs: string[];
+  default_export: { format: string; quantization: string | null; tag: string | null } | null;
+  aliases: Record<string, string>;
+  autoclean_after_export: boolean;
 }
 
 interface OllamaPathInfo {
@@ -36,6 +45,7 @@ interface OllamaPathInfo {
   configured_path: string | null;
   configured_has_layout: boolean;
   configured_model_count: number;
+  configured_writable: boolean;
 }
 
 function formatBytes(bytes: number): string {
@@ -140,7 +150,16 @@ export function SettingsPage() {
     const selected = await dialogOpen({ directory: true, multiple: false, title: t("environment.browseModelDir", { source }) });
     if (selected && typeof selected === "string") {
       if (source === "export") {
-        await invoke("set_export_path", { path: selected });
+        const check = await invoke<{ ok: boolean; reason: string | null; message: string | null }>(
+          "set_export_path", { path: selected },
+        );
+        if (!check.ok) {
+          setStorageMsg({
+            type: "warning",
+            text: t("storage.exportPathNotWritable", { error: check.message ?? check.reason ?? "" }),
+          });
+          return;
+        }
       } else {
         await invoke("set_model_source_path", { source, path: selected });
       }
@@ -640,6 +659,11 @@ export function SettingsPage() {
             </button>
           ))}
         </div>
+        {config?.hf_endpoint && (
+          <p className="text-xs text-muted-foreground">
+            {t("downloadSource.effectiveEndpoint", { endpoint: config.hf_endpoint })}
+          </p>
+        )}
         {config?.hf_source === "modelscope" && (
           <p className="text-xs text-warning/80">
             ⚠ {t("downloadSource.modelscopeWarn")}
@@ -861,6 +885,9 @@ export function SettingsPage() {
                   {ollamaPathInfo.configured_path && ollamaPathInfo.configured_has_layout && ollamaPathInfo.configured_model_count === 0 && (
                     <p className="text-warning">{t("storage.ollamaPathNoModelHint")}</p>
                   )}
+                  {ollamaPathInfo.configured_path && !ollamaPathInfo.configured_writable && (
+                    <p className="text-warning">{t("storage.ollamaPathNotWritable")}</p>
+                  )}
                 </div>
               )}
             </div>
@@ -3,7 +3,7 @@ import { useTranslation } from "react-i18next";
 import { Tooltip, TooltipContent, TooltipTrigger } from "@/components/ui/tooltip";
 import { invoke } from "@tauri-apps/api/core";
 import { listen, type UnlistenFn } from "@tauri-apps/api/event";
-import { Trash2, Send, FolderOpen, CheckCircle2, Circle, ChevronDown, ChevronRight, MessageSquare, GitCompare, Info, Settings } from "lucide-react";
+import { Trash2, Send, Square, FolderOpen, CheckCircle2, Circle, ChevronDown, ChevronRight, MessageSquare, GitCompare, Info, Settings } from "lucide-react";
 import { useProjectStore } from "@/stores/projectStore";
 import { useTestingStore } from "@/stores/testingStore";
 
@@ -74,6 +74,7 @@ export function TestingPage() {
   const [showResetDialog, setShowResetDialog] = useState(false);
   const chatRef = useRef<HTMLDivElement>(null);
   const inputRef = useRef<HTMLTextAreaElement | null>(null);
+  const chatRequestIdRef = useRef<string | null>(null);
 
   const selectAdapter = (adapter: AdapterInfo | null) => {
     if (adapter) {
@@ -90,10 +91,10 @@ export function TestingPage() {
   const loadAdapters = async () => {
     if (!currentProject) return;
     try {
-      const list = await invoke<AdapterInfo[]>("list_adapters", {
+      const withWeights = await invoke<AdapterInfo[]>("list_adapters", {
         projectId: currentProject.id,
+        onlyComplete: true,
       });
-      const withWeights = list.filter((a) => a.has_weights);
       setAdapters(withWeights);
       // Auto-select the latest adapter with weights if none selected for this project
       if (withWeights.length > 0 && !selectedAdapter) {
@@ -152,6 +153,7 @@ export function TestingPage() {
       let timeoutId: number | null = null;
       let offResponse: UnlistenFn | null = null;
       let offError: UnlistenFn | null = null;
+      let offStopped: UnlistenFn | null = null;
 
       const done = (payload: { response?: string; error?: string }) => {
         if (finished) return;
@@ -162,6 +164,7 @@ export function TestingPage() {
         }
         if (offResponse) offResponse();
         if (offError) offError();
+        if (offStopped) offStopped();
 
         const durationMs = Math.max(1, Math.round(performance.now() - startedAt));
         const response = payload.response ?? "";
@@ -186,10 +189,15 @@ export function TestingPage() {
           if ((e.payload.request_id || "") !== requestId) return;
           done({ error: e.payload.message || t("ab.errorUnknown") });
         }),
+        listen<{ request_id?: string }>("inference:stopped", (e) => {
+          if ((e.payload.request_id || "") !== requestId) return;
+          done({ error: t("chat.stopped") });
+        }),
       ])
-        .then(([u1, u2]) => {
+        .then(([u1, u2, u3]) => {
           offResponse = u1;
           offError = u2;
+          offStopped = u3;
 
           timeoutId = window.setTimeout(() => {
             done({ error: t("ab.errorTimeout") });
@@ -233,12 +241,14 @@ export function TestingPage() {
     setInput("");
     setIsGenerating(true);
 
+    const requestId = createRequestId();
+    chatRequestIdRef.current = requestId;
     try {
       const result = await runInference({
         prompt: userMsg.content,
         adapterPath: selectedAdapter || null,
         messages: conversationMessages,
-        requestId: createRequestId(),
+        requestId,
       });
 
       if (result.error) {
@@ -249,6 +259,7 @@ export function TestingPage() {
     } catch (err) {
       addMessage({ role: "assistant", content: `Error: ${String(err)}` });
     } finally {
+      chatRequestIdRef.current = null;
       setIsGenerating(false);
       window.requestAnimationFrame(() => inputRef.current?.focus());
     }
@@ -546,10 +557,20 @@ export function TestingPage() {
           ))
         )}
         {isGenerating && (
-          <div className="flex justify-start">
+          <div className="flex items-center justify-start gap-2">
             <div className="rounded-lg bg-muted px-4 py-2.5 text-sm text-muted-foreground">
               {t("chat.thinking")}
             </div>
+            <button
+              onClick={() => {
+                const requestId = chatRequestIdRef.current;
+                if (requestId) invoke("stop_inference", { requestId }).catch(() => {});
+              }}
+              className="flex items-center gap-1 rounded-md border border-border px-2 py-1 text-xs text-muted-foreground transition-colors hover:bg-accent hover:text-foreground"
+            >
+              <Square size={11} />
+              {t("chat.stop")}
+            </button>
           </div>
         )}
       </div>
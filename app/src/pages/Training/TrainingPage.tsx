@@ -329,6 +329,7 @@ interface DatasetVersionInfo {
   mode: string;
   source: string;
   model: string;
+  incomplete?: boolean;
 }
 
 export function TrainingPage() {
@@ -480,11 +481,11 @@ export function TrainingPage() {
       );
       if (useProjectStore.getState().currentProject?.id !== projectId) return;
       setDatasetVersions(versions);
-      // Auto-select latest (first) version if none selected or current selection no longer exists
+      // Auto-select the latest complete version if none selected or current selection no longer exists
       if (versions.length > 0) {
         const currentStillExists = selectedVersion && versions.some((v) => v.version === selectedVersion);
         if (!currentStillExists) {
-          setSelectedVersion(versions[0].version);
+          setSelectedVersion((versions.find((v) => !v.incomplete) ?? versions[0]).version);
         }
       } else {
         setSelectedVersion("");
@@ -13,8 +13,16 @@ export async function generateDataset(
   return invoke("generate_dataset", { projectId, model, mode, lang });
 }
 
+export interface DatasetPreview {
+  items: Record<string, unknown>[];
+  total: number;
+  offset: number;
+  split: "train" | "valid";
+}
+
 export async function getDatasetPreview(
-  projectId: string
-): Promise<Record<string, unknown>[]> {
-  return invoke("get_dataset_preview", { projectId });
+  projectId: string,
+  options: { version?: string; offset?: number; count?: number; split?: "train" | "valid" } = {}
+): Promise<DatasetPreview> {
+  return invoke("get_dataset_preview", { projectId, ...options });
 }
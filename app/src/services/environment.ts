@@ -6,6 +6,8 @@ export interface EnvironmentStatus {
   mlx_lm_version: string | null;
   mlx_lm_version_supported: boolean;
   mlx_lm_min_version: string;
+  python_version: string | null;
+  python_version_supported: boolean;
   chip: string;
   memory_gb: number;
   os_version: string;
@@ -16,6 +18,7 @@ export interface EnvironmentStatus {
 export interface OllamaStatus {
   installed: boolean;
   running: boolean;
+  version?: string | null;
 }
 
 export async function checkEnvironment(): Promise<EnvironmentStatus> {
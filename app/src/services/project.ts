@@ -22,11 +22,11 @@ export async function createProject(
   return project;
 }
 
-export async function listProjects(): Promise<Project[]> {
-  const db = await getDb();
-  return db.select<Project[]>(
-    "SELECT id, name, path, status, model_path, created_at, updated_at FROM projects ORDER BY created_at DESC"
-  );
+// Archived projects are left out unless `includeArchived` is set.
+export async function listProjects(includeArchived = false): Promise<Project[]> {
+  // The command reads the database the frontend loads, so make sure it is open.
+  await getDb();
+  return invoke<Project[]>("list_projects", { includeArchived });
 }
 
 export async function deleteProject(id: string): Promise<void> {
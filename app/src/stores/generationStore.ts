@@ -45,6 +45,8 @@ export interface GenFileEntry {
 
 interface GenerationState {
   generating: boolean;
+  /** Project the running generation belongs to; events for other projects are ignored. */
+  genProjectId: string | null;
   genProgress: string;
   genStep: number;
   genTotal: number;
@@ -69,7 +71,7 @@ interface GenerationState {
   formEnableQualityScoring: boolean;
 
   // Actions
-  startGeneration: () => void;
+  startGeneration: (projectId?: string) => void;
   stopGeneration: () => void;
   resetGeneration: () => void;
   clearLogs: () => void;
@@ -90,6 +92,7 @@ interface GenerationState {
 
 export const useGenerationStore = create<GenerationState>((set, get) => ({
   generating: false,
+  genProjectId: null,
   genProgress: "",
   genStep: 0,
   genTotal: 0,
@@ -118,8 +121,8 @@ export const useGenerationStore = create<GenerationState>((set, get) => ({
 
   setGenFiles: (files) => set({ genFiles: files, genCurrentFileIdx: 0 }),
 
-  startGeneration: () =>
-    set({ generating: true, genStopped: false, genProgress: "", genError: "", ollamaPathMismatch: false, genCurrentFileIdx: 0, genSuccessCount: 0, genFailCount: 0 }),
+  startGeneration: (projectId) =>
+    set({ generating: true, genProjectId: projectId ?? null, genStopped: false, genProgress: "", genError: "", ollamaPathMismatch: false, genCurrentFileIdx: 0, genSuccessCount: 0, genFailCount: 0 }),
 
   stopGeneration: () =>
     set({
@@ -179,11 +182,16 @@ export const useGenerationStore = create<GenerationState>((set, get) => ({
     set({ _listenersReady: true });
 
     const unlistens: UnlistenFn[] = [];
+    // Payloads carry project_id; drop those from another project's run.
+    const isOtherProject = (payload: { project_id?: string } | null | undefined) => {
+      const current = get().genProjectId;
+      return !!current && !!payload?.project_id && payload.project_id !== current;
+    };
 
-    const u1 = await listen<{ step?: number; total?: number; desc?: string }>(
+    const u1 = await listen<{ step?: number; total?: number; desc?: string; project_id?: string }>(
       "dataset:progress",
       (e) => {
-        if (!get().generating) return;
+        if (!get().generating || isOtherProject(e.payload)) return;
         const step = e.payload.step ?? get().genStep;
         const total = e.payload.total ?? get().genTotal;
         // Estimate which file we're currently processing based on cumulative size ratio
@@ -221,10 +229,10 @@ export const useGenerationStore = create<GenerationState>((set, get) => ({
     );
     unlistens.push(u1);
 
-    const u2 = await listen<{ message?: string; line?: string }>(
+    const u2 = await listen<{ message?: string; line?: string; project_id?: string }>(
       "dataset:log",
       (e) => {
-        if (!get().generating) return;
+        if (!get().generating || isOtherProject(e.payload)) return;
         const msg = e.payload.message || e.payload.line || "";
         if (msg) {
           const { successCount, failCount } = parseGenerationCounts(
@@ -242,8 +250,8 @@ export const useGenerationStore = create<GenerationState>((set, get) => ({
     );
     unlistens.push(u2);
 
-    const u2v = await listen<{ version?: string }>("dataset:version", (e) => {
-      if (!get().generating) return;
+    const u2v = await listen<{ version?: string; project_id?: string }>("dataset:version", (e) => {
+      if (!get().generating || isOtherProject(e.payload)) return;
       const vid = e.payload.version;
       if (vid) {
         set({ newVersionIds: [vid] });
@@ -251,8 +259,8 @@ export const useGenerationStore = create<GenerationState>((set, get) => ({
     });
     unlistens.push(u2v);
 
-    const u3 = await listen("dataset:complete", () => {
-      if (!get().generating) return;
+    const u3 = await listen<{ project_id?: string }>("dataset:complete", (e) => {
+      if (!get().generating || isOtherProject(e.payload)) return;
       const successCount = get().genSuccessCount;
       set({
         generating: false,
@@ -275,8 +283,8 @@ export const useGenerationStore = create<GenerationState>((set, get) => ({
     });
     unlistens.push(u3);
 
-    const u4 = await listen<{ message?: string; is_path_mismatch?: boolean }>("dataset:error", (e) => {
-      if (!get().generating) return;
+    const u4 = await listen<{ message?: string; is_path_mismatch?: boolean; project_id?: string }>("dataset:error", (e) => {
+      if (!get().generating || isOtherProject(e.payload)) return;
       const errMsg = e.payload.message || "Generation failed";
       set({
         generating: false,
@@ -295,7 +303,8 @@ export const useGenerationStore = create<GenerationState>((set, get) => ({
     });
     unlistens.push(u4);
 
-    const u5 = await listen("dataset:stopped", () => {
+    const u5 = await listen<{ project_id?: string }>("dataset:stopped", (e) => {
+      if (isOtherProject(e.payload)) return;
       set({
         generating: false,
         genStopped: true,
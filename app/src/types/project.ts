@@ -13,6 +13,7 @@ export interface Project {
   model_path: string | null;
   created_at: string;
   updated_at: string;
+  archived?: boolean;
 }
 
 export interface CreateProjectInput {